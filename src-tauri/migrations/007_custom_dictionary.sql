@@ -0,0 +1,6 @@
+-- User-maintained words accepted by the spell checker
+
+CREATE TABLE IF NOT EXISTS custom_dictionary (
+    word TEXT PRIMARY KEY, -- Stored lowercased
+    created_at INTEGER NOT NULL
+);
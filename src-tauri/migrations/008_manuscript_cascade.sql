@@ -0,0 +1,11 @@
+-- Cascade manuscript deletes to scenes
+-- Single manuscript mode dropped scenes.manuscript_id, so there is no foreign key
+-- to cascade through; every scene belongs to the one manuscript. Rows that refer
+-- to scenes follow through their own ON DELETE clauses once the connection has
+-- PRAGMA foreign_keys = ON.
+
+CREATE TRIGGER IF NOT EXISTS manuscripts_delete_cascade
+    AFTER DELETE ON manuscripts
+BEGIN
+    DELETE FROM scenes;
+END;
@@ -0,0 +1,9 @@
+-- Structural beats (inciting incident, midpoint, climax...) tagged on scenes.
+-- A scene carries at most one beat; the row goes with its scene.
+
+CREATE TABLE IF NOT EXISTS story_beats (
+    scene_id TEXT PRIMARY KEY,
+    beat_type TEXT NOT NULL, -- snake_case BeatType
+    updated_at INTEGER NOT NULL,
+    FOREIGN KEY(scene_id) REFERENCES scenes(id) ON DELETE CASCADE
+);
@@ -0,0 +1,17 @@
+-- Setups planted in a scene and the scene that pays them off. Offsets are
+-- character offsets into the planting scene's raw_text.
+
+CREATE TABLE IF NOT EXISTS plants (
+    id TEXT PRIMARY KEY,
+    scene_id TEXT NOT NULL,
+    start_offset INTEGER NOT NULL,
+    end_offset INTEGER NOT NULL,
+    description TEXT NOT NULL,
+    payoff_scene_id TEXT, -- NULL until the setup is paid off
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL,
+    FOREIGN KEY(scene_id) REFERENCES scenes(id) ON DELETE CASCADE,
+    FOREIGN KEY(payoff_scene_id) REFERENCES scenes(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_plants_scene ON plants(scene_id);
@@ -0,0 +1,14 @@
+-- Plot events extracted from each scene, for timeline and continuity views.
+-- `sequence` orders events within their scene; scenes order the rest.
+
+CREATE TABLE IF NOT EXISTS events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    scene_id TEXT NOT NULL,
+    sequence INTEGER NOT NULL,
+    description TEXT NOT NULL,
+    characters TEXT NOT NULL DEFAULT '[]', -- JSON array of character names
+    time_marker TEXT,
+    created_at INTEGER NOT NULL,
+    FOREIGN KEY(scene_id) REFERENCES scenes(id) ON DELETE CASCADE,
+    UNIQUE(scene_id, sequence)
+);
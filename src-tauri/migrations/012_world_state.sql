@@ -0,0 +1,13 @@
+-- Facts about characters and the world as they stand at a scene ("Ana.injury",
+-- "sword.location"). A key's rows in manuscript order make up its timeline.
+
+CREATE TABLE IF NOT EXISTS world_state (
+    scene_id TEXT NOT NULL,
+    state_key TEXT NOT NULL,
+    value TEXT NOT NULL,
+    updated_at INTEGER NOT NULL,
+    PRIMARY KEY (scene_id, state_key),
+    FOREIGN KEY(scene_id) REFERENCES scenes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_world_state_key ON world_state(state_key);
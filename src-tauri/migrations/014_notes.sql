@@ -0,0 +1,12 @@
+-- Free-form notes shown in the Floating Notes window, one set per manuscript
+
+CREATE TABLE IF NOT EXISTS notes (
+    id TEXT PRIMARY KEY,
+    manuscript_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    body TEXT NOT NULL DEFAULT '',
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_notes_manuscript ON notes(manuscript_id, updated_at);
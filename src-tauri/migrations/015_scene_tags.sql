@@ -0,0 +1,12 @@
+-- Free-form labels on scenes ("needs revision", "flashback"). Tags compare
+-- case-insensitively; the first spelling used on a scene is kept.
+
+CREATE TABLE IF NOT EXISTS scene_tags (
+    scene_id TEXT NOT NULL,
+    tag TEXT NOT NULL COLLATE NOCASE,
+    created_at INTEGER NOT NULL,
+    PRIMARY KEY(scene_id, tag),
+    FOREIGN KEY(scene_id) REFERENCES scenes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_scene_tags_tag ON scene_tags(tag);
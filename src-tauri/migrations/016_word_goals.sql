@@ -0,0 +1,19 @@
+-- Word-count goals. A project goal is measured against the manuscript total; a
+-- daily goal against words added since the day's first write.
+
+CREATE TABLE IF NOT EXISTS word_goals (
+    manuscript_id TEXT NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('project', 'daily')),
+    target INTEGER NOT NULL CHECK (target > 0),
+    reached_on TEXT, -- Local date the goal was last reached, for goal-reached events
+    updated_at INTEGER NOT NULL,
+    PRIMARY KEY(manuscript_id, kind)
+);
+
+-- The manuscript total at the start of each local day
+CREATE TABLE IF NOT EXISTS word_count_snapshots (
+    manuscript_id TEXT NOT NULL,
+    day TEXT NOT NULL,
+    starting_word_count INTEGER NOT NULL,
+    PRIMARY KEY(manuscript_id, day)
+);
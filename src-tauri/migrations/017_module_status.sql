@@ -0,0 +1,15 @@
+-- Which analysis modules need reprocessing for each scene. 006 sketched this
+-- table but was never registered; this version also goes with its scene.
+
+CREATE TABLE IF NOT EXISTS module_status (
+    scene_id TEXT PRIMARY KEY,
+    events_v TEXT, events_dirty INTEGER DEFAULT 1,
+    plants_v TEXT, plants_dirty INTEGER DEFAULT 1,
+    state_v  TEXT, state_dirty  INTEGER DEFAULT 1,
+    beats_v  TEXT, beats_dirty  INTEGER DEFAULT 1,
+    last_processed TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY(scene_id) REFERENCES scenes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_module_status_dirty
+ON module_status(events_dirty, plants_dirty, state_dirty, beats_dirty);
@@ -0,0 +1,1652 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use sqlx::SqlitePool;
+use tauri::{AppHandle, State};
+use crate::commands::validate_scene_id;
+use crate::db::{DatabaseService, Scene, SceneEvent, SINGLETON_MANUSCRIPT_ID};
+use crate::error::{AppError, AppResult};
+use crate::text::{sentences, strip_html, word_count, words};
+
+// Readability metrics over a body of text
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReadabilityMetrics {
+    pub word_count: u32,
+    pub sentence_count: u32,
+    pub syllable_count: u32,
+    pub flesch_reading_ease: f64,
+    pub flesch_kincaid_grade: f64,
+    pub average_sentence_length: f64,
+    pub average_syllables_per_word: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChapterReadability {
+    pub chapter_number: Option<i32>,
+    pub scene_ids: Vec<String>,
+    pub metrics: ReadabilityMetrics,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadabilityReport {
+    pub manuscript_id: String,
+    pub overall: ReadabilityMetrics,
+    pub chapters: Vec<ChapterReadability>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DialogueStats {
+    pub dialogue_words: u32,
+    pub narration_words: u32,
+    pub dialogue_percentage: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneDialogue {
+    pub scene_id: String,
+    pub title: Option<String>,
+    pub stats: DialogueStats,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DialogueReport {
+    pub manuscript_id: String,
+    pub overall: DialogueStats,
+    pub scenes: Vec<SceneDialogue>,
+}
+
+impl DialogueStats {
+    fn new(dialogue_words: u32, narration_words: u32) -> Self {
+        let total = dialogue_words + narration_words;
+        let dialogue_percentage = if total == 0 {
+            0.0
+        } else {
+            round2(dialogue_words as f64 * 100.0 / total as f64)
+        };
+
+        Self { dialogue_words, narration_words, dialogue_percentage }
+    }
+}
+
+// Sentence lengths in words, in reading order, for sparkline rendering
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenePacing {
+    pub scene_id: String,
+    pub title: Option<String>,
+    pub sentence_lengths: Vec<u32>,
+    pub mean: f64,
+    pub std_dev: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PacingReport {
+    pub manuscript_id: String,
+    pub scenes: Vec<ScenePacing>,
+    pub overall_mean: f64,
+    pub overall_std_dev: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PovCharacterStats {
+    pub name: String,
+    pub scene_count: u32,
+    pub word_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PovSwitch {
+    pub from_scene_id: String,
+    pub to_scene_id: String,
+    pub from_pov: String,
+    pub to_pov: String,
+    pub within_chapter: bool, // Mid-chapter switches are the usual head-hopping signal
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PovReport {
+    pub manuscript_id: String,
+    pub characters: Vec<PovCharacterStats>,
+    pub switches: Vec<PovSwitch>,
+    pub unassigned_scene_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineEntry {
+    pub scene_id: String,
+    pub title: Option<String>,
+    pub index_in_manuscript: u32,
+    pub chapter_number: Option<i32>,
+    pub location: Option<String>,
+    pub time_marker: Option<String>,
+    pub missing_time_marker: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutlineNodeKind {
+    Chapter,
+    Scene,
+}
+
+// One entry in the document outline: chapters hold their scenes, and scenes
+// outside any chapter sit at the top level
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutlineNode {
+    pub kind: OutlineNodeKind,
+    pub id: String, // Scene id, or "chapter-N" for chapters
+    pub title: Option<String>,
+    pub chapter_number: Option<i32>,
+    pub index_in_manuscript: u32, // First scene's index for chapters
+    pub word_count: u32,
+    pub start_word_offset: u32, // Words in the manuscript before this node
+    pub pov_character: Option<String>,
+    pub location: Option<String>,
+    pub tags: Vec<String>, // Scene tags; always empty for chapters
+    pub children: Vec<OutlineNode>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProseFlagCategory {
+    Adverb,
+    FilterWord,
+    PassiveVoice,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProseFlag {
+    pub category: ProseFlagCategory,
+    pub word: String, // Matched text; the whole phrase for passive voice
+
+    pub start_offset: u32, // Character offsets into the stored scene content
+    pub end_offset: u32,
+}
+
+// A revision marker left in the draft, e.g. "TODO: fix pacing" or
+// "[[check timeline]]"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TodoItem {
+    pub scene_id: String,
+    pub marker: String, // The configured marker that matched
+    pub text: String,   // What the note says, without the marker
+
+    pub start_offset: u32, // Character offsets into the stored scene content
+    pub end_offset: u32,
+}
+
+// Heuristic scores (0-100) for the manuscript's opening scene
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpeningAnalysis {
+    pub manuscript_id: String,
+    pub scene_id: String,
+    pub first_line: String,
+    pub first_line_punch: u32, // Short first sentences without stock openers score high
+    pub dialogue_balance: u32, // Some speech beats pure exposition or wall-to-wall talk
+    pub sentence_variety: u32, // Spread of sentence lengths relative to their mean
+    pub pov_presence: u32,     // POV character assigned, and named early on
+    pub opening_strength: u32,
+    pub hook_effectiveness: u32,
+}
+
+// A marker either runs to the end of the line ("TODO") or, when written with
+// "..." in it, wraps the note between its two halves ("[[...]]")
+pub const DEFAULT_TODO_MARKERS: &[&str] = &["TODO", "FIXME", "[note]", "[[...]]"];
+
+pub const DEFAULT_FILTER_WORDS: &[&str] = &[
+    "saw", "see", "seen", "heard", "hear", "felt", "feel", "noticed", "notice",
+    "watched", "realized", "wondered", "thought", "seemed", "knew", "decided",
+];
+
+// Common "-ly" words that are not adverbs
+const NON_ADVERB_LY_WORDS: &[&str] = &[
+    "only", "family", "reply", "early", "supply", "belly", "holy", "ugly", "lonely",
+    "friendly", "likely", "lovely", "daily", "silly", "jelly", "bully", "rally", "ally",
+    "italy", "july", "fly", "apply", "lily", "assembly", "anomaly", "monopoly", "elderly",
+    "costly", "lively", "deadly", "curly", "chilly", "hilly", "wily", "oily", "sly",
+];
+
+const PASSIVE_AUXILIARIES: &[&str] = &[
+    "am", "is", "are", "was", "were", "be", "been", "being",
+    "get", "gets", "got", "gotten", "getting",
+];
+
+// Words allowed between the auxiliary and the participle ("was not seen")
+const PASSIVE_INTERVENING: &[&str] = &["not", "never", "also", "just", "still", "always", "often", "being"];
+
+const IRREGULAR_PARTICIPLES: &[&str] = &[
+    "beaten", "bitten", "born", "bought", "bound", "broken", "brought", "built", "caught",
+    "chosen", "cut", "done", "drawn", "driven", "eaten", "fed", "forbidden", "forgiven",
+    "forgotten", "found", "frozen", "given", "heard", "held", "hidden", "hit", "hung",
+    "hurt", "kept", "known", "laid", "led", "left", "lost", "made", "meant", "met",
+    "paid", "put", "read", "ridden", "said", "seen", "sent", "set", "shaken", "shot",
+    "shown", "shut", "sold", "spent", "spoken", "stolen", "struck", "swept", "taken",
+    "taught", "thrown", "told", "torn", "understood", "won", "worn", "woven", "written",
+];
+
+// "-ed" words that usually describe a state rather than an action, or that are
+// not participles at all
+const ADJECTIVAL_PARTICIPLES: &[&str] = &[
+    "tired", "excited", "interested", "bored", "scared", "worried", "married", "pleased",
+    "surprised", "used", "supposed", "ashamed", "embarrassed", "exhausted", "confused",
+    "annoyed", "delighted", "satisfied", "frightened", "determined", "amazed", "relieved",
+    "concerned", "involved", "related", "prepared", "dressed", "finished", "located",
+    "convinced", "disappointed", "terrified", "thrilled", "shocked", "stunned", "armed",
+    "sacred", "naked", "wicked", "hundred", "indeed", "speed", "bleed",
+];
+
+// Raw counts, kept separate so chapter and manuscript totals can be summed
+#[derive(Debug, Clone, Copy, Default)]
+struct TextCounts {
+    words: u32,
+    sentences: u32,
+    syllables: u32,
+}
+
+impl TextCounts {
+    fn from_text(text: &str) -> Self {
+        let word_list = words(text);
+        Self {
+            words: word_list.len() as u32,
+            sentences: sentences(text).len() as u32,
+            syllables: word_list.iter().map(|(_, w)| count_syllables(w)).sum(),
+        }
+    }
+
+    fn add(&mut self, other: TextCounts) {
+        self.words += other.words;
+        self.sentences += other.sentences;
+        self.syllables += other.syllables;
+    }
+
+    fn metrics(&self) -> ReadabilityMetrics {
+        if self.words == 0 || self.sentences == 0 {
+            return ReadabilityMetrics::default();
+        }
+
+        let words_per_sentence = self.words as f64 / self.sentences as f64;
+        let syllables_per_word = self.syllables as f64 / self.words as f64;
+
+        ReadabilityMetrics {
+            word_count: self.words,
+            sentence_count: self.sentences,
+            syllable_count: self.syllables,
+            flesch_reading_ease: round2(206.835 - 1.015 * words_per_sentence - 84.6 * syllables_per_word),
+            flesch_kincaid_grade: round2(0.39 * words_per_sentence + 11.8 * syllables_per_word - 15.59),
+            average_sentence_length: round2(words_per_sentence),
+            average_syllables_per_word: round2(syllables_per_word),
+        }
+    }
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+// Vowel-group heuristic, close enough for readability formulas
+pub fn count_syllables(word: &str) -> u32 {
+    let letters: Vec<char> = word
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .collect();
+
+    if letters.is_empty() {
+        return 0;
+    }
+    if letters.len() <= 3 {
+        return 1;
+    }
+
+    let is_vowel = |c: char| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+    let mut count = 0;
+    let mut previous_vowel = false;
+    for &c in &letters {
+        let vowel = is_vowel(c);
+        if vowel && !previous_vowel {
+            count += 1;
+        }
+        previous_vowel = vowel;
+    }
+
+    // Silent trailing 'e' ("make"), but not a syllabic "-le" ("table")
+    let n = letters.len();
+    if letters[n - 1] == 'e' && !(letters[n - 2] == 'l' && !is_vowel(letters[n - 3])) && count > 1 {
+        count -= 1;
+    }
+    // "-es"/"-ed" endings are usually not voiced ("makes", "jumped")
+    if (letters[n - 1] == 's' || letters[n - 1] == 'd')
+        && letters[n - 2] == 'e'
+        && !matches!(letters[n - 3], 't' | 'd' | 's' | 'c' | 'x' | 'z' | 'g')
+        && count > 1
+    {
+        count -= 1;
+    }
+
+    count.max(1)
+}
+
+// Byte ranges of quoted speech in stripped text. Straight quotes toggle, curly
+// quotes open and close explicitly. An unclosed quote ends at the paragraph break,
+// matching the convention of omitting closing quotes on continued speech.
+pub fn dialogue_spans(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut open: Option<usize> = None;
+
+    for (i, ch) in text.char_indices() {
+        match (ch, open) {
+            ('"', None) | ('“', None) => open = Some(i),
+            ('"', Some(start)) | ('”', Some(start)) => {
+                spans.push((start, i + ch.len_utf8()));
+                open = None;
+            }
+            ('\n', Some(start)) => {
+                spans.push((start, i));
+                open = None;
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(start) = open {
+        spans.push((start, text.len()));
+    }
+
+    spans
+}
+
+pub fn dialogue_stats_for_html(html: &str) -> DialogueStats {
+    let text = strip_html(html).text;
+    let spans = dialogue_spans(&text);
+
+    let mut dialogue = 0;
+    let mut narration = 0;
+    for (offset, _) in words(&text) {
+        if spans.iter().any(|&(start, end)| offset > start && offset < end) {
+            dialogue += 1;
+        } else {
+            narration += 1;
+        }
+    }
+
+    DialogueStats::new(dialogue, narration)
+}
+
+pub fn build_dialogue_report(manuscript_id: &str, scenes: &[Scene]) -> DialogueReport {
+    let scenes: Vec<SceneDialogue> = scenes
+        .iter()
+        .map(|scene| SceneDialogue {
+            scene_id: scene.id.clone(),
+            title: scene.title.clone(),
+            stats: dialogue_stats_for_html(&scene.raw_text),
+        })
+        .collect();
+
+    let dialogue = scenes.iter().map(|s| s.stats.dialogue_words).sum();
+    let narration = scenes.iter().map(|s| s.stats.narration_words).sum();
+
+    DialogueReport {
+        manuscript_id: manuscript_id.to_string(),
+        overall: DialogueStats::new(dialogue, narration),
+        scenes,
+    }
+}
+
+fn is_ly_adverb(lower: &str) -> bool {
+    lower.chars().count() > 4 && lower.ends_with("ly") && !NON_ADVERB_LY_WORDS.contains(&lower)
+}
+
+pub fn find_prose_crutches(html: &str, filter_words: &[String]) -> Vec<ProseFlag> {
+    let stripped = strip_html(html);
+    let filter_words: Vec<String> = filter_words.iter().map(|w| w.trim().to_lowercase()).collect();
+
+    words(&stripped.text)
+        .into_iter()
+        .filter_map(|(offset, word)| {
+            let lower = word.to_lowercase();
+            let category = if filter_words.contains(&lower) {
+                ProseFlagCategory::FilterWord
+            } else if is_ly_adverb(&lower) {
+                ProseFlagCategory::Adverb
+            } else {
+                return None;
+            };
+
+            let (start, end) = stripped.source_range(offset, offset + word.len());
+            Some(ProseFlag {
+                category,
+                word: word.to_string(),
+                start_offset: start as u32,
+                end_offset: end as u32,
+            })
+        })
+        .collect()
+}
+
+fn is_past_participle(lower: &str) -> bool {
+    IRREGULAR_PARTICIPLES.contains(&lower)
+        || (lower.chars().count() > 3 && lower.ends_with("ed") && !ADJECTIVAL_PARTICIPLES.contains(&lower))
+}
+
+// Heuristic "to be"/"to get" + past participle detection within each sentence.
+// Adjectival participles ("she was tired") are skipped unless an agent follows
+// ("she was tired by the climb").
+pub fn find_passive_voice(html: &str) -> Vec<ProseFlag> {
+    let stripped = strip_html(html);
+    let mut flags = Vec::new();
+
+    for (sentence_offset, sentence) in sentences(&stripped.text) {
+        let sentence_words = words(sentence);
+        let mut covered_until = 0;
+
+        for (i, &(aux_offset, aux)) in sentence_words.iter().enumerate() {
+            // "was being watched" is one phrase, not two
+            if aux_offset < covered_until {
+                continue;
+            }
+            if !PASSIVE_AUXILIARIES.contains(&aux.to_lowercase().as_str()) {
+                continue;
+            }
+
+            // Skip up to two intervening adverbs
+            let mut j = i + 1;
+            while j < sentence_words.len() && j <= i + 2 {
+                let lower = sentence_words[j].1.to_lowercase();
+                if PASSIVE_INTERVENING.contains(&lower.as_str()) || is_ly_adverb(&lower) {
+                    j += 1;
+                } else {
+                    break;
+                }
+            }
+
+            let Some(&(participle_offset, participle)) = sentence_words.get(j) else {
+                continue;
+            };
+            let lower = participle.to_lowercase();
+            let followed_by_agent = sentence_words
+                .get(j + 1)
+                .is_some_and(|(_, w)| w.eq_ignore_ascii_case("by"));
+
+            if !is_past_participle(&lower)
+                && !(followed_by_agent && ADJECTIVAL_PARTICIPLES.contains(&lower.as_str()))
+            {
+                continue;
+            }
+
+            covered_until = participle_offset + participle.len();
+            let phrase_start = sentence_offset + aux_offset;
+            let phrase_end = sentence_offset + participle_offset + participle.len();
+            let (start, end) = stripped.source_range(phrase_start, phrase_end);
+            flags.push(ProseFlag {
+                category: ProseFlagCategory::PassiveVoice,
+                word: stripped.text[phrase_start..phrase_end].to_string(),
+                start_offset: start as u32,
+                end_offset: end as u32,
+            });
+        }
+    }
+
+    flags
+}
+
+// Markers that start or end with a letter only match as whole words, so
+// "TODOS" isn't a TODO. Overlapping matches keep the earliest.
+pub fn find_todos(scene: &Scene, markers: &[String]) -> Vec<TodoItem> {
+    let stripped = strip_html(&scene.raw_text);
+    let text = stripped.text.as_str();
+    let mut matches: Vec<(usize, usize, &str, &str)> = Vec::new();
+
+    for marker in markers.iter().map(|m| m.trim()).filter(|m| !m.is_empty()) {
+        let (open, close) = match marker.split_once("...") {
+            Some((open, close)) if !open.is_empty() && !close.is_empty() => (open, Some(close)),
+            _ => (marker, None),
+        };
+
+        for (start, _) in text.match_indices(open) {
+            let after_open = start + open.len();
+            let boundary_before = !open.starts_with(char::is_alphanumeric)
+                || !text[..start].ends_with(char::is_alphanumeric);
+            let boundary_after = !open.ends_with(char::is_alphanumeric)
+                || !text[after_open..].starts_with(char::is_alphanumeric);
+            if !boundary_before || !boundary_after {
+                continue;
+            }
+
+            let line_end = text[after_open..].find('\n').map_or(text.len(), |i| after_open + i);
+            let (note, end) = match close {
+                Some(close) => match text[after_open..line_end].find(close) {
+                    Some(i) => (&text[after_open..after_open + i], after_open + i + close.len()),
+                    None => continue,
+                },
+                None => {
+                    let rest = text[after_open..line_end].trim_end();
+                    (rest.trim_start_matches(|c: char| c == ':' || c.is_whitespace()), after_open + rest.len())
+                }
+            };
+            matches.push((start, end, marker, note.trim()));
+        }
+    }
+
+    matches.sort_by_key(|&(start, end, _, _)| (start, std::cmp::Reverse(end)));
+
+    let mut todos = Vec::new();
+    let mut covered_until = 0;
+    for (start, end, marker, note) in matches {
+        if start < covered_until {
+            continue;
+        }
+        covered_until = end;
+
+        let (start_offset, end_offset) = stripped.source_range(start, end);
+        todos.push(TodoItem {
+            scene_id: scene.id.clone(),
+            marker: marker.to_string(),
+            text: note.to_string(),
+            start_offset: start_offset as u32,
+            end_offset: end_offset as u32,
+        });
+    }
+
+    todos
+}
+
+pub fn sentence_lengths(html: &str) -> Vec<u32> {
+    sentences(&strip_html(html).text)
+        .into_iter()
+        .map(|(_, sentence)| words(sentence).len() as u32)
+        .collect()
+}
+
+// Mean and population standard deviation
+fn mean_and_std_dev(values: &[u32]) -> (f64, f64) {
+    if values.is_empty() {
+        return (0.0, 0.0);
+    }
+
+    let n = values.len() as f64;
+    let mean = values.iter().map(|&v| v as f64).sum::<f64>() / n;
+    let variance = values.iter().map(|&v| (v as f64 - mean).powi(2)).sum::<f64>() / n;
+
+    (round2(mean), round2(variance.sqrt()))
+}
+
+pub fn build_pacing_report(manuscript_id: &str, scenes: &[Scene]) -> PacingReport {
+    let scenes: Vec<ScenePacing> = scenes
+        .iter()
+        .map(|scene| {
+            let lengths = sentence_lengths(&scene.raw_text);
+            let (mean, std_dev) = mean_and_std_dev(&lengths);
+            ScenePacing {
+                scene_id: scene.id.clone(),
+                title: scene.title.clone(),
+                sentence_lengths: lengths,
+                mean,
+                std_dev,
+            }
+        })
+        .collect();
+
+    let all_lengths: Vec<u32> = scenes
+        .iter()
+        .flat_map(|s| s.sentence_lengths.iter().copied())
+        .collect();
+    let (overall_mean, overall_std_dev) = mean_and_std_dev(&all_lengths);
+
+    PacingReport {
+        manuscript_id: manuscript_id.to_string(),
+        scenes,
+        overall_mean,
+        overall_std_dev,
+    }
+}
+
+fn pov_of(scene: &Scene) -> Option<&str> {
+    scene.pov_character
+        .as_deref()
+        .map(str::trim)
+        .filter(|pov| !pov.is_empty())
+}
+
+// Characters are listed in order of first appearance. A switch is recorded only
+// between adjacent scenes that both have a POV assigned.
+pub fn build_pov_report(manuscript_id: &str, scenes: &[Scene]) -> PovReport {
+    let mut characters: Vec<PovCharacterStats> = Vec::new();
+    let mut switches = Vec::new();
+    let mut unassigned_scene_ids = Vec::new();
+
+    for (i, scene) in scenes.iter().enumerate() {
+        let Some(pov) = pov_of(scene) else {
+            unassigned_scene_ids.push(scene.id.clone());
+            continue;
+        };
+
+        let word_count = word_count(&scene.raw_text, true);
+        match characters.iter_mut().find(|c| c.name == pov) {
+            Some(stats) => {
+                stats.scene_count += 1;
+                stats.word_count += word_count;
+            }
+            None => characters.push(PovCharacterStats {
+                name: pov.to_string(),
+                scene_count: 1,
+                word_count,
+            }),
+        }
+
+        if let Some(previous) = i.checked_sub(1).map(|p| &scenes[p]) {
+            if let Some(previous_pov) = pov_of(previous) {
+                if previous_pov != pov {
+                    switches.push(PovSwitch {
+                        from_scene_id: previous.id.clone(),
+                        to_scene_id: scene.id.clone(),
+                        from_pov: previous_pov.to_string(),
+                        to_pov: pov.to_string(),
+                        within_chapter: previous.chapter_number.is_some()
+                            && previous.chapter_number == scene.chapter_number,
+                    });
+                }
+            }
+        }
+    }
+
+    PovReport {
+        manuscript_id: manuscript_id.to_string(),
+        characters,
+        switches,
+        unassigned_scene_ids,
+    }
+}
+
+// Openers editors see so often that they blunt the first line
+const STOCK_OPENERS: &[&str] = &[
+    "it was a dark", "once upon a time", "woke up", "alarm clock", "the weather", "my name is",
+];
+
+// Words from the start of the scene in which the POV character should be named
+const POV_NAME_WINDOW: usize = 100;
+
+// Full marks up to 12 words, losing 4 points per word after that
+fn first_line_punch(first_line: &str) -> u32 {
+    let length = words(first_line).len() as i64;
+    if length == 0 {
+        return 0;
+    }
+
+    let mut score = 100 - (length - 12).max(0) * 4;
+    let lower = first_line.to_lowercase();
+    if STOCK_OPENERS.iter().any(|opener| lower.contains(opener)) {
+        score -= 40;
+    }
+    score.clamp(0, 100) as u32
+}
+
+// Full marks for 20-50% dialogue, falling off toward none or all of it
+fn dialogue_balance(percentage: f64) -> u32 {
+    let score = if percentage < 20.0 {
+        percentage * 5.0
+    } else if percentage > 50.0 {
+        100.0 - (percentage - 50.0) * 2.0
+    } else {
+        100.0
+    };
+    score.round().clamp(0.0, 100.0) as u32
+}
+
+// Coefficient of variation of sentence lengths; 0.6 or more earns full marks
+fn sentence_variety(lengths: &[u32]) -> u32 {
+    let (mean, std_dev) = mean_and_std_dev(lengths);
+    if lengths.len() < 2 || mean == 0.0 {
+        return 0;
+    }
+    (std_dev / mean / 0.6 * 100.0).round().min(100.0) as u32
+}
+
+// Half marks for an assigned POV character, the rest for naming them (any
+// part of the name, possessives included) early in the scene
+fn pov_presence(scene: &Scene, text: &str) -> u32 {
+    let Some(pov) = pov_of(scene) else {
+        return 0;
+    };
+
+    let name_parts: Vec<String> = words(pov).into_iter().map(|(_, part)| part.to_lowercase()).collect();
+    let named = words(text)
+        .into_iter()
+        .take(POV_NAME_WINDOW)
+        .map(|(_, word)| word.to_lowercase())
+        .any(|word| name_parts.iter().any(|part| {
+            word == *part || word.strip_prefix(part.as_str()).is_some_and(|rest| rest == "'s" || rest == "’s")
+        }));
+
+    if named { 100 } else { 50 }
+}
+
+// The first scene with any text; template skeletons start with empty scenes
+pub fn opening_scene(scenes: &[Scene]) -> Option<&Scene> {
+    scenes
+        .iter()
+        .min_by_key(|scene| (word_count(&scene.raw_text, true) == 0, scene.index_in_manuscript))
+        .filter(|scene| word_count(&scene.raw_text, true) > 0)
+}
+
+pub fn analyze_opening_scene(manuscript_id: &str, scene: &Scene) -> OpeningAnalysis {
+    let text = strip_html(&scene.raw_text).text;
+    let first_line = sentences(&text)
+        .first()
+        .map(|(_, sentence)| sentence.to_string())
+        .unwrap_or_default();
+
+    let first_line_punch = first_line_punch(&first_line);
+    let dialogue_balance = dialogue_balance(dialogue_stats_for_html(&scene.raw_text).dialogue_percentage);
+    let sentence_variety = sentence_variety(&sentence_lengths(&scene.raw_text));
+    let pov_presence = pov_presence(scene, &text);
+
+    let weighted = |weights: [u32; 4]| {
+        let scores = [first_line_punch, dialogue_balance, sentence_variety, pov_presence];
+        let total: u32 = scores.iter().zip(weights).map(|(score, weight)| score * weight).sum();
+        (total + 50) / 100
+    };
+
+    OpeningAnalysis {
+        manuscript_id: manuscript_id.to_string(),
+        scene_id: scene.id.clone(),
+        first_line,
+        first_line_punch,
+        dialogue_balance,
+        sentence_variety,
+        pov_presence,
+        opening_strength: weighted([30, 20, 25, 25]),
+        hook_effectiveness: weighted([60, 20, 20, 0]),
+    }
+}
+
+fn non_blank(value: &Option<String>) -> Option<String> {
+    value.as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+pub fn build_timeline(scenes: &[Scene]) -> Vec<TimelineEntry> {
+    scenes
+        .iter()
+        .map(|scene| {
+            let time_marker = non_blank(&scene.time_marker);
+            TimelineEntry {
+                scene_id: scene.id.clone(),
+                title: scene.title.clone(),
+                index_in_manuscript: scene.index_in_manuscript,
+                chapter_number: scene.chapter_number,
+                location: non_blank(&scene.location),
+                missing_time_marker: time_marker.is_none(),
+                time_marker,
+            }
+        })
+        .collect()
+}
+
+// Local event extraction for the events module: one event per paragraph,
+// described by its opening sentence and attributed to the POV character
+pub fn extract_scene_events(html: &str, pov_character: Option<&str>, time_marker: Option<&str>) -> Vec<SceneEvent> {
+    let characters: Vec<String> = pov_character
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .into_iter()
+        .collect();
+
+    strip_html(html)
+        .text
+        .split('\n')
+        .filter_map(|paragraph| sentences(paragraph).first().map(|(_, sentence)| sentence.to_string()))
+        .map(|description| SceneEvent {
+            description,
+            characters: characters.clone(),
+            time_marker: time_marker.map(str::to_string),
+        })
+        .collect()
+}
+
+pub fn build_outline(scenes: &[Scene], tags: &HashMap<String, Vec<String>>) -> Vec<OutlineNode> {
+    let mut outline = Vec::new();
+    let mut offset = 0;
+
+    for (chapter, members) in group_by_chapter(scenes) {
+        let chapter_start = offset;
+        let scene_nodes: Vec<OutlineNode> = members
+            .iter()
+            .map(|scene| {
+                let words = word_count(&scene.raw_text, true);
+                let node = OutlineNode {
+                    kind: OutlineNodeKind::Scene,
+                    id: scene.id.clone(),
+                    title: non_blank(&scene.title),
+                    chapter_number: scene.chapter_number,
+                    index_in_manuscript: scene.index_in_manuscript,
+                    word_count: words,
+                    start_word_offset: offset,
+                    pov_character: non_blank(&scene.pov_character),
+                    location: non_blank(&scene.location),
+                    tags: tags.get(&scene.id).cloned().unwrap_or_default(),
+                    children: Vec::new(),
+                };
+                offset += words;
+                node
+            })
+            .collect();
+
+        match chapter {
+            Some(number) => outline.push(OutlineNode {
+                kind: OutlineNodeKind::Chapter,
+                id: format!("chapter-{}", number),
+                title: Some(format!("Chapter {}", number)),
+                chapter_number: Some(number),
+                index_in_manuscript: members[0].index_in_manuscript,
+                word_count: offset - chapter_start,
+                start_word_offset: chapter_start,
+                pov_character: None,
+                location: None,
+                tags: Vec::new(),
+                children: scene_nodes,
+            }),
+            None => outline.extend(scene_nodes),
+        }
+    }
+
+    outline
+}
+
+pub fn readability_for_html(html: &str) -> ReadabilityMetrics {
+    TextCounts::from_text(&strip_html(html).text).metrics()
+}
+
+// Scenes in manuscript order, grouped into runs sharing a chapter number
+pub fn group_by_chapter(scenes: &[Scene]) -> Vec<(Option<i32>, Vec<&Scene>)> {
+    let mut groups: Vec<(Option<i32>, Vec<&Scene>)> = Vec::new();
+
+    for scene in scenes {
+        match groups.last_mut() {
+            Some((chapter, members)) if *chapter == scene.chapter_number => members.push(scene),
+            _ => groups.push((scene.chapter_number, vec![scene])),
+        }
+    }
+
+    groups
+}
+
+pub fn build_readability_report(manuscript_id: &str, scenes: &[Scene]) -> ReadabilityReport {
+    let mut overall = TextCounts::default();
+    let mut chapters = Vec::new();
+
+    for (chapter_number, members) in group_by_chapter(scenes) {
+        let mut chapter_counts = TextCounts::default();
+        for scene in &members {
+            chapter_counts.add(TextCounts::from_text(&strip_html(&scene.raw_text).text));
+        }
+        overall.add(chapter_counts);
+
+        chapters.push(ChapterReadability {
+            chapter_number,
+            scene_ids: members.iter().map(|s| s.id.clone()).collect(),
+            metrics: chapter_counts.metrics(),
+        });
+    }
+
+    ReadabilityReport {
+        manuscript_id: manuscript_id.to_string(),
+        overall: overall.metrics(),
+        chapters,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordFrequency {
+    pub word: String,
+    pub count: u32,
+}
+
+// Every manuscript-level analysis, bundled for export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FullAnalysisReport {
+    pub manuscript_id: String,
+    pub readability: ReadabilityReport,
+    pub dialogue: DialogueReport,
+    pub pacing: PacingReport,
+    pub pov: PovReport,
+    pub word_frequencies: Vec<WordFrequency>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Json,
+    Csv,
+}
+
+const REPORT_WORD_FREQUENCY_LIMIT: usize = 100;
+
+// Most frequent words (lowercased), ties broken alphabetically
+pub fn word_frequencies(scenes: &[Scene], limit: usize) -> Vec<WordFrequency> {
+    let mut counts: std::collections::HashMap<String, u32> = std::collections::HashMap::new();
+    for scene in scenes {
+        for (_, word) in words(&strip_html(&scene.raw_text).text) {
+            *counts.entry(word.to_lowercase()).or_insert(0) += 1;
+        }
+    }
+
+    let mut frequencies: Vec<WordFrequency> = counts
+        .into_iter()
+        .map(|(word, count)| WordFrequency { word, count })
+        .collect();
+    frequencies.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.word.cmp(&b.word)));
+    frequencies.truncate(limit);
+    frequencies
+}
+
+pub fn build_full_report(manuscript_id: &str, scenes: &[Scene]) -> FullAnalysisReport {
+    FullAnalysisReport {
+        manuscript_id: manuscript_id.to_string(),
+        readability: build_readability_report(manuscript_id, scenes),
+        dialogue: build_dialogue_report(manuscript_id, scenes),
+        pacing: build_pacing_report(manuscript_id, scenes),
+        pov: build_pov_report(manuscript_id, scenes),
+        word_frequencies: word_frequencies(scenes, REPORT_WORD_FREQUENCY_LIMIT),
+    }
+}
+
+// CSV reports are in long format, one metric per row, so a spreadsheet can
+// filter or pivot them without knowing the report's shape
+pub const REPORT_CSV_HEADER: &str = "section,label,chapter_number,scene_id,metric,value";
+
+struct ReportRow {
+    section: &'static str,
+    label: String,
+    chapter_number: Option<i32>,
+    scene_id: Option<String>,
+    metric: &'static str,
+    value: String,
+}
+
+fn chapter_label(chapter_number: Option<i32>) -> String {
+    match chapter_number {
+        Some(number) => format!("Chapter {}", number),
+        None => "Unnumbered".to_string(),
+    }
+}
+
+fn readability_rows(rows: &mut Vec<ReportRow>, label: String, chapter_number: Option<i32>, metrics: &ReadabilityMetrics) {
+    let values = [
+        ("word_count", metrics.word_count.to_string()),
+        ("sentence_count", metrics.sentence_count.to_string()),
+        ("syllable_count", metrics.syllable_count.to_string()),
+        ("flesch_reading_ease", metrics.flesch_reading_ease.to_string()),
+        ("flesch_kincaid_grade", metrics.flesch_kincaid_grade.to_string()),
+        ("average_sentence_length", metrics.average_sentence_length.to_string()),
+        ("average_syllables_per_word", metrics.average_syllables_per_word.to_string()),
+    ];
+    for (metric, value) in values {
+        rows.push(ReportRow { section: "readability", label: label.clone(), chapter_number, scene_id: None, metric, value });
+    }
+}
+
+fn dialogue_rows(rows: &mut Vec<ReportRow>, label: String, scene_id: Option<&str>, stats: &DialogueStats) {
+    let values = [
+        ("dialogue_words", stats.dialogue_words.to_string()),
+        ("narration_words", stats.narration_words.to_string()),
+        ("dialogue_percentage", stats.dialogue_percentage.to_string()),
+    ];
+    for (metric, value) in values {
+        rows.push(ReportRow {
+            section: "dialogue",
+            label: label.clone(),
+            chapter_number: None,
+            scene_id: scene_id.map(str::to_string),
+            metric,
+            value,
+        });
+    }
+}
+
+fn report_rows(report: &FullAnalysisReport) -> Vec<ReportRow> {
+    let mut rows = Vec::new();
+    let row = |section, label: &str, scene_id: Option<&str>, metric, value: String| ReportRow {
+        section,
+        label: label.to_string(),
+        chapter_number: None,
+        scene_id: scene_id.map(str::to_string),
+        metric,
+        value,
+    };
+
+    readability_rows(&mut rows, "Manuscript".to_string(), None, &report.readability.overall);
+    for chapter in &report.readability.chapters {
+        readability_rows(&mut rows, chapter_label(chapter.chapter_number), chapter.chapter_number, &chapter.metrics);
+    }
+
+    dialogue_rows(&mut rows, "Manuscript".to_string(), None, &report.dialogue.overall);
+    for scene in &report.dialogue.scenes {
+        let label = scene.title.clone().unwrap_or_default();
+        dialogue_rows(&mut rows, label, Some(&scene.scene_id), &scene.stats);
+    }
+
+    rows.push(row("pacing", "Manuscript", None, "mean_sentence_length", report.pacing.overall_mean.to_string()));
+    rows.push(row("pacing", "Manuscript", None, "sentence_length_std_dev", report.pacing.overall_std_dev.to_string()));
+    for scene in &report.pacing.scenes {
+        let label = scene.title.as_deref().unwrap_or("");
+        rows.push(row("pacing", label, Some(&scene.scene_id), "sentence_count", scene.sentence_lengths.len().to_string()));
+        rows.push(row("pacing", label, Some(&scene.scene_id), "mean_sentence_length", scene.mean.to_string()));
+        rows.push(row("pacing", label, Some(&scene.scene_id), "sentence_length_std_dev", scene.std_dev.to_string()));
+    }
+
+    for character in &report.pov.characters {
+        rows.push(row("pov", &character.name, None, "scene_count", character.scene_count.to_string()));
+        rows.push(row("pov", &character.name, None, "word_count", character.word_count.to_string()));
+    }
+
+    for frequency in &report.word_frequencies {
+        rows.push(row("word_frequency", &frequency.word, None, "count", frequency.count.to_string()));
+    }
+
+    rows
+}
+
+// Quote a field when it holds a delimiter, quote or line break (RFC 4180)
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn report_to_csv(report: &FullAnalysisReport) -> String {
+    let mut csv = String::from(REPORT_CSV_HEADER);
+    csv.push_str("\r\n");
+    for row in report_rows(report) {
+        let fields = [
+            row.section.to_string(),
+            row.label,
+            row.chapter_number.map(|n| n.to_string()).unwrap_or_default(),
+            row.scene_id.unwrap_or_default(),
+            row.metric.to_string(),
+            row.value,
+        ];
+        let line: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&line.join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+pub fn write_analysis_report(report: &FullAnalysisReport, format: ReportFormat, path: &Path) -> AppResult<()> {
+    let contents = match format {
+        ReportFormat::Json => serde_json::to_string_pretty(report)?,
+        ReportFormat::Csv => report_to_csv(report),
+    };
+
+    std::fs::write(path, contents).map_err(|e| AppError::file_system_with_path(
+        e.to_string(),
+        "write analysis report".to_string(),
+        path.to_path_buf()
+    ))
+}
+
+// Analysis commands accept an optional manuscript id for forward compatibility,
+// but only the singleton manuscript exists.
+pub fn resolve_manuscript_id(manuscript_id: Option<String>) -> AppResult<String> {
+    match manuscript_id {
+        None => Ok(SINGLETON_MANUSCRIPT_ID.to_string()),
+        Some(id) if id == SINGLETON_MANUSCRIPT_ID => Ok(id),
+        Some(id) => Err(AppError::not_found_with_id("Manuscript".to_string(), id)),
+    }
+}
+
+pub async fn load_manuscript_scenes(app: &AppHandle) -> AppResult<Vec<Scene>> {
+    let mut scenes = crate::db::get_all_scenes_impl(app).await?;
+    scenes.sort_by_key(|s| s.index_in_manuscript);
+    Ok(scenes)
+}
+
+pub async fn load_scene_content(pool: &SqlitePool, scene_id: &str) -> AppResult<String> {
+    validate_scene_id(scene_id)?;
+
+    crate::db::get_scene_text(pool, scene_id).await
+}
+
+#[tauri::command]
+pub async fn compute_readability(
+    app: AppHandle,
+    manuscript_id: Option<String>
+) -> Result<ReadabilityReport, AppError> {
+    let manuscript_id = resolve_manuscript_id(manuscript_id)?;
+    let scenes = load_manuscript_scenes(&app).await?;
+
+    Ok(build_readability_report(&manuscript_id, &scenes))
+}
+
+#[tauri::command]
+pub async fn analyze_dialogue_ratio(
+    app: AppHandle,
+    manuscript_id: Option<String>
+) -> Result<DialogueReport, AppError> {
+    let manuscript_id = resolve_manuscript_id(manuscript_id)?;
+    let scenes = load_manuscript_scenes(&app).await?;
+
+    Ok(build_dialogue_report(&manuscript_id, &scenes))
+}
+
+#[tauri::command]
+pub async fn analyze_prose_crutches(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    scene_id: String,
+    filter_words: Option<Vec<String>>
+) -> Result<Vec<ProseFlag>, AppError> {
+    let pool = db_service.pool(&app).await?;
+    let content = load_scene_content(&pool, &scene_id).await?;
+    let filter_words = filter_words
+        .unwrap_or_else(|| DEFAULT_FILTER_WORDS.iter().map(|w| w.to_string()).collect());
+
+    Ok(find_prose_crutches(&content, &filter_words))
+}
+
+#[tauri::command]
+pub async fn detect_passive_voice(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    scene_id: String
+) -> Result<Vec<ProseFlag>, AppError> {
+    let pool = db_service.pool(&app).await?;
+    let content = load_scene_content(&pool, &scene_id).await?;
+
+    Ok(find_passive_voice(&content))
+}
+
+// Revision markers across the manuscript, in reading order, for a task list
+#[tauri::command]
+pub async fn extract_todos(
+    app: AppHandle,
+    manuscript_id: Option<String>,
+    markers: Option<Vec<String>>
+) -> Result<Vec<TodoItem>, AppError> {
+    resolve_manuscript_id(manuscript_id)?;
+    let scenes = load_manuscript_scenes(&app).await?;
+    let markers = markers
+        .unwrap_or_else(|| DEFAULT_TODO_MARKERS.iter().map(|m| m.to_string()).collect());
+
+    Ok(scenes.iter().flat_map(|scene| find_todos(scene, &markers)).collect())
+}
+
+#[tauri::command]
+pub async fn analyze_pacing(
+    app: AppHandle,
+    manuscript_id: Option<String>
+) -> Result<PacingReport, AppError> {
+    let manuscript_id = resolve_manuscript_id(manuscript_id)?;
+    let scenes = load_manuscript_scenes(&app).await?;
+
+    Ok(build_pacing_report(&manuscript_id, &scenes))
+}
+
+// Scores the opening scene and stores the result on the manuscript
+#[tauri::command]
+pub async fn analyze_opening(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    manuscript_id: Option<String>
+) -> Result<OpeningAnalysis, AppError> {
+    let manuscript_id = resolve_manuscript_id(manuscript_id)?;
+    let scenes = load_manuscript_scenes(&app).await?;
+    let scene = opening_scene(&scenes)
+        .ok_or_else(|| AppError::validation("The manuscript has no scene text to analyze"))?;
+    let analysis = analyze_opening_scene(&manuscript_id, scene);
+
+    let pool = db_service.pool(&app).await?;
+    let now = chrono::Utc::now().timestamp_millis();
+    crate::db::store_opening_scores(&pool, &manuscript_id, analysis.opening_strength, analysis.hook_effectiveness, now).await?;
+    db_service.invalidate_cache("manuscripts").await;
+
+    Ok(analysis)
+}
+
+#[tauri::command]
+pub async fn analyze_pov_distribution(
+    app: AppHandle,
+    manuscript_id: Option<String>
+) -> Result<PovReport, AppError> {
+    let manuscript_id = resolve_manuscript_id(manuscript_id)?;
+    let scenes = load_manuscript_scenes(&app).await?;
+
+    Ok(build_pov_report(&manuscript_id, &scenes))
+}
+
+#[tauri::command]
+pub async fn build_scene_timeline(
+    app: AppHandle,
+    manuscript_id: Option<String>
+) -> Result<Vec<TimelineEntry>, AppError> {
+    resolve_manuscript_id(manuscript_id)?;
+    let scenes = load_manuscript_scenes(&app).await?;
+
+    Ok(build_timeline(&scenes))
+}
+
+// Backs View > Document Outline
+#[tauri::command]
+pub async fn build_document_outline(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    manuscript_id: Option<String>
+) -> Result<Vec<OutlineNode>, AppError> {
+    resolve_manuscript_id(manuscript_id)?;
+    let scenes = load_manuscript_scenes(&app).await?;
+    let pool = db_service.pool(&app).await?;
+    let tags = crate::db::get_all_scene_tags(&pool).await?;
+
+    Ok(build_outline(&scenes, &tags))
+}
+
+#[tauri::command]
+pub async fn get_full_analysis_report(
+    app: AppHandle,
+    manuscript_id: Option<String>
+) -> Result<FullAnalysisReport, AppError> {
+    let manuscript_id = resolve_manuscript_id(manuscript_id)?;
+    let scenes = load_manuscript_scenes(&app).await?;
+
+    Ok(build_full_report(&manuscript_id, &scenes))
+}
+
+// Save a report for use outside the app: JSON as-is, or CSV for spreadsheets
+#[tauri::command]
+pub async fn export_analysis_report(
+    report: FullAnalysisReport,
+    format: ReportFormat,
+    path: String
+) -> Result<(), AppError> {
+    write_analysis_report(&report, format, Path::new(&path))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn scene(index: u32, chapter: Option<i32>, html: &str) -> Scene {
+        Scene {
+            id: format!("scene-{}", index),
+            chapter_number: chapter,
+            scene_number_in_chapter: None,
+            index_in_manuscript: index,
+            title: None,
+            raw_text: html.to_string(),
+            word_count: 0,
+            is_opening: index == 0,
+            is_chapter_end: false,
+            opens_with_hook: false,
+            ends_with_hook: false,
+            pov_character: None,
+            location: None,
+            time_marker: None,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_syllable_heuristic() {
+        assert_eq!(count_syllables("cat"), 1);
+        assert_eq!(count_syllables("make"), 1);
+        assert_eq!(count_syllables("table"), 2);
+        assert_eq!(count_syllables("jumped"), 1);
+        assert_eq!(count_syllables("readability"), 5);
+    }
+
+    #[test]
+    fn test_grade_level_for_simple_and_dense_prose() {
+        let simple = readability_for_html("<p>The cat sat on the mat. The dog ran to the sun. We had fun.</p>");
+        assert_eq!(simple.sentence_count, 3);
+        assert!(simple.flesch_kincaid_grade < 2.0, "grade was {}", simple.flesch_kincaid_grade);
+        assert!(simple.flesch_reading_ease > 90.0);
+
+        let dense = readability_for_html(
+            "<p>Institutional considerations notwithstanding, the administrative reorganization \
+             precipitated unprecedented complications regarding organizational accountability \
+             and interdepartmental communication.</p>"
+        );
+        assert!(dense.flesch_kincaid_grade > 16.0, "grade was {}", dense.flesch_kincaid_grade);
+        assert!(dense.flesch_reading_ease < 10.0);
+    }
+
+    #[test]
+    fn test_report_breaks_down_by_chapter() {
+        let scenes = vec![
+            scene(0, Some(1), "<p>The cat sat on the mat.</p>"),
+            scene(1, Some(1), "<p>The dog ran.</p>"),
+            scene(2, Some(2), "<p>We had fun in the sun.</p>"),
+        ];
+        let report = build_readability_report(SINGLETON_MANUSCRIPT_ID, &scenes);
+
+        assert_eq!(report.chapters.len(), 2);
+        assert_eq!(report.chapters[0].metrics.sentence_count, 2);
+        assert_eq!(report.chapters[0].scene_ids, vec!["scene-0", "scene-1"]);
+        assert_eq!(report.overall.word_count, 15);
+    }
+
+    #[test]
+    fn test_dialogue_ratio_with_straight_and_curly_quotes() {
+        let stats = dialogue_stats_for_html(
+            "<p>\"I am here,\" she said.</p><p>“Come in,” he replied quietly.</p>"
+        );
+        assert_eq!(stats.dialogue_words, 5);
+        assert_eq!(stats.narration_words, 5);
+        assert!((stats.dialogue_percentage - 50.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_dialogue_report_totals_across_scenes() {
+        let scenes = vec![
+            scene(0, Some(1), "<p>“Run,” she said.</p>"),
+            scene(1, Some(1), "<p>Nobody moved at all.</p>"),
+        ];
+        let report = build_dialogue_report(SINGLETON_MANUSCRIPT_ID, &scenes);
+
+        assert_eq!(report.scenes.len(), 2);
+        assert_eq!(report.scenes[1].stats.dialogue_words, 0);
+        // 1 of 7 words is dialogue
+        assert!((report.overall.dialogue_percentage - 14.29).abs() < 0.01);
+    }
+
+    fn default_filter_words() -> Vec<String> {
+        DEFAULT_FILTER_WORDS.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn test_prose_crutch_offsets_map_to_source() {
+        let html = "<p>She moved <b>quickly</b>; she felt cold.</p>";
+        let flags = find_prose_crutches(html, &default_filter_words());
+
+        assert_eq!(flags.len(), 2);
+        assert_eq!(flags[0].category, ProseFlagCategory::Adverb);
+        assert_eq!((flags[0].start_offset, flags[0].end_offset), (16, 23));
+        assert_eq!(flags[1].category, ProseFlagCategory::FilterWord);
+        assert_eq!((flags[1].start_offset, flags[1].end_offset), (33, 37));
+    }
+
+    #[test]
+    fn test_prose_crutches_use_custom_filter_list() {
+        let flags = find_prose_crutches("<p>Only she felt it. He glanced over.</p>", &["glanced".to_string()]);
+
+        assert_eq!(flags.len(), 1);
+        assert_eq!(flags[0].word, "glanced");
+    }
+
+    #[test]
+    fn test_scene_content_loads_from_the_database() {
+        let mut stored = scene(0, Some(1), "<p>She felt the cold. He really ran.</p>");
+        stored.id = uuid::Uuid::new_v4().to_string();
+
+        tokio_test::block_on(async {
+            let pool = crate::db::tests::memory_pool().await;
+            crate::db::replace_manuscript_with_scenes(&pool, &crate::db::tests::manuscript("Draft"), &[stored.clone()]).await.unwrap();
+
+            let content = load_scene_content(&pool, &stored.id).await.unwrap();
+            let flags = find_prose_crutches(&content, &default_filter_words());
+            assert_eq!(flags.iter().map(|f| f.word.as_str()).collect::<Vec<_>>(), vec!["felt", "really"]);
+
+            let missing = load_scene_content(&pool, &uuid::Uuid::new_v4().to_string()).await;
+            assert!(matches!(missing, Err(AppError::NotFound { .. })));
+            assert!(matches!(load_scene_content(&pool, "scene-0").await, Err(AppError::Validation { .. })));
+        });
+    }
+
+    #[test]
+    fn test_passive_voice_runs_on_stored_scene_text() {
+        let mut stored = scene(0, Some(1), "<p>The ball was thrown by Tom. Tom ran.</p>");
+        stored.id = uuid::Uuid::new_v4().to_string();
+
+        tokio_test::block_on(async {
+            let pool = crate::db::tests::memory_pool().await;
+            crate::db::replace_manuscript_with_scenes(&pool, &crate::db::tests::manuscript("Draft"), &[stored.clone()]).await.unwrap();
+
+            let content = load_scene_content(&pool, &stored.id).await.unwrap();
+            let phrases: Vec<String> = find_passive_voice(&content).into_iter().map(|f| f.word).collect();
+            assert_eq!(phrases, vec!["was thrown"]);
+        });
+    }
+
+    #[test]
+    fn test_passive_voice_detection() {
+        let passive = find_passive_voice("<p>The ball was thrown by Tom. The letters were quickly written.</p>");
+        let phrases: Vec<&str> = passive.iter().map(|f| f.word.as_str()).collect();
+        assert_eq!(phrases, vec!["was thrown", "were quickly written"]);
+        assert!(passive.iter().all(|f| f.category == ProseFlagCategory::PassiveVoice));
+
+        let html = "<p>The ball was thrown by Tom.</p>";
+        let source: Vec<char> = html.chars().collect();
+        let flag = &find_passive_voice(html)[0];
+        let flagged: String = source[flag.start_offset as usize..flag.end_offset as usize].iter().collect();
+        assert_eq!(flagged, "was thrown");
+    }
+
+    #[test]
+    fn test_active_and_adjectival_sentences_are_not_flagged() {
+        assert!(find_passive_voice("<p>Tom threw the ball. She was tired. He is red-faced and was happy.</p>").is_empty());
+        assert_eq!(find_passive_voice("<p>She was surprised by the news.</p>").len(), 1);
+    }
+
+    #[test]
+    fn test_pacing_sentence_lengths_and_mean() {
+        let scenes = vec![scene(
+            0,
+            Some(1),
+            "<p>Mr. Smith arrived late. He sat down. The meeting, which had been scheduled for noon, finally began!</p>",
+        )];
+        let report = build_pacing_report(SINGLETON_MANUSCRIPT_ID, &scenes);
+        let pacing = &report.scenes[0];
+
+        assert_eq!(pacing.sentence_lengths, vec![4, 3, 10]);
+        assert!((pacing.mean - 5.67).abs() < 0.01);
+        assert!((pacing.std_dev - 3.09).abs() < 0.01);
+        assert_eq!(report.overall_mean, pacing.mean);
+    }
+
+    fn pov_scene(index: u32, chapter: i32, pov: Option<&str>, html: &str) -> Scene {
+        let mut scene = scene(index, Some(chapter), html);
+        scene.pov_character = pov.map(str::to_string);
+        scene
+    }
+
+    #[test]
+    fn test_pov_distribution_and_switches() {
+        let scenes = vec![
+            pov_scene(0, 1, Some("Anna"), "<p>Anna woke early.</p>"),
+            pov_scene(1, 1, Some("Ben"), "<p>Ben was already gone.</p>"),
+            pov_scene(2, 2, Some("Ben"), "<p>He drove north.</p>"),
+            pov_scene(3, 2, None, "<p>The storm broke.</p>"),
+            pov_scene(4, 3, Some("Anna "), "<p>Anna waited.</p>"),
+        ];
+        let report = build_pov_report(SINGLETON_MANUSCRIPT_ID, &scenes);
+
+        assert_eq!(report.characters.len(), 2);
+        assert_eq!(report.characters[0].name, "Anna");
+        assert_eq!(report.characters[0].scene_count, 2);
+        assert_eq!(report.characters[0].word_count, 5);
+        assert_eq!(report.characters[1].scene_count, 2);
+        assert_eq!(report.unassigned_scene_ids, vec!["scene-3"]);
+
+        // Unassigned scene 3 breaks the Ben -> Anna adjacency
+        assert_eq!(report.switches.len(), 1);
+        assert_eq!(report.switches[0].from_pov, "Anna");
+        assert_eq!(report.switches[0].to_pov, "Ben");
+        assert!(report.switches[0].within_chapter);
+    }
+
+    #[test]
+    fn test_timeline_flags_missing_time_markers() {
+        let mut first = scene(0, Some(1), "<p>One</p>");
+        first.time_marker = Some("Monday, dawn".to_string());
+        first.location = Some("Harbor".to_string());
+        let mut second = scene(1, Some(1), "<p>Two</p>");
+        second.time_marker = Some("   ".to_string());
+        let mut third = scene(2, Some(2), "<p>Three</p>");
+        third.time_marker = Some("Tuesday".to_string());
+
+        let timeline = build_timeline(&[first, second, third]);
+        let order: Vec<u32> = timeline.iter().map(|e| e.index_in_manuscript).collect();
+        let missing: Vec<bool> = timeline.iter().map(|e| e.missing_time_marker).collect();
+
+        assert_eq!(order, vec![0, 1, 2]);
+        assert_eq!(missing, vec![false, true, false]);
+        assert_eq!(timeline[0].location.as_deref(), Some("Harbor"));
+        assert_eq!(timeline[1].time_marker, None);
+    }
+
+    #[test]
+    fn test_outline_nests_scenes_under_chapters() {
+        let mut opening = scene(0, None, "<p>A short prologue.</p>");
+        opening.title = Some("Prologue".to_string());
+        let mut first = scene(1, Some(1), "<p>One two three four.</p>");
+        first.pov_character = Some("Mara".to_string());
+        first.location = Some("Harbor".to_string());
+        let second = scene(2, Some(1), "<p>Five six.</p>");
+        let third = scene(3, Some(2), "<p>Seven eight nine.</p>");
+
+        let tags = HashMap::from([("scene-1".to_string(), vec!["flashback".to_string()])]);
+        let outline = build_outline(&[opening, first, second, third], &tags);
+        let top: Vec<(OutlineNodeKind, &str)> = outline.iter().map(|n| (n.kind, n.id.as_str())).collect();
+        assert_eq!(top, vec![
+            (OutlineNodeKind::Scene, "scene-0"),
+            (OutlineNodeKind::Chapter, "chapter-1"),
+            (OutlineNodeKind::Chapter, "chapter-2"),
+        ]);
+
+        let chapter_one = &outline[1];
+        assert_eq!(chapter_one.children.len(), 2);
+        assert_eq!(chapter_one.word_count, 6);
+        assert_eq!(chapter_one.start_word_offset, 3);
+        assert_eq!(chapter_one.children[0].pov_character.as_deref(), Some("Mara"));
+        assert_eq!(chapter_one.children[0].location.as_deref(), Some("Harbor"));
+        assert_eq!(chapter_one.children[1].start_word_offset, 7);
+        assert_eq!(chapter_one.children[0].tags, vec!["flashback"]);
+        assert!(chapter_one.tags.is_empty() && chapter_one.children[1].tags.is_empty());
+
+        assert_eq!(outline[2].start_word_offset, 9);
+        assert_eq!(outline[2].word_count, 3);
+        assert_eq!(outline[0].title.as_deref(), Some("Prologue"));
+    }
+
+    #[test]
+    fn test_unknown_manuscript_id_is_not_found() {
+        assert!(resolve_manuscript_id(None).is_ok());
+        assert!(matches!(
+            resolve_manuscript_id(Some("other".to_string())),
+            Err(AppError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_csv_report_has_one_row_per_metric() {
+        let mut scenes = vec![
+            scene(0, Some(1), "<p>\"Run,\" she said. They ran.</p>"),
+            scene(1, Some(1), "<p>The night was long.</p>"),
+            scene(2, Some(2), "<p>Morning came. They slept.</p>"),
+        ];
+        scenes[0].title = Some("Flight, part one".to_string());
+        scenes[0].pov_character = Some("Ana".to_string());
+        scenes[2].pov_character = Some("Ben".to_string());
+        let report = build_full_report(SINGLETON_MANUSCRIPT_ID, &scenes);
+
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("report.csv");
+        write_analysis_report(&report, ReportFormat::Csv, &path).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], REPORT_CSV_HEADER);
+        let expected_rows = 7 * (1 + report.readability.chapters.len())
+            + 3 * (1 + report.dialogue.scenes.len())
+            + 2 + 3 * report.pacing.scenes.len()
+            + 2 * report.pov.characters.len()
+            + report.word_frequencies.len();
+        assert_eq!(lines.len() - 1, expected_rows);
+        assert!(csv.contains("dialogue,\"Flight, part one\",,scene-0,dialogue_words,1\r\n"), "{}", csv);
+        assert!(csv.contains("word_frequency,they,,,count,2\r\n"));
+
+        let json_path = temp.path().join("report.json");
+        write_analysis_report(&report, ReportFormat::Json, &json_path).unwrap();
+        let parsed: FullAnalysisReport = serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+        assert_eq!(parsed.readability.chapters.len(), 2);
+        assert_eq!(parsed.pov.characters.len(), 2);
+    }
+
+    #[test]
+    fn test_find_todos_maps_markers_to_source_offsets() {
+        let html = "<p>Opening. TODO: fix the &amp; pacing</p><p>She ran. [[check <em>timeline</em>]] Then FIXME tighten</p><p>TODOS aren't markers. [note] ask Sam</p>";
+        let markers: Vec<String> = DEFAULT_TODO_MARKERS.iter().map(|m| m.to_string()).collect();
+        let todos = find_todos(&scene(4, Some(1), html), &markers);
+
+        let source: Vec<char> = html.chars().collect();
+        let found: Vec<(&str, &str, String)> = todos
+            .iter()
+            .map(|todo| (
+                todo.marker.as_str(),
+                todo.text.as_str(),
+                source[todo.start_offset as usize..todo.end_offset as usize].iter().collect(),
+            ))
+            .collect();
+        assert_eq!(found, vec![
+            ("TODO", "fix the & pacing", "TODO: fix the &amp; pacing".to_string()),
+            ("[[...]]", "check timeline", "[[check <em>timeline</em>]]".to_string()),
+            ("FIXME", "tighten", "FIXME tighten".to_string()),
+            ("[note]", "ask Sam", "[note] ask Sam".to_string()),
+        ]);
+        assert!(todos.iter().all(|todo| todo.scene_id == "scene-4"));
+
+        let custom = find_todos(&scene(0, None, "<p>XXX: recheck. TODO later</p>"), &["XXX".to_string(), " ".to_string()]);
+        assert_eq!(custom.len(), 1);
+        assert_eq!(custom[0].text, "recheck. TODO later");
+    }
+
+    #[test]
+    fn test_strong_opening_outscores_weak_opening() {
+        let mut strong = scene(0, Some(1),
+            "<p>The gun was still warm when Mara found it.</p>\
+             <p>“Who’s there?” she whispered. Nothing. She edged down the hallway, counting doors the way \
+             her father had taught her, breath held, finger nowhere near the trigger.</p>\
+             <p>“Mara, put it down,” said a voice behind her. “Please.”</p>");
+        strong.pov_character = Some("Mara Voss".to_string());
+        let weak = scene(0, Some(1),
+            "<p>It was a dark and stormy night, and the rain fell in torrents over the sleepy little town \
+             where nothing much had happened for as long as anyone could remember.</p>\
+             <p>The town had a church and a school and a small square with some old trees in the middle. \
+             The people who lived there mostly worked on the farms that surrounded it on every side.</p>");
+
+        let strong = analyze_opening_scene(SINGLETON_MANUSCRIPT_ID, &strong);
+        let weak = analyze_opening_scene(SINGLETON_MANUSCRIPT_ID, &weak);
+
+        assert_eq!(strong.first_line, "The gun was still warm when Mara found it.");
+        assert_eq!((strong.first_line_punch, strong.pov_presence), (100, 100));
+        assert_eq!((weak.first_line_punch, weak.dialogue_balance, weak.pov_presence), (0, 0, 0));
+        assert!(strong.sentence_variety > weak.sentence_variety);
+        assert!(strong.opening_strength > weak.opening_strength, "{:?} vs {:?}", strong, weak);
+        assert!(strong.hook_effectiveness > weak.hook_effectiveness);
+        assert!(strong.opening_strength <= 100 && strong.hook_effectiveness <= 100);
+
+        // Empty template scenes are skipped when picking the opening
+        let scenes = vec![scene(0, Some(1), ""), scene(1, Some(1), "<p>Text.</p>")];
+        assert_eq!(opening_scene(&scenes).map(|s| s.id.as_str()), Some("scene-1"));
+        assert!(opening_scene(&scenes[..1]).is_none());
+
+        tokio_test::block_on(async {
+            let pool = crate::db::tests::memory_pool().await;
+            crate::db::replace_manuscript_with_scenes(&pool, &crate::db::tests::manuscript("Draft"), &[]).await.unwrap();
+            crate::db::store_opening_scores(&pool, SINGLETON_MANUSCRIPT_ID, strong.opening_strength, strong.hook_effectiveness, 7).await.unwrap();
+
+            let stored: (i64, i64) = sqlx::query_as("SELECT opening_strength_score, hook_effectiveness FROM manuscripts")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(stored, (strong.opening_strength as i64, strong.hook_effectiveness as i64));
+            let missing = crate::db::store_opening_scores(&pool, "other", 1, 1, 7).await;
+            assert!(matches!(missing, Err(AppError::NotFound { .. })));
+        });
+    }
+}
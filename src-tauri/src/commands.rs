@@ -1,7 +1,12 @@
-use crate::error::{AppError, AppResult, ErrorLogger, retry_with_backoff, RetryConfig};
-use crate::db::DatabaseService;
-use tauri::{AppHandle, State};
+use crate::error::{AppError, AppResult, ErrorCode, ErrorLogger, ErrorLogFilter, ErrorLogWatcher, ErrorStats};
+use crate::db::{BeatType, DatabaseService, DatabaseSize, GoalKind, GoalProgress, GoalStatus, IntegrityReport, Manuscript, ManuscriptCopy, ManuscriptEvent, NewPlant, Note, Plant, RecomputedScene, ReindexSummary, Scene, SceneBeat, SceneComment, SceneEvent, StateChange, TaggedScene, VacuumResult, SINGLETON_MANUSCRIPT_ID};
+use crate::fs::{ContentReplacement, ImportOptions, SceneBreakType};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, State};
 use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 
 // Wrapper macro for Tauri commands with error handling and logging
 macro_rules! tauri_command_with_error_handling {
@@ -100,13 +105,19 @@ pub fn validate_title(title: &str) -> AppResult<()> {
 }
 
 // Enhanced database commands with proper error handling
-// Single manuscript mode - get the singleton manuscript
+// Single manuscript mode - get the singleton manuscript, creating it on a fresh
+// database so the frontend always has one to edit
 #[tauri::command]
 pub async fn get_manuscript_safe(
     app: AppHandle,
     db_service: State<'_, DatabaseService>
 ) -> Result<Value, AppError> {
-    let result = retry_with_backoff(|| {
+    let pool = db_service.pool(&app).await?;
+    if crate::db::ensure_singleton_manuscript(&pool).await? {
+        db_service.invalidate_cache("manuscripts").await;
+    }
+
+    let result = db_service.with_retry(|| {
         let app = app.clone();
         let db_service = db_service.inner().clone();
         
@@ -117,11 +128,30 @@ pub async fn get_manuscript_safe(
                 &[]
             ).await
         }
-    }, RetryConfig::default()).await?;
+    }).await?;
     
     Ok(result)
 }
 
+// Typed variants of get_manuscript_safe / get_scenes_safe
+#[tauri::command]
+pub async fn get_manuscript_typed(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>
+) -> Result<Option<Manuscript>, AppError> {
+    let pool = db_service.pool(&app).await?;
+    if crate::db::ensure_singleton_manuscript(&pool).await? {
+        db_service.invalidate_cache("manuscripts").await;
+    }
+
+    crate::db::get_manuscript_impl(&app).await
+}
+
+#[tauri::command]
+pub async fn get_scenes_typed(app: AppHandle) -> Result<Vec<Scene>, AppError> {
+    crate::db::get_all_scenes_impl(&app).await
+}
+
 #[tauri::command]
 pub async fn update_manuscript_safe(
     app: AppHandle,
@@ -135,7 +165,7 @@ pub async fn update_manuscript_safe(
     
     let now = chrono::Utc::now().timestamp_millis();
     
-    let result = retry_with_backoff(|| {
+    let result = db_service.with_retry(|| {
         let app = app.clone();
         let db_service = db_service.inner().clone();
         let title = title.clone();
@@ -155,7 +185,7 @@ pub async fn update_manuscript_safe(
                 ]
             ).await
         }
-    }, RetryConfig::default()).await?;
+    }).await?;
     
     Ok(serde_json::json!({ "success": true }))
 }
@@ -165,7 +195,7 @@ pub async fn get_scenes_safe(
     app: AppHandle,
     db_service: State<'_, DatabaseService>
 ) -> Result<Value, AppError> {
-    let result = retry_with_backoff(|| {
+    let result = db_service.with_retry(|| {
         let app = app.clone();
         let db_service = db_service.inner().clone();
         
@@ -177,7 +207,7 @@ pub async fn get_scenes_safe(
                 &[]
             ).await
         }
-    }, RetryConfig::default()).await?;
+    }).await?;
     
     Ok(result)
 }
@@ -187,7 +217,8 @@ pub async fn update_scene_safe(
     app: AppHandle,
     db_service: State<'_, DatabaseService>,
     scene_id: String,
-    updates: Value
+    updates: Value,
+    expected_updated_at: Option<i64>
 ) -> Result<Value, AppError> {
     validate_scene_id(&scene_id)?;
     
@@ -198,54 +229,202 @@ pub async fn update_scene_safe(
     let raw_text = updates.get("raw_text").and_then(|v| v.as_str());
     
     if let Some(text) = raw_text {
-        if text.len() > 500_000 {
-            return Err(AppError::validation_field(
-                "Scene text too large (max 500KB)",
-                "raw_text",
-                &format!("{} chars", text.len())
-            ));
-        }
+        validate_scene_content(text, "raw_text")?;
     }
     
-    let result = retry_with_backoff(|| {
-        let app = app.clone();
-        let db_service = db_service.inner().clone();
+    let pool = db_service.pool(&app).await?;
+
+    db_service.with_retry(|| {
+        let pool = pool.clone();
         let scene_id = scene_id.clone();
         let title = title.map(|s| s.to_string());
         let raw_text = raw_text.map(|s| s.to_string());
         
         async move {
-            if let Some(text) = &raw_text {
-                let word_count = text.split_whitespace().count() as i32;
-                db_service.execute_with_cache(
-                    &app,
-                    "UPDATE scenes SET raw_text = ?, word_count = ?, updated_at = ? WHERE id = ?",
-                    &[
-                        text.clone(),
-                        word_count.to_string(),
-                        now.to_string(),
-                        scene_id.clone()
-                    ]
-                ).await?;
-            }
-            
-            if let Some(title_text) = &title {
-                db_service.execute_with_cache(
-                    &app,
-                    "UPDATE scenes SET title = ?, updated_at = ? WHERE id = ?",
-                    &[
-                        title_text.clone(),
-                        now.to_string(),
-                        scene_id.clone()
-                    ]
-                ).await?;
-            }
-            
-            Ok::<(), AppError>(())
+            // One transaction per attempt: a stale expected_updated_at is
+            // rejected with a conflict before anything is written, and a
+            // failed attempt leaves nothing behind for the retry to trip over.
+            // The text update recounts the scene and the manuscript total.
+            crate::db::update_scene_fields(
+                &pool,
+                &scene_id,
+                raw_text.as_deref(),
+                title.as_deref(),
+                expected_updated_at,
+                now
+            ).await
         }
-    }, RetryConfig::default()).await?;
+    }).await?;
+
+    db_service.invalidate_cache("scenes").await;
+    if raw_text.is_some() {
+        db_service.invalidate_cache("manuscripts").await;
+    }
     
-    Ok(serde_json::json!({ "success": true }))
+    Ok(serde_json::json!({ "success": true, "updated_at": now }))
+}
+
+// Emitted after an autosave reaches the database
+pub const AUTOSAVE_EVENT: &str = "scene-autosaved";
+
+// How long a scene must go without edits before its autosave is written
+const AUTOSAVE_DEBOUNCE: Duration = Duration::from_millis(1500);
+
+struct PendingAutosave {
+    generation: u64,
+    raw_text: String,
+}
+
+// Latest unsaved text per scene. Each call supersedes the one before it, so
+// only the last edit in a burst is written.
+#[derive(Default)]
+pub struct AutosaveQueue {
+    pending: std::sync::Mutex<HashMap<String, PendingAutosave>>,
+    next_generation: AtomicU64,
+}
+
+impl AutosaveQueue {
+    fn schedule(&self, scene_id: &str, raw_text: String) -> u64 {
+        let generation = self.next_generation.fetch_add(1, Ordering::Relaxed);
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        pending.insert(scene_id.to_string(), PendingAutosave { generation, raw_text });
+        generation
+    }
+
+    fn take_if_latest(&self, scene_id: &str, generation: u64) -> Option<String> {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        if pending.get(scene_id)?.generation != generation {
+            return None;
+        }
+        pending.remove(scene_id).map(|autosave| autosave.raw_text)
+    }
+
+    // Wait out the debounce window; the text comes back only if no newer call
+    // for the same scene arrived in the meantime
+    pub async fn debounce(&self, scene_id: &str, raw_text: String, window: Duration) -> Option<String> {
+        let generation = self.schedule(scene_id, raw_text);
+        tokio::time::sleep(window).await;
+        self.take_if_latest(scene_id, generation)
+    }
+
+    pub fn is_pending(&self, scene_id: &str) -> bool {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner()).contains_key(scene_id)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneAutosaved {
+    pub scene_id: String,
+    pub word_count: u32,
+    pub saved_at: i64,
+}
+
+// Returns false when a later call for the same scene superseded this one. A
+// stale `expected_updated_at` (the saved_at of the last autosave the client
+// saw) fails with a Conflict instead of overwriting another writer's text.
+#[tauri::command]
+pub async fn autosave_scene(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    autosave: State<'_, AutosaveQueue>,
+    scene_id: String,
+    raw_text: String,
+    expected_updated_at: Option<i64>
+) -> Result<bool, AppError> {
+    validate_scene_id(&scene_id)?;
+    validate_scene_content(&raw_text, "raw_text")?;
+
+    let Some(raw_text) = autosave.debounce(&scene_id, raw_text, AUTOSAVE_DEBOUNCE).await else {
+        return Ok(false);
+    };
+
+    let pool = db_service.pool(&app).await?;
+    let saved_at = chrono::Utc::now().timestamp_millis();
+    let word_count = db_service.with_retry(|| {
+        let pool = pool.clone();
+        let scene_id = scene_id.clone();
+        let raw_text = raw_text.clone();
+        async move { crate::db::update_scene_text(&pool, &scene_id, &raw_text, expected_updated_at, saved_at).await }
+    }).await?;
+
+    db_service.invalidate_cache("scenes").await;
+    db_service.invalidate_cache("manuscripts").await;
+
+    app.emit(AUTOSAVE_EVENT, SceneAutosaved { scene_id, word_count, saved_at })
+        .map_err(|e| AppError::internal(format!("Failed to emit {}: {}", AUTOSAVE_EVENT, e)))?;
+    check_word_goals(&app, &pool, SINGLETON_MANUSCRIPT_ID).await?;
+
+    Ok(true)
+}
+
+// Emitted once each time a word goal is crossed
+pub const GOAL_REACHED_EVENT: &str = "goal-reached";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalReached {
+    pub manuscript_id: String,
+    pub kind: GoalKind,
+    pub status: GoalStatus,
+}
+
+async fn check_word_goals(app: &AppHandle, pool: &sqlx::SqlitePool, manuscript_id: &str) -> AppResult<GoalProgress> {
+    let (progress, reached) = crate::db::refresh_goal_progress(pool, manuscript_id).await?;
+
+    for kind in reached {
+        let status = match kind {
+            GoalKind::Project => progress.project.clone(),
+            GoalKind::Daily => progress.daily.clone(),
+        };
+        if let Some(status) = status {
+            app.emit(GOAL_REACHED_EVENT, GoalReached { manuscript_id: manuscript_id.to_string(), kind, status })
+                .map_err(|e| AppError::internal(format!("Failed to emit {}: {}", GOAL_REACHED_EVENT, e)))?;
+        }
+    }
+
+    Ok(progress)
+}
+
+#[tauri::command]
+pub async fn set_word_goal(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    manuscript_id: Option<String>,
+    target: u32,
+    kind: GoalKind
+) -> Result<GoalProgress, AppError> {
+    let manuscript_id = crate::analysis::resolve_manuscript_id(manuscript_id)?;
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let pool = db_service.pool(&app).await?;
+    crate::db::set_word_goal(&pool, &manuscript_id, kind, target, now).await?;
+
+    check_word_goals(&app, &pool, &manuscript_id).await
+}
+
+#[tauri::command]
+pub async fn get_goal_progress(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    manuscript_id: Option<String>
+) -> Result<GoalProgress, AppError> {
+    let manuscript_id = crate::analysis::resolve_manuscript_id(manuscript_id)?;
+
+    let pool = db_service.pool(&app).await?;
+    check_word_goals(&app, &pool, &manuscript_id).await
+}
+
+const MAX_SCENE_CONTENT_BYTES: usize = 500_000;
+
+fn validate_scene_content(content: &str, field: &str) -> AppResult<()> {
+    if content.len() > MAX_SCENE_CONTENT_BYTES {
+        return Err(AppError::validation_field(
+            "Scene content too large (max 500KB)",
+            field,
+            format!("{} chars", content.len()).as_str()
+        ).with_code(ErrorCode::ContentTooLarge));
+    }
+
+    Ok(())
 }
 
 #[tauri::command]
@@ -258,57 +437,93 @@ pub async fn create_scene_safe(
     pov_character: Option<String>
 ) -> Result<Value, AppError> {
     // Validate input
-    if content.len() > 500_000 {
-        return Err(AppError::validation_field(
-            "Scene content too large (max 500KB)",
-            "content",
-            &format!("{} chars", content.len())
-        ));
-    }
-    
-    let scene_id = uuid::Uuid::new_v4().to_string();
-    let now = chrono::Utc::now().timestamp_millis();
-    let word_count = content.split_whitespace().count() as i32;
-    
-    let result = retry_with_backoff(|| {
-        let app = app.clone();
-        let db_service = db_service.inner().clone();
-        let scene_id = scene_id.clone();
-        let title = title.clone();
-        let content = content.clone();
-        let pov_character = pov_character.clone();
-        
+    validate_scene_content(&content, "content")?;
+
+    let rows = prepare_new_scenes(vec![NewScene {
+        title: Some(title).filter(|t| !t.is_empty()),
+        content,
+        chapter_number,
+        pov_character: pov_character.filter(|p| !p.is_empty()),
+    }], chrono::Utc::now().timestamp_millis())?;
+    let scene_id = rows[0].id.clone();
+    let pool = db_service.pool(&app).await?;
+
+    // Appends after the last scene and adds its words to the manuscript total
+    db_service.with_retry(|| {
+        let pool = pool.clone();
+        let mut rows = rows.clone();
+
         async move {
-            // Get the next index
-            let index_result = db_service.execute_with_cache(
-                &app,
-                "SELECT COALESCE(MAX(index_in_manuscript), -1) + 1 as next_index FROM scenes",
-                &[]
-            ).await?;
-            
-            let next_index = 0; // TODO: Parse from index_result
-            
-            db_service.execute_with_cache(
-                &app,
-                "INSERT INTO scenes (id, index_in_manuscript, title, raw_text, word_count, chapter_number, pov_character, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
-                &[
-                    scene_id.clone(),
-                    next_index.to_string(),
-                    title,
-                    content,
-                    word_count.to_string(),
-                    chapter_number.map(|n| n.to_string()).unwrap_or_default(),
-                    pov_character.unwrap_or_default(),
-                    now.to_string(),
-                    now.to_string(),
-                ]
-            ).await?;
-            
-            Ok::<String, AppError>(scene_id)
+            crate::db::append_scenes(&pool, &mut rows).await
         }
-    }, RetryConfig::default()).await?;
+    }).await?;
+
+    db_service.invalidate_cache("scenes").await;
+    db_service.invalidate_cache("manuscripts").await;
     
-    Ok(serde_json::json!({ "id": result }))
+    Ok(serde_json::json!({ "id": scene_id }))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewScene {
+    pub title: Option<String>,
+    pub content: String,
+    pub chapter_number: Option<i32>,
+    pub pov_character: Option<String>,
+}
+
+// Validate every scene up front, then build rows. Indices are assigned when the
+// batch is inserted.
+pub fn prepare_new_scenes(scenes: Vec<NewScene>, now: i64) -> AppResult<Vec<Scene>> {
+    for (i, scene) in scenes.iter().enumerate() {
+        validate_scene_content(&scene.content, &format!("scenes[{}].content", i))?;
+        if let Some(title) = &scene.title {
+            validate_title(title)?;
+        }
+    }
+
+    Ok(scenes
+        .into_iter()
+        .map(|scene| Scene {
+            id: uuid::Uuid::new_v4().to_string(),
+            chapter_number: scene.chapter_number,
+            scene_number_in_chapter: None,
+            index_in_manuscript: 0,
+            title: scene.title,
+            word_count: crate::text::word_count(&scene.content, true),
+            raw_text: scene.content,
+            is_opening: false,
+            is_chapter_end: false,
+            opens_with_hook: false,
+            ends_with_hook: false,
+            pov_character: scene.pov_character,
+            location: None,
+            time_marker: None,
+            created_at: now,
+            updated_at: now,
+        })
+        .collect())
+}
+
+// Insert many scenes in one transaction; returns the new ids in order
+#[tauri::command]
+pub async fn create_scenes_batch(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    scenes: Vec<NewScene>
+) -> Result<Vec<String>, AppError> {
+    let mut rows = prepare_new_scenes(scenes, chrono::Utc::now().timestamp_millis())?;
+    if rows.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let pool = db_service.pool(&app).await?;
+    crate::db::append_scenes(&pool, &mut rows).await?;
+
+    db_service.invalidate_cache("scenes").await;
+    db_service.invalidate_cache("manuscripts").await;
+
+    Ok(rows.into_iter().map(|scene| scene.id).collect())
 }
 
 #[tauri::command]
@@ -319,36 +534,1233 @@ pub async fn delete_scene_safe(
 ) -> Result<Value, AppError> {
     validate_scene_id(&scene_id)?;
     
-    let result = retry_with_backoff(|| {
-        let app = app.clone();
-        let db_service = db_service.inner().clone();
+    let pool = db_service.pool(&app).await?;
+
+    let deleted = db_service.with_retry(|| {
+        let pool = pool.clone();
         let scene_id = scene_id.clone();
         
         async move {
-            db_service.execute_with_cache(
-                &app,
-                "DELETE FROM scenes WHERE id = ?",
-                &[scene_id]
-            ).await
+            crate::db::remove_scene(&pool, &scene_id).await
         }
-    }, RetryConfig::default()).await?;
+    }).await?;
+
+    db_service.invalidate_cache("scenes").await;
+    db_service.invalidate_cache("manuscripts").await;
     
+    Ok(serde_json::json!({ "success": true, "deleted": deleted }))
+}
+
+pub fn validate_score(score: u32, field: &str) -> AppResult<()> {
+    if score > 100 {
+        return Err(AppError::validation_field(
+            "Score must be between 0 and 100",
+            field,
+            score.to_string().as_str()
+        ));
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_scene_hook_flags(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    scene_id: String,
+    opens_with_hook: bool,
+    ends_with_hook: bool
+) -> Result<Value, AppError> {
+    validate_scene_id(&scene_id)?;
+
+    let now = chrono::Utc::now().timestamp_millis();
+
+    let pool = db_service.pool(&app).await?;
+
+    db_service.with_retry(|| {
+        let pool = pool.clone();
+        let scene_id = scene_id.clone();
+
+        async move {
+            crate::db::set_scene_hook_flags(&pool, &scene_id, opens_with_hook, ends_with_hook, now).await
+        }
+    }).await?;
+
+    db_service.invalidate_cache("scenes").await;
+
     Ok(serde_json::json!({ "success": true }))
 }
 
-// Add error logging command for frontend
+// Tag a scene with a structural beat, or clear it with a null beat_type
 #[tauri::command]
-pub async fn get_recent_errors(
+pub async fn set_scene_beat(
     app: AppHandle,
-    limit: Option<usize>
+    db_service: State<'_, DatabaseService>,
+    scene_id: String,
+    beat_type: Option<BeatType>
 ) -> Result<Value, AppError> {
-    let error_logger = ErrorLogger::new();
-    let errors = error_logger.get_recent_errors(limit.unwrap_or(50))
-        .map_err(|e| AppError::file_system(
-            format!("Failed to read error log: {}", e),
-            "read_error_log"
-        ))?;
-    
-    Ok(serde_json::to_value(errors)
-        .map_err(|e| AppError::internal(format!("Failed to serialize errors: {}", e)))?)
-}
\ No newline at end of file
+    validate_scene_id(&scene_id)?;
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let pool = db_service.pool(&app).await?;
+
+    db_service.with_retry(|| {
+        let pool = pool.clone();
+        let scene_id = scene_id.clone();
+
+        async move {
+            crate::db::set_scene_beat(&pool, &scene_id, beat_type, now).await
+        }
+    }).await?;
+
+    Ok(serde_json::json!({ "success": true }))
+}
+
+// The beat map: every tagged scene in manuscript order
+#[tauri::command]
+pub async fn get_manuscript_beats(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    manuscript_id: Option<String>
+) -> Result<Vec<SceneBeat>, AppError> {
+    crate::analysis::resolve_manuscript_id(manuscript_id)?;
+
+    let pool = db_service.pool(&app).await?;
+    crate::db::get_manuscript_beats(&pool).await
+}
+
+// Plant a setup at a span of a scene for payoff tracking
+#[tauri::command]
+pub async fn create_plant(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    plant: NewPlant
+) -> Result<Plant, AppError> {
+    validate_scene_id(&plant.scene_id)?;
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let pool = db_service.pool(&app).await?;
+
+    db_service.with_retry(|| {
+        let pool = pool.clone();
+        let plant = &plant;
+
+        async move {
+            crate::db::create_plant(&pool, plant, now).await
+        }
+    }).await
+}
+
+// Link the scene that pays off a plant; a null payoff_scene_id unlinks it
+#[tauri::command]
+pub async fn link_plant_payoff(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    plant_id: String,
+    payoff_scene_id: Option<String>
+) -> Result<Value, AppError> {
+    if let Some(scene_id) = &payoff_scene_id {
+        validate_scene_id(scene_id)?;
+    }
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let pool = db_service.pool(&app).await?;
+
+    db_service.with_retry(|| {
+        let pool = pool.clone();
+        let plant_id = plant_id.clone();
+        let payoff_scene_id = payoff_scene_id.clone();
+
+        async move {
+            crate::db::set_plant_payoff(&pool, &plant_id, payoff_scene_id.as_deref(), now).await
+        }
+    }).await?;
+
+    Ok(serde_json::json!({ "success": true }))
+}
+
+// Setups that no scene pays off yet, in manuscript order
+#[tauri::command]
+pub async fn get_unpaid_plants(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    manuscript_id: Option<String>
+) -> Result<Vec<Plant>, AppError> {
+    crate::analysis::resolve_manuscript_id(manuscript_id)?;
+
+    let pool = db_service.pool(&app).await?;
+    crate::db::get_unpaid_plants(&pool).await
+}
+
+// Store the plot events extracted from a scene, replacing any recorded before
+#[tauri::command]
+pub async fn record_scene_events(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    scene_id: String,
+    events: Vec<SceneEvent>
+) -> Result<Value, AppError> {
+    validate_scene_id(&scene_id)?;
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let pool = db_service.pool(&app).await?;
+
+    db_service.with_retry(|| {
+        let pool = pool.clone();
+        let scene_id = scene_id.clone();
+        let events = &events;
+
+        async move {
+            crate::db::record_scene_events(&pool, &scene_id, events, now).await
+        }
+    }).await?;
+
+    Ok(serde_json::json!({ "success": true, "count": events.len() }))
+}
+
+// Every recorded plot event, ordered for a timeline view
+#[tauri::command]
+pub async fn get_manuscript_events(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    manuscript_id: Option<String>
+) -> Result<Vec<ManuscriptEvent>, AppError> {
+    crate::analysis::resolve_manuscript_id(manuscript_id)?;
+
+    let pool = db_service.pool(&app).await?;
+    crate::db::get_manuscript_events(&pool).await
+}
+
+// Record a character or world fact as it stands at a scene; a null value
+// removes it
+#[tauri::command]
+pub async fn set_state_at_scene(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    scene_id: String,
+    key: String,
+    value: Option<String>
+) -> Result<Value, AppError> {
+    validate_scene_id(&scene_id)?;
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let pool = db_service.pool(&app).await?;
+
+    db_service.with_retry(|| {
+        let pool = pool.clone();
+        let scene_id = scene_id.clone();
+        let key = key.clone();
+        let value = value.clone();
+
+        async move {
+            crate::db::set_state_at_scene(&pool, &scene_id, &key, value.as_deref(), now).await
+        }
+    }).await?;
+
+    Ok(serde_json::json!({ "success": true }))
+}
+
+// A state key's values across the manuscript, for continuity checks
+#[tauri::command]
+pub async fn get_state_timeline(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    manuscript_id: Option<String>,
+    key: String
+) -> Result<Vec<StateChange>, AppError> {
+    crate::analysis::resolve_manuscript_id(manuscript_id)?;
+
+    let pool = db_service.pool(&app).await?;
+    crate::db::get_state_timeline(&pool, &key).await
+}
+
+// Refresh every stale analysis module, one transaction per scene
+#[tauri::command]
+pub async fn recompute_dirty_modules(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    manuscript_id: Option<String>
+) -> Result<Vec<RecomputedScene>, AppError> {
+    crate::analysis::resolve_manuscript_id(manuscript_id)?;
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let pool = db_service.pool(&app).await?;
+
+    crate::db::recompute_dirty_modules(&pool, now).await
+}
+
+#[tauri::command]
+pub async fn add_comment(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    scene_id: String,
+    position: u32,
+    text: String,
+    author: Option<String>
+) -> Result<SceneComment, AppError> {
+    validate_scene_id(&scene_id)?;
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let pool = db_service.pool(&app).await?;
+
+    crate::db::add_comment(&pool, &scene_id, position, &text, author.as_deref(), now).await
+}
+
+#[tauri::command]
+pub async fn get_scene_comments(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    scene_id: String
+) -> Result<Vec<SceneComment>, AppError> {
+    validate_scene_id(&scene_id)?;
+
+    let pool = db_service.pool(&app).await?;
+    crate::db::get_scene_comments(&pool, &scene_id).await
+}
+
+#[tauri::command]
+pub async fn delete_comment(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    comment_id: String
+) -> Result<Value, AppError> {
+    let pool = db_service.pool(&app).await?;
+    if !crate::db::delete_comment(&pool, &comment_id).await? {
+        return Err(AppError::not_found_with_id("Comment", comment_id.as_str()));
+    }
+
+    Ok(serde_json::json!({"success": true}))
+}
+
+#[tauri::command]
+pub async fn get_database_size(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>
+) -> Result<DatabaseSize, AppError> {
+    let pool = db_service.pool(&app).await?;
+    crate::db::get_database_size(&pool).await
+}
+
+// Reclaim space left by deleted revisions and backups, then refresh the
+// query planner's statistics
+#[tauri::command]
+pub async fn vacuum_database(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>
+) -> Result<VacuumResult, AppError> {
+    let pool = db_service.pool(&app).await?;
+    crate::db::vacuum_database(&pool).await
+}
+
+// Run before restoring or exporting to confirm the database isn't corrupt
+#[tauri::command]
+pub async fn check_database_integrity(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>
+) -> Result<IntegrityReport, AppError> {
+    let pool = db_service.pool(&app).await?;
+    crate::db::check_database_integrity(&pool).await
+}
+
+#[tauri::command]
+pub async fn move_scene_to_chapter(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    scene_id: String,
+    target_chapter_number: i32,
+    position: u32
+) -> Result<Value, AppError> {
+    validate_scene_id(&scene_id)?;
+
+    let pool = db_service.pool(&app).await?;
+    db_service.with_retry(|| {
+        let pool = pool.clone();
+        let scene_id = scene_id.clone();
+        async move { crate::db::move_scene_to_chapter(&pool, &scene_id, target_chapter_number, position).await }
+    }).await?;
+
+    db_service.invalidate_cache("scenes").await;
+
+    Ok(serde_json::json!({"success": true}))
+}
+
+// Stored scene HTML cleaned up for the comparison and distraction-free views,
+// which inject it directly. Imports can leave unbalanced or unsafe markup.
+#[tauri::command]
+pub async fn render_scene_preview(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    scene_id: String
+) -> Result<String, AppError> {
+    validate_scene_id(&scene_id)?;
+
+    let pool = db_service.pool(&app).await?;
+    let html = crate::db::get_scene_text(&pool, &scene_id).await?;
+
+    Ok(crate::text::sanitize_html(&html))
+}
+
+// Save a branch of the manuscript to a new database file before a big revision
+#[tauri::command]
+pub async fn duplicate_manuscript(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    source_id: Option<String>,
+    new_title: String,
+    destination: String
+) -> Result<ManuscriptCopy, AppError> {
+    crate::analysis::resolve_manuscript_id(source_id)?;
+    let destination = std::path::PathBuf::from(&destination);
+    if !destination.is_absolute() {
+        return Err(AppError::validation_field(
+            "Destination path must be absolute".to_string(),
+            "destination".to_string(),
+            destination.to_string_lossy().into_owned()
+        ));
+    }
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let pool = db_service.pool(&app).await?;
+    crate::db::duplicate_manuscript(&pool, &destination, &new_title, now).await
+}
+
+// Repair indices, word counts and chapter numbering after bulk imports
+#[tauri::command]
+pub async fn reindex_manuscript(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    manuscript_id: Option<String>
+) -> Result<ReindexSummary, AppError> {
+    crate::analysis::resolve_manuscript_id(manuscript_id)?;
+
+    let pool = db_service.pool(&app).await?;
+    let summary = crate::db::reindex_manuscript(&pool).await?;
+
+    db_service.invalidate_cache("scenes").await;
+    db_service.invalidate_cache("manuscripts").await;
+
+    Ok(summary)
+}
+
+#[tauri::command]
+pub async fn add_scene_tag(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    scene_id: String,
+    tag: String
+) -> Result<Value, AppError> {
+    validate_scene_id(&scene_id)?;
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let pool = db_service.pool(&app).await?;
+    crate::db::add_scene_tag(&pool, &scene_id, &tag, now).await?;
+
+    Ok(serde_json::json!({"success": true}))
+}
+
+#[tauri::command]
+pub async fn remove_scene_tag(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    scene_id: String,
+    tag: String
+) -> Result<Value, AppError> {
+    validate_scene_id(&scene_id)?;
+
+    let pool = db_service.pool(&app).await?;
+    let removed = crate::db::remove_scene_tag(&pool, &scene_id, &tag).await?;
+
+    Ok(serde_json::json!({"success": true, "removed": removed}))
+}
+
+#[tauri::command]
+pub async fn get_scenes_by_tag(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    manuscript_id: Option<String>,
+    tag: String
+) -> Result<Vec<TaggedScene>, AppError> {
+    crate::analysis::resolve_manuscript_id(manuscript_id)?;
+
+    let pool = db_service.pool(&app).await?;
+    crate::db::get_scenes_by_tag(&pool, &tag).await
+}
+
+#[tauri::command]
+pub async fn create_note(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    manuscript_id: Option<String>,
+    title: String,
+    body: String
+) -> Result<Note, AppError> {
+    let manuscript_id = crate::analysis::resolve_manuscript_id(manuscript_id)?;
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let pool = db_service.pool(&app).await?;
+
+    crate::db::create_note(&pool, &manuscript_id, &title, &body, now).await
+}
+
+#[tauri::command]
+pub async fn list_notes(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    manuscript_id: Option<String>
+) -> Result<Vec<Note>, AppError> {
+    let manuscript_id = crate::analysis::resolve_manuscript_id(manuscript_id)?;
+
+    let pool = db_service.pool(&app).await?;
+    crate::db::list_notes(&pool, &manuscript_id).await
+}
+
+#[tauri::command]
+pub async fn update_note(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    manuscript_id: Option<String>,
+    note_id: String,
+    title: String,
+    body: String
+) -> Result<Note, AppError> {
+    let manuscript_id = crate::analysis::resolve_manuscript_id(manuscript_id)?;
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let pool = db_service.pool(&app).await?;
+
+    crate::db::update_note(&pool, &manuscript_id, &note_id, &title, &body, now).await
+}
+
+#[tauri::command]
+pub async fn delete_note(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    manuscript_id: Option<String>,
+    note_id: String
+) -> Result<Value, AppError> {
+    let manuscript_id = crate::analysis::resolve_manuscript_id(manuscript_id)?;
+
+    let pool = db_service.pool(&app).await?;
+    if !crate::db::delete_note(&pool, &manuscript_id, &note_id).await? {
+        return Err(AppError::not_found_with_id("Note", note_id.as_str()));
+    }
+
+    Ok(serde_json::json!({"success": true}))
+}
+
+#[tauri::command]
+pub async fn update_opening_scores(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    manuscript_id: Option<String>,
+    opening_strength: u32,
+    hook_effectiveness: u32
+) -> Result<Value, AppError> {
+    crate::analysis::resolve_manuscript_id(manuscript_id)?;
+    validate_score(opening_strength, "opening_strength")?;
+    validate_score(hook_effectiveness, "hook_effectiveness")?;
+
+    let now = chrono::Utc::now().timestamp_millis();
+
+    let pool = db_service.pool(&app).await?;
+
+    db_service.with_retry(|| {
+        let pool = pool.clone();
+
+        async move {
+            crate::db::store_opening_scores(&pool, SINGLETON_MANUSCRIPT_ID, opening_strength, hook_effectiveness, now).await
+        }
+    }).await?;
+
+    db_service.invalidate_cache("manuscripts").await;
+
+    Ok(serde_json::json!({ "success": true }))
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChapterAssignment {
+    pub scene_id: String,
+    pub chapter_number: i32,
+    pub scene_number_in_chapter: i32,
+}
+
+// Walk scenes in manuscript order and assign contiguous chapter numbers. A new
+// chapter starts after a scene marked as a chapter end, or wherever the stored
+// chapter number changes. Only scenes whose numbering changes are returned.
+pub fn plan_chapter_renumbering(scenes: &[Scene]) -> Vec<ChapterAssignment> {
+    let mut ordered: Vec<&Scene> = scenes.iter().collect();
+    ordered.sort_by_key(|s| s.index_in_manuscript);
+
+    let mut changes = Vec::new();
+    let mut chapter = 0;
+    let mut scene_in_chapter = 0;
+
+    for (i, scene) in ordered.iter().enumerate() {
+        let starts_chapter = match i.checked_sub(1).map(|p| ordered[p]) {
+            None => true,
+            Some(previous) => previous.is_chapter_end || previous.chapter_number != scene.chapter_number,
+        };
+
+        if starts_chapter {
+            chapter += 1;
+            scene_in_chapter = 0;
+        }
+        scene_in_chapter += 1;
+
+        if scene.chapter_number != Some(chapter) || scene.scene_number_in_chapter != Some(scene_in_chapter) {
+            changes.push(ChapterAssignment {
+                scene_id: scene.id.clone(),
+                chapter_number: chapter,
+                scene_number_in_chapter: scene_in_chapter,
+            });
+        }
+    }
+
+    changes
+}
+
+#[tauri::command]
+pub async fn renumber_chapters(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    manuscript_id: Option<String>
+) -> Result<Value, AppError> {
+    crate::analysis::resolve_manuscript_id(manuscript_id)?;
+
+    let scenes = crate::analysis::load_manuscript_scenes(&app).await?;
+    let changes = plan_chapter_renumbering(&scenes);
+
+    if changes.is_empty() {
+        return Ok(serde_json::json!({ "updated": 0, "changes": changes }));
+    }
+
+    // One UPDATE statement so the renumbering is applied atomically
+    let mut params = Vec::new();
+    let mut chapter_cases = String::new();
+    for change in &changes {
+        chapter_cases.push_str(" WHEN ? THEN ?");
+        params.push(change.scene_id.clone());
+        params.push(change.chapter_number.to_string());
+    }
+    let mut scene_cases = String::new();
+    for change in &changes {
+        scene_cases.push_str(" WHEN ? THEN ?");
+        params.push(change.scene_id.clone());
+        params.push(change.scene_number_in_chapter.to_string());
+    }
+    params.push(chrono::Utc::now().timestamp_millis().to_string());
+    params.extend(changes.iter().map(|c| c.scene_id.clone()));
+
+    let placeholders = vec!["?"; changes.len()].join(", ");
+    let query = format!(
+        "UPDATE scenes SET chapter_number = CASE id{} END, scene_number_in_chapter = CASE id{} END, updated_at = ? WHERE id IN ({})",
+        chapter_cases, scene_cases, placeholders
+    );
+
+    db_service.with_retry(|| {
+        let app = app.clone();
+        let db_service = db_service.inner().clone();
+        let query = query.clone();
+        let params = params.clone();
+
+        async move {
+            db_service.execute_with_cache(&app, &query, &params).await
+        }
+    }).await?;
+
+    db_service.invalidate_cache("scenes").await;
+
+    Ok(serde_json::json!({ "updated": changes.len(), "changes": changes }))
+}
+
+// Rows for an imported file: the singleton manuscript plus its detected scenes
+// in order, numbered within their chapters
+pub fn plan_imported_manuscript(import: &ContentReplacement, now: i64) -> (Manuscript, Vec<Scene>) {
+    let title = import.metadata.title
+        .clone()
+        .filter(|title| !title.trim().is_empty())
+        .unwrap_or_else(|| {
+            std::path::Path::new(&import.filename)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| import.filename.clone())
+        });
+
+    let manuscript = Manuscript {
+        id: SINGLETON_MANUSCRIPT_ID.to_string(),
+        title,
+        author: import.metadata.author.clone(),
+        genre: None,
+        target_audience: None,
+        comp_titles: None,
+        created_at: now,
+        updated_at: now,
+        total_word_count: import.word_count,
+        opening_strength_score: None,
+        hook_effectiveness: None,
+    };
+
+    let mut scenes: Vec<Scene> = Vec::with_capacity(import.scenes.len());
+    let mut scene_in_chapter = 0;
+    for (index, info) in import.scenes.iter().enumerate() {
+        let chapter_number = info.chapter_number.map(|c| c as i32);
+        let starts_chapter = matches!(info.break_type, SceneBreakType::ChapterStart)
+            || scenes.last().map_or(true, |previous| previous.chapter_number != chapter_number);
+
+        if starts_chapter {
+            scene_in_chapter = 0;
+            if let Some(previous) = scenes.last_mut() {
+                previous.is_chapter_end = true;
+            }
+        }
+        scene_in_chapter += 1;
+
+        scenes.push(Scene {
+            id: uuid::Uuid::new_v4().to_string(),
+            chapter_number,
+            scene_number_in_chapter: Some(scene_in_chapter),
+            index_in_manuscript: index as u32,
+            title: info.title.clone(),
+            raw_text: info.content.clone(),
+            word_count: info.word_count,
+            is_opening: index == 0,
+            is_chapter_end: false,
+            opens_with_hook: false,
+            ends_with_hook: false,
+            pov_character: None,
+            location: None,
+            time_marker: None,
+            created_at: now,
+            updated_at: now,
+        });
+    }
+    if let Some(last) = scenes.last_mut() {
+        last.is_chapter_end = true;
+    }
+
+    (manuscript, scenes)
+}
+
+// Delete the manuscript together with its scenes and per-scene data, atomically
+#[tauri::command]
+pub async fn delete_manuscript_safe(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    manuscript_id: Option<String>
+) -> Result<Value, AppError> {
+    crate::analysis::resolve_manuscript_id(manuscript_id)?;
+
+    let pool = db_service.pool(&app).await?;
+    db_service.with_retry(|| {
+        let pool = pool.clone();
+
+        async move {
+            crate::db::delete_manuscript(&pool).await
+        }
+    }).await?;
+
+    db_service.invalidate_cache("manuscripts").await;
+    db_service.invalidate_cache("scenes").await;
+
+    Ok(serde_json::json!({ "success": true }))
+}
+
+// Import a file and store it as the manuscript. An existing manuscript with
+// scenes is only replaced when `replace` is true; otherwise this is a Conflict.
+#[tauri::command]
+pub async fn import_and_create_manuscript(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    file_path: String,
+    options: Option<ImportOptions>,
+    replace: Option<bool>
+) -> Result<String, AppError> {
+    let options = options.unwrap_or_default();
+    options.validate()?;
+
+    let import = crate::fs::import_file(&file_path, &options).await?;
+    store_import(&app, db_service.inner(), &import, replace.unwrap_or(false)).await
+}
+
+async fn store_import(
+    app: &AppHandle,
+    db_service: &DatabaseService,
+    import: &ContentReplacement,
+    replace: bool
+) -> AppResult<String> {
+    let (manuscript, scenes) = plan_imported_manuscript(import, chrono::Utc::now().timestamp_millis());
+    let pool = db_service.pool(app).await?;
+
+    db_service.with_retry(|| {
+        let pool = pool.clone();
+        let manuscript = &manuscript;
+        let scenes = &scenes;
+
+        async move {
+            crate::db::import_manuscript(&pool, manuscript, scenes, replace).await
+        }
+    }).await?;
+
+    db_service.invalidate_cache("manuscripts").await;
+    db_service.invalidate_cache("scenes").await;
+
+    Ok(manuscript.id)
+}
+
+// Only the most recent previews are kept; older tokens expire
+const MAX_PENDING_IMPORTS: usize = 5;
+
+// Parsed imports waiting for the user to confirm them, keyed by preview token
+#[derive(Default)]
+pub struct PendingImports {
+    imports: std::sync::Mutex<Vec<(String, ContentReplacement)>>,
+}
+
+impl PendingImports {
+    pub fn insert(&self, import: ContentReplacement) -> String {
+        let token = uuid::Uuid::new_v4().to_string();
+        let mut imports = self.imports.lock().unwrap_or_else(|e| e.into_inner());
+        if imports.len() >= MAX_PENDING_IMPORTS {
+            imports.remove(0);
+        }
+        imports.push((token.clone(), import));
+        token
+    }
+
+    pub fn take(&self, token: &str) -> Option<ContentReplacement> {
+        let mut imports = self.imports.lock().unwrap_or_else(|e| e.into_inner());
+        let position = imports.iter().position(|(t, _)| t == token)?;
+        Some(imports.remove(position).1)
+    }
+
+    // Put back an import that was taken but not stored, under its old token
+    pub fn restore(&self, token: String, import: ContentReplacement) {
+        let mut imports = self.imports.lock().unwrap_or_else(|e| e.into_inner());
+        if imports.len() >= MAX_PENDING_IMPORTS {
+            imports.remove(0);
+        }
+        imports.push((token, import));
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChapterPreview {
+    pub chapter_number: Option<u32>,
+    pub title: Option<String>,
+    pub scene_count: u32,
+    pub word_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenePreview {
+    pub title: Option<String>,
+    pub chapter_number: Option<u32>,
+    pub word_count: u32,
+    pub excerpt: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportPreview {
+    pub preview_token: String,
+    pub filename: String,
+    pub chapters: Vec<ChapterPreview>,
+    pub scenes: Vec<ScenePreview>,
+    pub warnings: Vec<String>,
+    pub word_count: u32,
+}
+
+const PREVIEW_EXCERPT_CHARS: usize = 120;
+
+pub fn build_import_preview(preview_token: String, import: &ContentReplacement) -> ImportPreview {
+    let mut chapters: Vec<ChapterPreview> = Vec::new();
+    let mut scenes = Vec::with_capacity(import.scenes.len());
+
+    for scene in &import.scenes {
+        let starts_chapter = matches!(scene.break_type, SceneBreakType::ChapterStart)
+            || !chapters.last().is_some_and(|c| c.chapter_number == scene.chapter_number);
+        if starts_chapter {
+            chapters.push(ChapterPreview {
+                chapter_number: scene.chapter_number,
+                title: scene.title.clone(),
+                scene_count: 0,
+                word_count: 0,
+            });
+        }
+        if let Some(chapter) = chapters.last_mut() {
+            chapter.scene_count += 1;
+            chapter.word_count += scene.word_count;
+        }
+
+        let text = crate::text::strip_html(&scene.content).text;
+        scenes.push(ScenePreview {
+            title: scene.title.clone(),
+            chapter_number: scene.chapter_number,
+            word_count: scene.word_count,
+            excerpt: text.split_whitespace().collect::<Vec<_>>().join(" ").chars().take(PREVIEW_EXCERPT_CHARS).collect(),
+        });
+    }
+
+    ImportPreview {
+        preview_token,
+        filename: import.filename.clone(),
+        chapters,
+        scenes,
+        warnings: import.import_warnings.clone(),
+        word_count: import.word_count,
+    }
+}
+
+// Parse a file and describe what an import would create, without writing it
+#[tauri::command]
+pub async fn preview_import(
+    pending: State<'_, PendingImports>,
+    file_path: String,
+    options: Option<ImportOptions>
+) -> Result<ImportPreview, AppError> {
+    let options = options.unwrap_or_default();
+    options.validate()?;
+
+    let import = crate::fs::import_file(&file_path, &options).await?;
+    let preview = build_import_preview(String::new(), &import);
+    let token = pending.insert(import);
+
+    Ok(ImportPreview { preview_token: token, ..preview })
+}
+
+// Store a previewed import as the manuscript. As with import_and_create_manuscript,
+// a manuscript with scenes is only replaced when `replace` is true. A refused
+// import stays pending, so it can be committed again once confirmed.
+#[tauri::command]
+pub async fn commit_import(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    pending: State<'_, PendingImports>,
+    preview_token: String,
+    replace: Option<bool>
+) -> Result<String, AppError> {
+    let import = pending.take(&preview_token)
+        .ok_or_else(|| AppError::not_found_with_id("Import preview", preview_token.as_str()))?;
+
+    match store_import(&app, db_service.inner(), &import, replace.unwrap_or(false)).await {
+        Err(e @ AppError::Conflict { .. }) => {
+            pending.restore(preview_token, import);
+            Err(e)
+        }
+        result => result,
+    }
+}
+
+// Add error logging command for frontend
+#[tauri::command]
+pub async fn get_recent_errors(
+    app: AppHandle,
+    limit: Option<usize>,
+    filter: Option<ErrorLogFilter>
+) -> Result<Value, AppError> {
+    let error_logger = ErrorLogger::new();
+    let errors = error_logger.get_recent_errors_filtered(limit.unwrap_or(50), &filter.unwrap_or_default())
+        .map_err(|e| AppError::file_system(
+            format!("Failed to read error log: {}", e),
+            "read_error_log"
+        ))?;
+    
+    Ok(serde_json::to_value(errors)
+        .map_err(|e| AppError::internal(format!("Failed to serialize errors: {}", e)))?)
+}
+
+// Error counts by type and severity, for support diagnostics
+#[tauri::command]
+pub async fn get_error_statistics(_app: AppHandle) -> Result<ErrorStats, AppError> {
+    ErrorLogger::new()
+        .error_statistics()
+        .map_err(|e| AppError::file_system(
+            format!("Failed to read error log: {}", e),
+            "read_error_log"
+        ))
+}
+
+pub const ERROR_LOGGED_EVENT: &str = "error-logged";
+
+const ERROR_LOG_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// The running error log stream, if any. Dropping the watcher stops it.
+#[derive(Default)]
+pub struct ErrorLogStream {
+    watcher: std::sync::Mutex<Option<ErrorLogWatcher>>,
+}
+
+// Emits each entry written to the error log from now on as an error-logged
+// event. Starting a stream that is already running does nothing.
+#[tauri::command]
+pub async fn start_error_log_stream(app: AppHandle, stream: State<'_, ErrorLogStream>) -> Result<(), AppError> {
+    let mut watcher = stream.watcher.lock().unwrap_or_else(|e| e.into_inner());
+    if watcher.is_none() {
+        let path = ErrorLogger::new().log_path().to_path_buf();
+        *watcher = Some(ErrorLogWatcher::spawn(path, ERROR_LOG_POLL_INTERVAL, move |entry| {
+            if let Err(e) = app.emit(ERROR_LOGGED_EVENT, entry) {
+                eprintln!("Failed to emit {}: {}", ERROR_LOGGED_EVENT, e);
+            }
+        }));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn stop_error_log_stream(stream: State<'_, ErrorLogStream>) -> Result<(), AppError> {
+    stream.watcher.lock().unwrap_or_else(|e| e.into_inner()).take();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::scene;
+
+    #[test]
+    fn test_scores_must_be_within_range() {
+        assert!(validate_score(0, "opening_strength").is_ok());
+        assert!(validate_score(100, "opening_strength").is_ok());
+        assert!(matches!(
+            validate_score(101, "hook_effectiveness"),
+            Err(AppError::Validation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_renumbering_closes_chapter_gaps() {
+        let scenes = vec![
+            scene(0, Some(1), ""),
+            scene(1, Some(1), ""),
+            scene(2, Some(4), ""),
+            scene(3, Some(7), ""),
+            scene(4, Some(7), ""),
+        ];
+        let changes = plan_chapter_renumbering(&scenes);
+
+        let mut numbers: Vec<i32> = scenes.iter().map(|s| s.chapter_number.unwrap()).collect();
+        for change in &changes {
+            let index = scenes.iter().position(|s| s.id == change.scene_id).unwrap();
+            numbers[index] = change.chapter_number;
+        }
+        assert_eq!(numbers, vec![1, 1, 2, 3, 3]);
+        assert_eq!(changes.iter().find(|c| c.scene_id == "scene-4").unwrap().scene_number_in_chapter, 2);
+    }
+
+    #[test]
+    fn test_import_and_create_persists_detected_scenes() {
+        tokio_test::block_on(async {
+            let temp = tempfile::tempdir().unwrap();
+            let path = temp.path().join("novel.md");
+            std::fs::write(
+                &path,
+                "# Chapter 1\n\nIt began.\n\n* * *\n\nIt continued.\n\n# Chapter 2\n\nIt ended."
+            ).unwrap();
+
+            let import = crate::fs::import_file(path.to_str().unwrap(), &ImportOptions::default()).await.unwrap();
+            let (manuscript, scenes) = plan_imported_manuscript(&import, 42);
+            assert_eq!(manuscript.id, SINGLETON_MANUSCRIPT_ID);
+
+            let pool = crate::db::tests::memory_pool().await;
+            crate::db::replace_manuscript_with_scenes(&pool, &manuscript, &scenes).await.unwrap();
+
+            let rows: Vec<(i64, Option<i64>, Option<i64>, bool)> = sqlx::query_as(
+                "SELECT index_in_manuscript, chapter_number, scene_number_in_chapter, is_chapter_end FROM scenes ORDER BY index_in_manuscript"
+            )
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+            assert_eq!(rows, vec![
+                (0, Some(1), Some(1), false),
+                (1, Some(1), Some(2), true),
+                (2, Some(2), Some(1), true),
+            ]);
+
+            let id: String = sqlx::query_scalar("SELECT id FROM manuscripts")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(id, manuscript.id);
+        });
+    }
+
+    #[test]
+    fn test_import_refuses_to_replace_manuscript_with_scenes() {
+        tokio_test::block_on(async {
+            let pool = crate::db::tests::memory_pool().await;
+            let existing = crate::db::tests::manuscript("Existing");
+            let scene = crate::analysis::tests::scene(0, Some(1), "<p>Keep me.</p>");
+
+            // An empty manuscript is replaced without asking
+            crate::db::import_manuscript(&pool, &existing, &[], false).await.unwrap();
+            crate::db::import_manuscript(&pool, &existing, &[scene.clone()], false).await.unwrap();
+
+            let imported = crate::db::tests::manuscript("Imported");
+            let refused = crate::db::import_manuscript(&pool, &imported, &[], false).await;
+            assert!(matches!(refused, Err(AppError::Conflict { .. })), "got {:?}", refused);
+
+            let kept: (String, i64) = sqlx::query_as("SELECT (SELECT title FROM manuscripts), (SELECT COUNT(*) FROM scenes)")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(kept, ("Existing".to_string(), 1));
+
+            crate::db::import_manuscript(&pool, &imported, &[], true).await.unwrap();
+            let title: String = sqlx::query_scalar("SELECT title FROM manuscripts")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(title, "Imported");
+        });
+    }
+
+    fn new_scene(content: &str) -> NewScene {
+        NewScene {
+            title: None,
+            content: content.to_string(),
+            chapter_number: Some(1),
+            pov_character: None,
+        }
+    }
+
+    #[test]
+    fn test_create_scenes_batch_inserts_all_in_order() {
+        tokio_test::block_on(async {
+            let pool = crate::db::tests::memory_pool().await;
+            let batch: Vec<NewScene> = (0..100).map(|i| new_scene(&format!("Scene number {}", i))).collect();
+
+            let mut rows = prepare_new_scenes(batch, 1).unwrap();
+            let ids: Vec<String> = rows.iter().map(|s| s.id.clone()).collect();
+            crate::db::append_scenes(&pool, &mut rows).await.unwrap();
+
+            let stored: Vec<String> = sqlx::query_scalar("SELECT id FROM scenes ORDER BY index_in_manuscript")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+            assert_eq!(stored, ids);
+        });
+    }
+
+    #[test]
+    fn test_create_scenes_batch_rejects_whole_batch_on_invalid_scene() {
+        let mut batch: Vec<NewScene> = (0..100).map(|i| new_scene(&format!("Scene {}", i))).collect();
+        batch[50].content = "x".repeat(MAX_SCENE_CONTENT_BYTES + 1);
+
+        match prepare_new_scenes(batch, 1) {
+            Err(AppError::Validation { field, code, .. }) => {
+                assert_eq!(field.as_deref(), Some("scenes[50].content"));
+                assert_eq!(code.as_deref(), Some(ErrorCode::ContentTooLarge.as_str()));
+            }
+            other => panic!("expected validation error, got {:?}", other.map(|rows| rows.len())),
+        }
+    }
+
+    #[test]
+    fn test_preview_then_commit_keeps_scene_count() {
+        tokio_test::block_on(async {
+            let temp = tempfile::tempdir().unwrap();
+            let path = temp.path().join("draft.md");
+            std::fs::write(
+                &path,
+                "# Chapter 1\n\nFirst scene.\n\n* * *\n\nSecond scene.\n\n# Chapter 2\n\nThird scene."
+            ).unwrap();
+
+            let pending = PendingImports::default();
+            let import = crate::fs::import_file(path.to_str().unwrap(), &ImportOptions::default()).await.unwrap();
+            let preview = build_import_preview(String::new(), &import);
+            let token = pending.insert(import);
+
+            assert_eq!(preview.scenes.len(), 3);
+            let chapter_sizes: Vec<u32> = preview.chapters.iter().map(|c| c.scene_count).collect();
+            assert_eq!(chapter_sizes, vec![2, 1]);
+            assert_eq!(preview.scenes[1].excerpt, "Second scene.");
+
+            let committed = pending.take(&token).unwrap();
+            assert!(pending.take(&token).is_none());
+            let (manuscript, scenes) = plan_imported_manuscript(&committed, 1);
+            let pool = crate::db::tests::memory_pool().await;
+            crate::db::replace_manuscript_with_scenes(&pool, &manuscript, &scenes).await.unwrap();
+
+            let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM scenes").fetch_one(&pool).await.unwrap();
+            assert_eq!(stored as usize, preview.scenes.len());
+
+            // Committing over those scenes needs the replace flag; a refused
+            // import can be put back and committed once confirmed
+            let token = pending.insert(committed);
+            let again = pending.take(&token).unwrap();
+            let refused = crate::db::import_manuscript(&pool, &manuscript, &scenes[..1], false).await;
+            assert!(matches!(refused, Err(AppError::Conflict { .. })));
+            pending.restore(token.clone(), again);
+            assert!(pending.take(&token).is_some());
+        });
+    }
+
+    #[test]
+    fn test_pending_imports_expire_oldest_first() {
+        let pending = PendingImports::default();
+        let replacement = |name: &str| ContentReplacement {
+            filename: name.to_string(),
+            content: String::new(),
+            word_count: 0,
+            format: "txt".to_string(),
+            scenes: Vec::new(),
+            metadata: crate::fs::FileMetadata {
+                author: None,
+                title: None,
+                created: None,
+                modified: None,
+                has_formatting: false,
+                encoding: "UTF-8".to_string(),
+                file_size: 0,
+                line_count: 0,
+            },
+            import_warnings: Vec::new(),
+        };
+
+        let first = pending.insert(replacement("first.txt"));
+        let tokens: Vec<String> = (0..MAX_PENDING_IMPORTS).map(|i| pending.insert(replacement(&format!("{}.txt", i)))).collect();
+
+        assert!(pending.take(&first).is_none());
+        assert_eq!(pending.take(&tokens[0]).unwrap().filename, "0.txt");
+    }
+
+    #[test]
+    fn test_chapter_end_flag_starts_new_chapter() {
+        let mut first = scene(0, None, "");
+        first.is_chapter_end = true;
+        let scenes = vec![first, scene(1, None, ""), scene(2, None, "")];
+        let changes = plan_chapter_renumbering(&scenes);
+
+        let chapters: Vec<i32> = changes.iter().map(|c| c.chapter_number).collect();
+        assert_eq!(chapters, vec![1, 2, 2]);
+    }
+
+    #[test]
+    fn test_autosave_commits_once_after_quiescence() {
+        tokio_test::block_on(async {
+            let queue = std::sync::Arc::new(AutosaveQueue::default());
+            let window = Duration::from_millis(60);
+
+            let mut calls = Vec::new();
+            for text in ["<p>T</p>", "<p>Th</p>", "<p>The</p>"] {
+                let queue = queue.clone();
+                calls.push(tokio::spawn(async move {
+                    queue.debounce("scene-0", text.to_string(), window).await
+                }));
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            let other = {
+                let queue = queue.clone();
+                tokio::spawn(async move { queue.debounce("scene-1", "<p>Other</p>".to_string(), window).await })
+            };
+            assert!(queue.is_pending("scene-0"));
+
+            let mut committed = Vec::new();
+            for call in calls {
+                committed.extend(call.await.unwrap());
+            }
+            assert_eq!(committed, vec!["<p>The</p>".to_string()]);
+            assert_eq!(other.await.unwrap().as_deref(), Some("<p>Other</p>"));
+            assert!(!queue.is_pending("scene-0"));
+        });
+    }
+}
@@ -1,10 +1,19 @@
 use serde::{Deserialize, Serialize};
 use chrono::Utc;
-use tauri::AppHandle;
+use tauri::{AppHandle, Manager};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use crate::error::{AppError, AppResult};
+use tokio::sync::{Mutex, RwLock};
+use std::path::Path;
+use std::time::Duration;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions};
+use sqlx::{Column, Row, SqliteConnection, TypeInfo, ValueRef};
+use sqlx::sqlite::SqliteRow;
+use regex::{Regex, RegexBuilder};
+use crate::error::{AppError, AppResult, CircuitBreaker, retry_with_backoff, RetryConfig};
+
+// Id of the only manuscript row in single manuscript mode
+pub const SINGLETON_MANUSCRIPT_ID: &str = "singleton-manuscript";
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Manuscript {
@@ -41,6 +50,90 @@ pub struct Scene {
     pub updated_at: i64,
 }
 
+pub const MANUSCRIPT_SELECT: &str = "SELECT id, title, author, genre, target_audience, comp_titles, created_at, updated_at, total_word_count, opening_strength_score, hook_effectiveness FROM manuscripts LIMIT 1";
+
+pub const SCENES_SELECT: &str = "SELECT id, chapter_number, scene_number_in_chapter, index_in_manuscript, title, raw_text, word_count, is_opening, is_chapter_end, opens_with_hook, ends_with_hook, pov_character, location, time_marker, created_at, updated_at FROM scenes ORDER BY index_in_manuscript";
+
+// Typed access to the JSON rows returned by execute_with_cache
+fn column<'a>(row: &'a serde_json::Value, name: &str) -> &'a serde_json::Value {
+    row.get(name).unwrap_or(&serde_json::Value::Null)
+}
+
+fn text_column(row: &serde_json::Value, name: &str) -> AppResult<String> {
+    column(row, name)
+        .as_str()
+        .map(|text| text.to_string())
+        .ok_or_else(|| AppError::database(format!("Row has no text value for column '{}'", name)))
+}
+
+fn optional_text_column(row: &serde_json::Value, name: &str) -> Option<String> {
+    column(row, name).as_str().map(|text| text.to_string())
+}
+
+fn optional_int_column(row: &serde_json::Value, name: &str) -> Option<i64> {
+    column(row, name).as_i64()
+}
+
+// SQLite stores booleans as 0/1
+fn bool_column(row: &serde_json::Value, name: &str) -> bool {
+    let value = column(row, name);
+    value.as_bool().unwrap_or_else(|| value.as_i64().unwrap_or(0) != 0)
+}
+
+impl Manuscript {
+    pub fn from_row(row: &serde_json::Value) -> AppResult<Self> {
+        Ok(Self {
+            id: text_column(row, "id")?,
+            title: text_column(row, "title")?,
+            author: optional_text_column(row, "author"),
+            genre: optional_text_column(row, "genre"),
+            target_audience: optional_text_column(row, "target_audience"),
+            comp_titles: optional_text_column(row, "comp_titles"),
+            created_at: optional_int_column(row, "created_at").unwrap_or(0),
+            updated_at: optional_int_column(row, "updated_at").unwrap_or(0),
+            total_word_count: optional_int_column(row, "total_word_count").unwrap_or(0) as u32,
+            opening_strength_score: optional_int_column(row, "opening_strength_score").map(|n| n as u32),
+            hook_effectiveness: optional_int_column(row, "hook_effectiveness").map(|n| n as u32),
+        })
+    }
+}
+
+impl Scene {
+    pub fn from_row(row: &serde_json::Value) -> AppResult<Self> {
+        Ok(Self {
+            id: text_column(row, "id")?,
+            chapter_number: optional_int_column(row, "chapter_number").map(|n| n as i32),
+            scene_number_in_chapter: optional_int_column(row, "scene_number_in_chapter").map(|n| n as i32),
+            index_in_manuscript: optional_int_column(row, "index_in_manuscript").unwrap_or(0) as u32,
+            title: optional_text_column(row, "title"),
+            raw_text: text_column(row, "raw_text")?,
+            word_count: optional_int_column(row, "word_count").unwrap_or(0) as u32,
+            is_opening: bool_column(row, "is_opening"),
+            is_chapter_end: bool_column(row, "is_chapter_end"),
+            opens_with_hook: bool_column(row, "opens_with_hook"),
+            ends_with_hook: bool_column(row, "ends_with_hook"),
+            pov_character: optional_text_column(row, "pov_character"),
+            location: optional_text_column(row, "location"),
+            time_marker: optional_text_column(row, "time_marker"),
+            created_at: optional_int_column(row, "created_at").unwrap_or(0),
+            updated_at: optional_int_column(row, "updated_at").unwrap_or(0),
+        })
+    }
+}
+
+pub fn rows_to_scenes(rows: &serde_json::Value) -> AppResult<Vec<Scene>> {
+    rows.as_array()
+        .map(|rows| rows.iter().map(Scene::from_row).collect())
+        .unwrap_or_else(|| Ok(Vec::new()))
+}
+
+pub fn rows_to_manuscript(rows: &serde_json::Value) -> AppResult<Option<Manuscript>> {
+    rows.as_array()
+        .and_then(|rows| rows.first())
+        .map(Manuscript::from_row)
+        .transpose()
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ModuleStatus {
     pub scene_id: String,
@@ -63,12 +156,213 @@ pub struct UpdateModuleStatusRequest {
     pub dirty: bool,
 }
 
+// Structural beats an author can tag a scene with, stored as snake_case text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BeatType {
+    Hook,
+    IncitingIncident,
+    FirstPlotPoint,
+    FirstPinchPoint,
+    Midpoint,
+    SecondPinchPoint,
+    SecondPlotPoint,
+    Climax,
+    Resolution,
+}
+
+impl BeatType {
+    const ALL: [BeatType; 9] = [
+        BeatType::Hook,
+        BeatType::IncitingIncident,
+        BeatType::FirstPlotPoint,
+        BeatType::FirstPinchPoint,
+        BeatType::Midpoint,
+        BeatType::SecondPinchPoint,
+        BeatType::SecondPlotPoint,
+        BeatType::Climax,
+        BeatType::Resolution,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BeatType::Hook => "hook",
+            BeatType::IncitingIncident => "inciting_incident",
+            BeatType::FirstPlotPoint => "first_plot_point",
+            BeatType::FirstPinchPoint => "first_pinch_point",
+            BeatType::Midpoint => "midpoint",
+            BeatType::SecondPinchPoint => "second_pinch_point",
+            BeatType::SecondPlotPoint => "second_plot_point",
+            BeatType::Climax => "climax",
+            BeatType::Resolution => "resolution",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<BeatType> {
+        BeatType::ALL.into_iter().find(|beat| beat.as_str() == value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GoalKind {
+    Project,
+    Daily,
+}
+
+impl GoalKind {
+    const ALL: [GoalKind; 2] = [GoalKind::Project, GoalKind::Daily];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GoalKind::Project => "project",
+            GoalKind::Daily => "daily",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<GoalKind> {
+        GoalKind::ALL.into_iter().find(|kind| kind.as_str() == value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GoalStatus {
+    pub target: u32,
+    pub words: u32,
+    pub percent: f64, // Not capped at 100
+    pub reached: bool,
+}
+
+impl GoalStatus {
+    fn new(target: u32, words: u32) -> Self {
+        Self {
+            target,
+            words,
+            percent: (words as f64 / target as f64 * 1000.0).round() / 10.0,
+            reached: words >= target,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GoalProgress {
+    pub manuscript_id: String,
+    pub total_word_count: u32,
+    pub words_today: u32,
+    pub project: Option<GoalStatus>,
+    pub daily: Option<GoalStatus>,
+}
+
+// One entry of the beat map: a tagged scene and where it sits in the manuscript
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SceneBeat {
+    pub scene_id: String,
+    pub scene_title: Option<String>,
+    pub chapter_number: Option<i32>,
+    pub index_in_manuscript: u32,
+    pub beat_type: BeatType,
+}
+
+// A setup planted at a span of a scene's raw_text, and the scene paying it off
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Plant {
+    pub id: String,
+    pub scene_id: String,
+    pub start_offset: u32,
+    pub end_offset: u32,
+    pub description: String,
+    pub payoff_scene_id: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+// A plot event extracted from a scene
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SceneEvent {
+    pub description: String,
+    #[serde(default)]
+    pub characters: Vec<String>,
+    #[serde(default)]
+    pub time_marker: Option<String>,
+}
+
+// An event placed on the manuscript timeline: by scene, then by `sequence`
+// within the scene
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManuscriptEvent {
+    pub scene_id: String,
+    pub index_in_manuscript: u32,
+    pub sequence: u32,
+    #[serde(flatten)]
+    pub event: SceneEvent,
+}
+
+// One point on a state key's timeline. `previous_value` is the value at the
+// key's prior scene, so a view can show each change and spot contradictions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateChange {
+    pub scene_id: String,
+    pub scene_title: Option<String>,
+    pub index_in_manuscript: u32,
+    pub value: String,
+    pub previous_value: Option<String>,
+}
+
+// A comment anchored at a character offset in a scene's raw_text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SceneComment {
+    pub id: String,
+    pub scene_id: String,
+    pub position: u32,
+    pub text: String,
+    pub author: Option<String>,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaggedScene {
+    pub scene_id: String,
+    pub scene_title: Option<String>,
+    pub chapter_number: Option<i32>,
+    pub index_in_manuscript: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Note {
+    pub id: String,
+    pub manuscript_id: String,
+    pub title: String,
+    pub body: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewPlant {
+    pub scene_id: String,
+    pub start_offset: u32,
+    pub end_offset: u32,
+    pub description: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SearchRequest {
     pub query: String,
     pub case_sensitive: bool,
     pub whole_words: bool,
     pub regex: bool,
+    #[serde(default)]
+    pub wildcard: bool, // Treat % and _ in a plain query as SQL LIKE wildcards
+    #[serde(default)]
+    pub context_chars: Option<usize>, // Characters of context either side of a match
+}
+
+impl SearchRequest {
+    pub fn context_window(&self) -> usize {
+        self.context_chars
+            .unwrap_or(SEARCH_CONTEXT_CHARS)
+            .min(MAX_SEARCH_CONTEXT_CHARS)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -81,12 +375,64 @@ pub struct SearchResult {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SearchMatch {
+    // Character offsets into the stored HTML, for edits such as replace
     pub start_offset: u32,
     pub end_offset: u32,
+    // Character offsets into the stripped, newline-preserving text the user sees
+    pub text_start_offset: u32,
+    pub text_end_offset: u32,
     pub context_before: String,
     pub matched_text: String,
     pub context_after: String,
-    pub line_number: u32,
+    pub line_number: u32, // 1-based, counted in the stripped text
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReplaceRequest {
+    #[serde(flatten)]
+    pub search: SearchRequest,
+    pub replacement: String, // May use $1-style groups in regex mode
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReplaceMatch {
+    #[serde(flatten)]
+    pub found: SearchMatch,
+    pub replacement_text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReplacePreview {
+    pub scene_id: String,
+    pub scene_title: Option<String>,
+    pub matches: Vec<ReplaceMatch>,
+    pub total_matches: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReplaceOutcome {
+    pub scenes_changed: u32,
+    pub replacements: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManuscriptCopy {
+    pub path: String,
+    pub manuscript_id: String,
+    pub title: String,
+    pub scene_count: u32,
+}
+
+// What reindex_manuscript had to fix; all zeros means the derived data was
+// already consistent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReindexSummary {
+    pub scenes: u32,
+    pub indices_changed: u32,
+    pub word_counts_changed: u32,
+    pub chapter_numbers_changed: u32,
+    pub previous_total_word_count: u32,
+    pub total_word_count: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -96,12 +442,16 @@ pub struct BatchSceneRequest {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ReorderRequest {
+    #[serde(default)]
+    pub manuscript_id: Option<String>,
     pub scene_id: String,
     pub new_index: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RenameRequest {
+    #[serde(default)]
+    pub manuscript_id: Option<String>,
     pub scene_id: String,
     pub new_title: String,
 }
@@ -115,30 +465,219 @@ pub struct BackupMetadata {
     pub compression_ratio: f32,
 }
 
+// Connection pool tuning. SQLite allows one writer at a time, so writers wait
+// up to busy_timeout for the lock instead of failing with "database is locked".
+#[derive(Debug, Clone)]
+pub struct DatabaseConfig {
+    pub max_connections: u32,
+    pub busy_timeout: Duration,
+    pub wal_mode: bool, // WAL lets readers proceed while a write is in progress
+    pub cache_ttl: Duration, // How long cached query results stay valid
+    pub circuit_failure_threshold: u32, // Failed retried operations before calls fail fast
+    pub circuit_cooldown: Duration, // How long calls fail fast once the circuit opens
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            max_connections: 5,
+            busy_timeout: Duration::from_secs(5),
+            wal_mode: true,
+            cache_ttl: Duration::from_secs(300),
+            circuit_failure_threshold: 5,
+            circuit_cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+pub async fn open_pool(path: &Path, config: &DatabaseConfig) -> AppResult<SqlitePool> {
+    // Foreign keys are off by default in SQLite and must be enabled on every
+    // connection for ON DELETE clauses to apply
+    let mut options = SqliteConnectOptions::new()
+        .filename(path)
+        .create_if_missing(true)
+        .foreign_keys(true)
+        .busy_timeout(config.busy_timeout);
+    if config.wal_mode {
+        options = options.journal_mode(SqliteJournalMode::Wal);
+    }
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(config.max_connections)
+        .acquire_timeout(config.busy_timeout * 2)
+        .connect_with(options)
+        .await?;
+
+    Ok(pool)
+}
+
+// Logical size of the database from SQLite's page counts. Free pages are space
+// left behind by deleted rows that only VACUUM gives back.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DatabaseSize {
+    pub page_size: u64,
+    pub page_count: u64,
+    pub free_pages: u64,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VacuumResult {
+    pub before: DatabaseSize,
+    pub after: DatabaseSize,
+}
+
+async fn database_size_on(conn: &mut SqliteConnection) -> AppResult<DatabaseSize> {
+    let page_size: i64 = sqlx::query_scalar("PRAGMA page_size").fetch_one(&mut *conn).await?;
+    let page_count: i64 = sqlx::query_scalar("PRAGMA page_count").fetch_one(&mut *conn).await?;
+    let free_pages: i64 = sqlx::query_scalar("PRAGMA freelist_count").fetch_one(&mut *conn).await?;
+
+    Ok(DatabaseSize {
+        page_size: page_size as u64,
+        page_count: page_count as u64,
+        free_pages: free_pages as u64,
+        size_bytes: (page_size * page_count) as u64,
+    })
+}
+
+pub async fn get_database_size(pool: &SqlitePool) -> AppResult<DatabaseSize> {
+    let mut conn = pool.acquire().await?;
+    database_size_on(&mut conn).await
+}
+
+// VACUUM can't run inside a transaction, so this takes its own pooled
+// connection (which never has one open) rather than joining a caller's.
+// It waits up to busy_timeout for other writers to finish.
+pub async fn vacuum_database(pool: &SqlitePool) -> AppResult<VacuumResult> {
+    let mut conn = pool.acquire().await?;
+    let before = database_size_on(&mut conn).await?;
+
+    sqlx::query("VACUUM").execute(&mut *conn).await?;
+    sqlx::query("ANALYZE").execute(&mut *conn).await?;
+    // In WAL mode the rewritten pages sit in the log until checkpointed
+    sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)").execute(&mut *conn).await?;
+
+    let after = database_size_on(&mut conn).await?;
+    Ok(VacuumResult { before, after })
+}
+
+// A row referencing a parent row that doesn't exist
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForeignKeyViolation {
+    pub table: String,
+    pub rowid: Option<i64>, // None for WITHOUT ROWID tables
+    pub parent: String,
+}
+
+// Result of a completed check. `ok` is true only when SQLite found nothing;
+// failing to run the check at all is an error instead.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    pub ok: bool,
+    pub integrity_errors: Vec<String>,
+    pub foreign_key_violations: Vec<ForeignKeyViolation>,
+}
+
+pub async fn check_database_integrity(pool: &SqlitePool) -> AppResult<IntegrityReport> {
+    let mut conn = pool.acquire().await?;
+
+    // A healthy database reports a single "ok" row
+    let integrity_errors: Vec<String> = sqlx::query_scalar::<_, String>("PRAGMA integrity_check")
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .filter(|message| message != "ok")
+        .collect();
+
+    let foreign_key_violations = sqlx::query_as::<_, (String, Option<i64>, String, i64)>("PRAGMA foreign_key_check")
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .map(|(table, rowid, parent, _)| ForeignKeyViolation { table, rowid, parent })
+        .collect::<Vec<_>>();
+
+    Ok(IntegrityReport {
+        ok: integrity_errors.is_empty() && foreign_key_violations.is_empty(),
+        integrity_errors,
+        foreign_key_violations,
+    })
+}
+
+const DATABASE_CIRCUIT: &str = "database";
+
 // Database service for managing connections and caching
 pub struct DatabaseService {
     cache: Arc<RwLock<HashMap<String, (String, i64)>>>, // key -> (value, timestamp)
     database_url: String,
+    config: DatabaseConfig,
+    pool: Arc<Mutex<Option<SqlitePool>>>, // Opened on first use
+    breaker: CircuitBreaker,
 }
 
 impl DatabaseService {
     pub fn new() -> Self {
+        Self::new_with_config(DatabaseConfig::default())
+    }
+
+    pub fn new_with_config(config: DatabaseConfig) -> Self {
         Self {
             cache: Arc::new(RwLock::new(HashMap::new())),
             database_url: "sqlite:narrative_surgeon.db".to_string(),
+            breaker: CircuitBreaker::new(config.circuit_failure_threshold, config.circuit_cooldown),
+            config,
+            pool: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    // Service backed by an already open pool (used by tests)
+    pub fn with_pool(pool: SqlitePool) -> Self {
+        Self {
+            pool: Arc::new(Mutex::new(Some(pool))),
+            ..Self::new()
         }
     }
 
+    // Retries a database operation with the database preset. Operations that
+    // still fail after their retries trip a shared circuit breaker, after which
+    // calls fail fast for the cooldown rather than retrying against a database
+    // that is down.
+    pub async fn with_retry<F, T, Fut>(&self, operation: F) -> AppResult<T>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = AppResult<T>>,
+    {
+        self.breaker.call(DATABASE_CIRCUIT, || retry_with_backoff(operation, RetryConfig::database())).await
+    }
+
     pub fn get_database_url(&self) -> &str {
         &self.database_url
     }
 
-    // Cache management methods
+    // Pool for direct SQLx access. The database file is the one the SQL plugin
+    // manages, which lives in the app config directory.
+    pub async fn pool(&self, app: &AppHandle) -> AppResult<SqlitePool> {
+        let mut pool = self.pool.lock().await;
+        if let Some(pool) = pool.as_ref() {
+            return Ok(pool.clone());
+        }
+
+        let config_dir = app.path().app_config_dir()
+            .map_err(|e| AppError::configuration(
+                format!("Cannot resolve app config directory: {}", e),
+                "app_config_dir".to_string()
+            ))?;
+        let filename = self.database_url.trim_start_matches("sqlite:");
+
+        let opened = open_pool(&config_dir.join(filename), &self.config).await?;
+        *pool = Some(opened.clone());
+        Ok(opened)
+    }
+
+    // Cache management methods. Timestamps are in milliseconds.
     pub async fn get_cached_result(&self, key: &str) -> Option<String> {
         let cache = self.cache.read().await;
         if let Some((value, timestamp)) = cache.get(key) {
-            // Cache for 5 minutes
-            if Utc::now().timestamp() - timestamp < 300 {
+            if Utc::now().timestamp_millis() - timestamp < self.config.cache_ttl.as_millis() as i64 {
                 return Some(value.clone());
             }
         }
@@ -147,11 +686,11 @@ impl DatabaseService {
 
     pub async fn cache_result(&self, key: &str, value: &str) {
         let mut cache = self.cache.write().await;
-        cache.insert(key.to_string(), (value.to_string(), Utc::now().timestamp()));
+        cache.insert(key.to_string(), (value.to_string(), Utc::now().timestamp_millis()));
         
         // Clean old entries if cache gets too large
         if cache.len() > 1000 {
-            let cutoff = Utc::now().timestamp() - 300;
+            let cutoff = Utc::now().timestamp_millis() - self.config.cache_ttl.as_millis() as i64;
             cache.retain(|_, (_, timestamp)| *timestamp > cutoff);
         }
     }
@@ -161,16 +700,102 @@ impl DatabaseService {
         cache.retain(|key, _| !key.contains(pattern));
     }
 
-    // Placeholder method for database operations
+    // Run a query with positional `?` parameters bound as text.
+    //
+    // Reads (SELECT, WITH, PRAGMA) return a JSON array with one object per row,
+    // keyed by column name, and are cached for the configured TTL keyed on the
+    // query and parameters. Anything else is a write: it returns
+    // `{ "rows_affected", "last_insert_rowid" }` and clears the whole cache,
+    // since triggers and cascades can change tables the statement doesn't name.
     pub async fn execute_with_cache(
         &self,
-        _app: &AppHandle,
-        _query: &str,
-        _params: &[String]
+        app: &AppHandle,
+        query: &str,
+        params: &[String]
+    ) -> AppResult<serde_json::Value> {
+        let pool = self.pool(app).await?;
+        self.execute_on(&pool, query, params).await
+    }
+
+    async fn execute_on(
+        &self,
+        pool: &SqlitePool,
+        query: &str,
+        params: &[String]
     ) -> AppResult<serde_json::Value> {
-        // TODO: Implement actual database operations with SQLx
-        Err(AppError::database("Database operations not yet implemented"))
+        let mut statement = sqlx::query(query);
+        for param in params {
+            statement = statement.bind(param);
+        }
+
+        if !is_read_query(query) {
+            let result = statement.execute(pool).await
+                .map_err(|e| with_query(e.into(), query))?;
+            self.cache.write().await.clear();
+
+            return Ok(serde_json::json!({
+                "rows_affected": result.rows_affected(),
+                "last_insert_rowid": result.last_insert_rowid(),
+            }));
+        }
+
+        let key = format!("{}\u{1f}{}", query, params.join("\u{1f}"));
+        if let Some(cached) = self.get_cached_result(&key).await {
+            if let Ok(value) = serde_json::from_str(&cached) {
+                return Ok(value);
+            }
+        }
+
+        let rows = statement.fetch_all(pool).await
+            .map_err(|e| with_query(e.into(), query))?;
+        let value = serde_json::Value::Array(rows.iter().map(row_to_json).collect());
+        self.cache_result(&key, &value.to_string()).await;
+
+        Ok(value)
+    }
+}
+
+fn is_read_query(query: &str) -> bool {
+    let keyword: String = query
+        .trim_start()
+        .chars()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .collect::<String>()
+        .to_uppercase();
+
+    matches!(keyword.as_str(), "SELECT" | "WITH" | "PRAGMA")
+}
+
+fn with_query(error: AppError, query: &str) -> AppError {
+    match error {
+        AppError::Database { message, code, timestamp, .. } => AppError::Database {
+            message,
+            code,
+            query: Some(query.to_string()),
+            timestamp,
+        },
+        other => other,
+    }
+}
+
+// Columns decoded by their runtime SQLite storage class
+fn row_to_json(row: &SqliteRow) -> serde_json::Value {
+    let mut object = serde_json::Map::new();
+
+    for (i, column) in row.columns().iter().enumerate() {
+        let value = match row.try_get_raw(i) {
+            Ok(raw) if !raw.is_null() => match raw.type_info().name() {
+                "INTEGER" | "BOOLEAN" => row.try_get::<i64, _>(i).map(serde_json::Value::from).ok(),
+                "REAL" => row.try_get::<f64, _>(i).map(serde_json::Value::from).ok(),
+                "BLOB" => row.try_get::<Vec<u8>, _>(i).map(serde_json::Value::from).ok(),
+                _ => row.try_get::<String, _>(i).map(serde_json::Value::from).ok(),
+            },
+            _ => None,
+        };
+        object.insert(column.name().to_string(), value.unwrap_or(serde_json::Value::Null));
     }
+
+    serde_json::Value::Object(object)
 }
 
 // Validation functions
@@ -214,112 +839,2013 @@ fn _validate_scene(scene: &Scene) -> AppResult<()> {
     Ok(())
 }
 
-fn _calculate_word_count(text: &str) -> u32 {
-    text.split_whitespace().count() as u32
+// Build the matcher for a search request. In regex mode the query is user-supplied,
+// so an invalid pattern surfaces as a validation error instead of a panic.
+pub fn build_search_regex(request: &SearchRequest) -> AppResult<Regex> {
+    if request.query.is_empty() {
+        return Err(AppError::validation_field(
+            "Search query cannot be empty",
+            "query",
+            ""
+        ));
+    }
+    
+    let mut pattern = if request.regex {
+        request.query.clone()
+    } else if request.wildcard {
+        request.query
+            .chars()
+            .map(|c| match c {
+                '%' => ".*?".to_string(),
+                '_' => ".".to_string(),
+                _ => regex::escape(&c.to_string()),
+            })
+            .collect()
+    } else {
+        regex::escape(&request.query)
+    };
+    
+    if request.whole_words {
+        pattern = format!(r"\b(?:{})\b", pattern);
+    }
+    
+    let regex = RegexBuilder::new(&pattern)
+        .case_insensitive(!request.case_sensitive)
+        .build()?;
+    
+    Ok(regex)
 }
 
-// PLACEHOLDER IMPLEMENTATIONS - TODO: Replace with SQLx
+// LIKE pattern used to narrow the scenes a plain search has to scan. Content is
+// stored as HTML, so the query is entity-escaped the way the editor stores it,
+// and LIKE's own wildcards are escaped unless the request opts into them.
+fn search_like_pattern(request: &SearchRequest) -> String {
+    let html_query = escape_html_text(&request.query);
 
-// MANUSCRIPT OPERATIONS (Single manuscript mode)
+    let body = if request.wildcard {
+        html_query
+    } else {
+        html_query
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    };
 
-pub async fn get_manuscript_impl(_app: &AppHandle) -> AppResult<Option<Manuscript>> {
-    // TODO: Implement with SQLx - get the singleton manuscript
-    Err(AppError::database("Database operations not yet implemented"))
+    format!("%{}%", body)
 }
 
-pub async fn update_manuscript_impl(_app: &AppHandle, _manuscript: Manuscript) -> AppResult<()> {
-    // TODO: Implement with SQLx - update the singleton manuscript
-    Err(AppError::database("Database operations not yet implemented"))
+fn escape_html_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
 }
 
-// SCENE CRUD OPERATIONS
+const SEARCH_CONTEXT_CHARS: usize = 40;
+const MAX_SEARCH_CONTEXT_CHARS: usize = 500;
 
-pub async fn get_all_scenes_impl(_app: &AppHandle) -> AppResult<Vec<Scene>> {
-    // TODO: Implement with SQLx - get all scenes for the singleton manuscript
-    Err(AppError::database("Database operations not yet implemented"))
-}
+// Scenes that may contain a match: (id, title, raw_text, word_count)
+type CandidateScene = (String, Option<String>, String, i64);
 
-pub async fn get_scene_impl(_app: &AppHandle, _id: String) -> AppResult<Option<Scene>> {
-    // TODO: Implement with SQLx
-    Err(AppError::database("Database operations not yet implemented"))
-}
+async fn candidate_scenes(conn: &mut SqliteConnection, request: &SearchRequest) -> AppResult<Vec<CandidateScene>> {
+    let rows = if request.regex {
+        sqlx::query_as("SELECT id, title, raw_text, COALESCE(word_count, 0) FROM scenes ORDER BY index_in_manuscript")
+            .fetch_all(&mut *conn)
+            .await?
+    } else {
+        sqlx::query_as(
+            "SELECT id, title, raw_text, COALESCE(word_count, 0) FROM scenes WHERE raw_text LIKE ? ESCAPE '\\' ORDER BY index_in_manuscript"
+        )
+        .bind(search_like_pattern(request))
+        .fetch_all(&mut *conn)
+        .await?
+    };
 
-pub async fn create_scene_impl(_app: &AppHandle, _scene: Scene) -> AppResult<String> {
-    // TODO: Implement with SQLx
-    Err(AppError::database("Database operations not yet implemented"))
+    Ok(rows)
 }
 
-pub async fn update_scene_impl(_app: &AppHandle, _scene: Scene) -> AppResult<()> {
-    // TODO: Implement with SQLx
-    Err(AppError::database("Database operations not yet implemented"))
-}
+// Context is counted in characters, so multi-byte text is never split mid-character
+fn search_match(
+    stripped: &crate::text::StrippedText,
+    line_breaks: &[usize],
+    found: &regex::Match<'_>,
+    context_chars: usize
+) -> SearchMatch {
+    let (start, end) = stripped.source_range(found.start(), found.end());
+    let before = &stripped.text[..found.start()];
+    let after = &stripped.text[found.end()..];
+    let text_start = before.chars().count();
+    let skip = text_start.saturating_sub(context_chars);
 
-pub async fn delete_scene_impl(_app: &AppHandle, _id: String) -> AppResult<()> {
-    // TODO: Implement with SQLx
-    Err(AppError::database("Database operations not yet implemented"))
+    SearchMatch {
+        start_offset: start as u32,
+        end_offset: end as u32,
+        text_start_offset: text_start as u32,
+        text_end_offset: (text_start + found.as_str().chars().count()) as u32,
+        context_before: before.chars().skip(skip).collect(),
+        matched_text: found.as_str().to_string(),
+        context_after: after.chars().take(context_chars).collect(),
+        line_number: crate::text::line_at(line_breaks, found.start()),
+    }
 }
 
-pub async fn rename_scene_impl(_app: &AppHandle, _request: RenameRequest) -> AppResult<()> {
-    // TODO: Implement with SQLx
-    Err(AppError::database("Database operations not yet implemented"))
-}
+// Search scene text. Matching runs over the visible text; each match carries
+// character offsets into both the stored content and that text, plus its line.
+// The query is always bound, never spliced in.
+pub async fn search_scenes(pool: &SqlitePool, request: &SearchRequest) -> AppResult<Vec<SearchResult>> {
+    let matcher = build_search_regex(request)?;
+    let rows = candidate_scenes(&mut *pool.acquire().await?, request).await?;
 
-pub async fn reorder_scenes_impl(_app: &AppHandle, _request: ReorderRequest) -> AppResult<()> {
-    // TODO: Implement with SQLx
-    Err(AppError::database("Database operations not yet implemented"))
-}
+    let mut results = Vec::new();
+    for (scene_id, scene_title, raw_text, _) in rows {
+        let stripped = crate::text::strip_html(&raw_text);
+        let line_breaks = stripped.line_breaks();
+        let matches: Vec<SearchMatch> = matcher
+            .find_iter(&stripped.text)
+            .filter(|m| !m.as_str().is_empty())
+            .map(|m| search_match(&stripped, &line_breaks, &m, request.context_window()))
+            .collect();
 
-// SEARCH AND UTILITY OPERATIONS
+        if !matches.is_empty() {
+            results.push(SearchResult {
+                scene_id,
+                scene_title,
+                total_matches: matches.len() as u32,
+                matches,
+            });
+        }
+    }
 
-pub async fn search_content_impl(_app: &AppHandle, _request: SearchRequest) -> AppResult<Vec<SearchResult>> {
-    // TODO: Implement with SQLx
-    Err(AppError::database("Database operations not yet implemented"))
+    Ok(results)
 }
 
-pub async fn create_database_backup_impl(_app: &AppHandle) -> AppResult<BackupMetadata> {
-    // TODO: Implement with SQLx
-    Err(AppError::database("Database operations not yet implemented"))
-}
+// Replacements a request would make in one scene. Matches that cross markup
+// (e.g. half inside <em>) are left out, since replacing them would unbalance
+// the HTML; preview and apply share this so their counts always agree.
+fn plan_replacements(matcher: &Regex, raw_text: &str, request: &ReplaceRequest) -> Vec<ReplaceMatch> {
+    let stripped = crate::text::strip_html(raw_text);
+    let line_breaks = stripped.line_breaks();
+    let source: Vec<char> = raw_text.chars().collect();
 
-// MODULE STATUS OPERATIONS
+    matcher
+        .captures_iter(&stripped.text)
+        .filter_map(|captures| {
+            let whole = captures.get(0).filter(|m| !m.as_str().is_empty())?;
+            let found = search_match(&stripped, &line_breaks, &whole, request.search.context_window());
+            if source[found.start_offset as usize..found.end_offset as usize].contains(&'<') {
+                return None;
+            }
 
-pub async fn get_dirty_scenes_impl(_app: &AppHandle) -> AppResult<Vec<String>> {
-    // TODO: Implement with SQLx
-    // Query: SELECT scene_id FROM module_status 
-    //        WHERE events_dirty = 1 OR plants_dirty = 1 OR state_dirty = 1 OR beats_dirty = 1
-    Err(AppError::database("Database operations not yet implemented"))
-}
+            let replacement_text = if request.search.regex {
+                let mut expanded = String::new();
+                captures.expand(&request.replacement, &mut expanded);
+                expanded
+            } else {
+                request.replacement.clone()
+            };
 
-pub async fn get_module_status_impl(_app: &AppHandle, _scene_id: String) -> AppResult<Option<ModuleStatus>> {
-    // TODO: Implement with SQLx
-    // Query: SELECT * FROM module_status WHERE scene_id = ?
-    Err(AppError::database("Database operations not yet implemented"))
+            Some(ReplaceMatch { found, replacement_text })
+        })
+        .collect()
 }
 
-pub async fn mark_modules_dirty_impl(_app: &AppHandle, _scene_id: String, _modules: Vec<String>) -> AppResult<()> {
-    // TODO: Implement with SQLx
-    // Update specific module dirty flags to 1 for the given scene
-    Err(AppError::database("Database operations not yet implemented"))
+fn apply_replacements(raw_text: &str, replacements: &[ReplaceMatch]) -> String {
+    let source: Vec<char> = raw_text.chars().collect();
+    let mut replaced = String::with_capacity(raw_text.len());
+    let mut cursor = 0;
+
+    for replacement in replacements {
+        replaced.extend(&source[cursor..replacement.found.start_offset as usize]);
+        replaced.push_str(&escape_html_text(&replacement.replacement_text));
+        cursor = replacement.found.end_offset as usize;
+    }
+    replaced.extend(&source[cursor..]);
+
+    replaced
 }
 
-pub async fn update_module_status_impl(_app: &AppHandle, _request: UpdateModuleStatusRequest) -> AppResult<()> {
-    // TODO: Implement with SQLx
-    // Update the specific module version and dirty flag
+// Dry run of a find-and-replace: every change it would make, nothing written
+pub async fn preview_replacements(pool: &SqlitePool, request: &ReplaceRequest) -> AppResult<Vec<ReplacePreview>> {
+    let matcher = build_search_regex(&request.search)?;
+    let rows = candidate_scenes(&mut *pool.acquire().await?, &request.search).await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(scene_id, scene_title, raw_text, _)| {
+            let matches = plan_replacements(&matcher, &raw_text, request);
+            if matches.is_empty() {
+                return None;
+            }
+
+            Some(ReplacePreview {
+                scene_id,
+                scene_title,
+                total_matches: matches.len() as u32,
+                matches,
+            })
+        })
+        .collect())
+}
+
+// TRANSACTIONAL WRITES
+
+// Apply a find-and-replace across all scenes in one transaction, keeping scene
+// word counts and the manuscript total current
+pub async fn apply_replacements_to_scenes(pool: &SqlitePool, request: &ReplaceRequest, now: i64) -> AppResult<ReplaceOutcome> {
+    let matcher = build_search_regex(&request.search)?;
+    let mut tx = pool.begin().await?;
+    let rows = candidate_scenes(&mut tx, &request.search).await?;
+
+    let mut outcome = ReplaceOutcome { scenes_changed: 0, replacements: 0 };
+    let mut delta = 0;
+    for (scene_id, _, raw_text, previous_count) in rows {
+        let replacements = plan_replacements(&matcher, &raw_text, request);
+        if replacements.is_empty() {
+            continue;
+        }
+
+        let replaced = apply_replacements(&raw_text, &replacements);
+        let word_count = crate::text::word_count(&replaced, true);
+        sqlx::query("UPDATE scenes SET raw_text = ?, word_count = ?, updated_at = ? WHERE id = ?")
+            .bind(&replaced)
+            .bind(word_count)
+            .bind(now)
+            .bind(&scene_id)
+            .execute(&mut *tx)
+            .await?;
+
+        delta += word_count as i64 - previous_count;
+        outcome.scenes_changed += 1;
+        outcome.replacements += replacements.len() as u32;
+    }
+    adjust_total_word_count(&mut tx, delta).await?;
+
+    tx.commit().await?;
+    Ok(outcome)
+}
+
+pub async fn insert_scenes(conn: &mut SqliteConnection, scenes: &[Scene]) -> AppResult<()> {
+    for scene in scenes {
+        sqlx::query(
+            "INSERT INTO scenes (id, chapter_number, scene_number_in_chapter, index_in_manuscript, title, raw_text, word_count, is_opening, is_chapter_end, opens_with_hook, ends_with_hook, pov_character, location, time_marker, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(&scene.id)
+        .bind(scene.chapter_number)
+        .bind(scene.scene_number_in_chapter)
+        .bind(scene.index_in_manuscript)
+        .bind(&scene.title)
+        .bind(&scene.raw_text)
+        .bind(scene.word_count)
+        .bind(scene.is_opening)
+        .bind(scene.is_chapter_end)
+        .bind(scene.opens_with_hook)
+        .bind(scene.ends_with_hook)
+        .bind(&scene.pov_character)
+        .bind(&scene.location)
+        .bind(&scene.time_marker)
+        .bind(scene.created_at)
+        .bind(scene.updated_at)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+// Append scenes after the current last scene, numbering them contiguously in
+// the order given. All or nothing: one transaction covers the whole batch.
+pub async fn append_scenes(pool: &SqlitePool, scenes: &mut [Scene]) -> AppResult<()> {
+    let mut tx = pool.begin().await?;
+
+    let next_index: i64 = sqlx::query_scalar(
+        "SELECT COALESCE(MAX(index_in_manuscript), -1) + 1 FROM scenes"
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    for (offset, scene) in scenes.iter_mut().enumerate() {
+        scene.index_in_manuscript = (next_index as usize + offset) as u32;
+    }
+    insert_scenes(&mut tx, scenes).await?;
+
+    let added: i64 = scenes.iter().map(|scene| scene.word_count as i64).sum();
+    adjust_total_word_count(&mut tx, added).await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+// Move the manuscript total by a change in scene word counts. Scene writes call
+// this inside their own transaction, so the total stays in step with the scenes
+// without re-aggregating them on every edit.
+async fn adjust_total_word_count(conn: &mut SqliteConnection, delta: i64) -> AppResult<()> {
+    if delta == 0 {
+        return Ok(());
+    }
+
+    record_day_start_word_count(conn).await?;
+    sqlx::query("UPDATE manuscripts SET total_word_count = MAX(COALESCE(total_word_count, 0) + ?, 0)")
+        .bind(delta)
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(())
+}
+
+// Daily goals count from the total before the day's first change. Days are
+// local dates as SQLite sees them, the same clock get_goal_progress uses.
+async fn record_day_start_word_count(conn: &mut SqliteConnection) -> AppResult<()> {
+    if !table_exists(conn, "word_count_snapshots").await? {
+        return Ok(());
+    }
+
+    sqlx::query(
+        "INSERT OR IGNORE INTO word_count_snapshots (manuscript_id, day, starting_word_count) SELECT id, date('now', 'localtime'), COALESCE(total_word_count, 0) FROM manuscripts"
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+// Optimistic concurrency: a writer that read the scene at `expected_updated_at`
+// may only write if nobody else has saved it since. Returns the stored word count.
+async fn check_scene_version(conn: &mut SqliteConnection, scene_id: &str, expected_updated_at: Option<i64>) -> AppResult<i64> {
+    let stored: Option<(i64, Option<i64>)> = sqlx::query_as("SELECT COALESCE(word_count, 0), updated_at FROM scenes WHERE id = ?")
+        .bind(scene_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+    let (word_count, updated_at) = stored.ok_or_else(|| AppError::not_found_with_id("Scene", scene_id))?;
+
+    if let Some(expected) = expected_updated_at {
+        if updated_at != Some(expected) {
+            return Err(AppError::conflict_with_id(
+                "Scene was changed elsewhere since it was loaded",
+                "scene",
+                scene_id
+            ));
+        }
+    }
+
+    Ok(word_count)
+}
+
+// Replace a scene's text, recounting its words and applying the difference to
+// the manuscript total in the same transaction
+pub async fn update_scene_text(
+    pool: &SqlitePool,
+    scene_id: &str,
+    raw_text: &str,
+    expected_updated_at: Option<i64>,
+    now: i64
+) -> AppResult<u32> {
+    let mut tx = pool.begin().await?;
+
+    let previous = check_scene_version(&mut tx, scene_id, expected_updated_at).await?;
+    let word_count = write_scene_text(&mut tx, scene_id, raw_text, previous, now).await?;
+
+    tx.commit().await?;
+    Ok(word_count)
+}
+
+pub async fn update_scene_title(
+    pool: &SqlitePool,
+    scene_id: &str,
+    title: &str,
+    expected_updated_at: Option<i64>,
+    now: i64
+) -> AppResult<()> {
+    let mut tx = pool.begin().await?;
+
+    check_scene_version(&mut tx, scene_id, expected_updated_at).await?;
+    write_scene_title(&mut tx, scene_id, title, now).await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+// Text and title changes applied together in one transaction, so a failure
+// part way leaves neither written and a retry sees the original version
+pub async fn update_scene_fields(
+    pool: &SqlitePool,
+    scene_id: &str,
+    raw_text: Option<&str>,
+    title: Option<&str>,
+    expected_updated_at: Option<i64>,
+    now: i64
+) -> AppResult<()> {
+    let mut tx = pool.begin().await?;
+
+    let previous = check_scene_version(&mut tx, scene_id, expected_updated_at).await?;
+    if let Some(raw_text) = raw_text {
+        write_scene_text(&mut tx, scene_id, raw_text, previous, now).await?;
+    }
+    if let Some(title) = title {
+        write_scene_title(&mut tx, scene_id, title, now).await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+// Store new scene text, recounting it and moving the manuscript total by the
+// difference from `previous_word_count`
+async fn write_scene_text(
+    conn: &mut SqliteConnection,
+    scene_id: &str,
+    raw_text: &str,
+    previous_word_count: i64,
+    now: i64
+) -> AppResult<u32> {
+    mark_plants_touched_by_edit(conn, scene_id, raw_text).await?;
+
+    let word_count = crate::text::word_count(raw_text, true);
+    sqlx::query("UPDATE scenes SET raw_text = ?, word_count = ?, updated_at = ? WHERE id = ?")
+        .bind(raw_text)
+        .bind(word_count)
+        .bind(now)
+        .bind(scene_id)
+        .execute(&mut *conn)
+        .await?;
+
+    adjust_total_word_count(conn, word_count as i64 - previous_word_count).await?;
+    Ok(word_count)
+}
+
+async fn write_scene_title(conn: &mut SqliteConnection, scene_id: &str, title: &str, now: i64) -> AppResult<()> {
+    sqlx::query("UPDATE scenes SET title = ?, updated_at = ? WHERE id = ?")
+        .bind(title)
+        .bind(now)
+        .bind(scene_id)
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+// Delete a scene and take its words off the manuscript total
+pub async fn remove_scene(pool: &SqlitePool, scene_id: &str) -> AppResult<bool> {
+    let mut tx = pool.begin().await?;
+
+    // Per-scene rows without ON DELETE clauses would block the delete
+    for table in SCENE_DEPENDENT_TABLES {
+        if table_exists(&mut tx, table).await? {
+            sqlx::query(&format!("DELETE FROM {} WHERE scene_id = ?", table))
+                .bind(scene_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+    }
+
+    let removed: Option<i64> = sqlx::query_scalar("DELETE FROM scenes WHERE id = ? RETURNING COALESCE(word_count, 0)")
+        .bind(scene_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+    if let Some(words) = removed {
+        adjust_total_word_count(&mut tx, -words).await?;
+    }
+
+    tx.commit().await?;
+    Ok(removed.is_some())
+}
+
+// Rebuild derived scene data after bulk imports or batch edits, in one
+// transaction:
+// - index_in_manuscript becomes 0..n in the current order (ties by creation)
+// - word counts are recounted from the text, and the manuscript total summed
+// - runs of scenes sharing a chapter number become chapters 1..m, with scene
+//   numbers and chapter ends filled in; scenes outside chapters are left alone
+// Scene updated_at is untouched so open editors don't see a conflict.
+pub async fn reindex_manuscript(pool: &SqlitePool) -> AppResult<ReindexSummary> {
+    let mut tx = pool.begin().await?;
+
+    let rows: Vec<(String, i64, String, Option<i64>, Option<i32>, Option<i32>, bool)> = sqlx::query_as(
+        "SELECT id, index_in_manuscript, raw_text, word_count, chapter_number, scene_number_in_chapter, COALESCE(is_chapter_end, 0) FROM scenes ORDER BY index_in_manuscript, created_at, id"
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    let previous_total: Option<i64> = sqlx::query_scalar("SELECT COALESCE(total_word_count, 0) FROM manuscripts LIMIT 1")
+        .fetch_optional(&mut *tx)
+        .await?;
+
+    let mut summary = ReindexSummary {
+        scenes: rows.len() as u32,
+        indices_changed: 0,
+        word_counts_changed: 0,
+        chapter_numbers_changed: 0,
+        previous_total_word_count: previous_total.unwrap_or(0) as u32,
+        total_word_count: 0,
+    };
+
+    let mut chapter = 0;
+    let mut scene_in_chapter = 0;
+    for (index, (id, old_index, raw_text, old_words, old_chapter, old_scene_number, old_chapter_end)) in rows.iter().enumerate() {
+        let words = crate::text::word_count(raw_text, true);
+        summary.total_word_count += words;
+        if *old_index != index as i64 {
+            summary.indices_changed += 1;
+        }
+        if *old_words != Some(words as i64) {
+            summary.word_counts_changed += 1;
+        }
+
+        let (chapter_number, scene_number, chapter_end) = match old_chapter {
+            Some(_) => {
+                let continues = index > 0 && rows[index - 1].4 == *old_chapter;
+                if continues {
+                    scene_in_chapter += 1;
+                } else {
+                    chapter += 1;
+                    scene_in_chapter = 1;
+                }
+                let ends = rows.get(index + 1).map_or(true, |next| next.4 != *old_chapter);
+                (Some(chapter), Some(scene_in_chapter), ends)
+            }
+            None => (None, *old_scene_number, *old_chapter_end),
+        };
+        if chapter_number != *old_chapter {
+            summary.chapter_numbers_changed += 1;
+        }
+
+        sqlx::query("UPDATE scenes SET index_in_manuscript = ?, word_count = ?, chapter_number = ?, scene_number_in_chapter = ?, is_chapter_end = ? WHERE id = ?")
+            .bind(index as i64)
+            .bind(words)
+            .bind(chapter_number)
+            .bind(scene_number)
+            .bind(chapter_end)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    sqlx::query("UPDATE manuscripts SET total_word_count = ?")
+        .bind(summary.total_word_count)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(summary)
+}
+
+// Requests that name a manuscript may only touch that manuscript's scenes. In
+// single-manuscript mode every scene belongs to the singleton, so this checks
+// the manuscript id and that the scene exists. The id's format is checked by
+// the commands.
+async fn check_scene_in_manuscript(conn: &mut SqliteConnection, manuscript_id: Option<&str>, scene_id: &str) -> AppResult<()> {
+    crate::analysis::resolve_manuscript_id(manuscript_id.map(str::to_string))?;
+    if scene_id.is_empty() {
+        return Err(AppError::validation_field("Scene ID cannot be empty", "scene_id", scene_id));
+    }
+    scene_text(conn, scene_id).await?;
+    Ok(())
+}
+
+pub async fn rename_scene_in(pool: &SqlitePool, request: &RenameRequest, now: i64) -> AppResult<()> {
+    let title = request.new_title.trim();
+    crate::commands::validate_title(title)?;
+
+    let mut tx = pool.begin().await?;
+    check_scene_in_manuscript(&mut tx, request.manuscript_id.as_deref(), &request.scene_id).await?;
+    sqlx::query("UPDATE scenes SET title = ?, updated_at = ? WHERE id = ?")
+        .bind(title)
+        .bind(now)
+        .bind(&request.scene_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+// Move a scene to `new_index` (clamped to the last position) and renumber the
+// rest contiguously around it
+pub async fn reorder_scene_in(pool: &SqlitePool, request: &ReorderRequest) -> AppResult<()> {
+    let mut tx = pool.begin().await?;
+    check_scene_in_manuscript(&mut tx, request.manuscript_id.as_deref(), &request.scene_id).await?;
+
+    let mut order: Vec<String> = sqlx::query_scalar("SELECT id FROM scenes ORDER BY index_in_manuscript, created_at, id")
+        .fetch_all(&mut *tx)
+        .await?;
+    order.retain(|id| *id != request.scene_id);
+    let new_index = (request.new_index as usize).min(order.len());
+    order.insert(new_index, request.scene_id.clone());
+
+    for (index, id) in order.iter().enumerate() {
+        sqlx::query("UPDATE scenes SET index_in_manuscript = ?, is_opening = ? WHERE id = ?")
+            .bind(index as i64)
+            .bind(index == 0)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+// Move a scene into a chapter at a 0-based position among that chapter's
+// scenes; positions past the end append. A chapter that doesn't exist yet goes
+// where its number sorts. Indices, scene numbers and the opening and
+// chapter-end flags are then recomputed for every scene, but other scenes keep
+// their chapter numbers, even if the move empties a chapter.
+pub async fn move_scene_to_chapter(
+    pool: &SqlitePool,
+    scene_id: &str,
+    target_chapter: i32,
+    position: u32
+) -> AppResult<()> {
+    if target_chapter < 1 {
+        return Err(AppError::validation_field(
+            "Chapter number must be at least 1".to_string(),
+            "target_chapter_number".to_string(),
+            target_chapter.to_string()
+        ));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let mut order: Vec<(String, Option<i32>)> = sqlx::query_as(
+        "SELECT id, chapter_number FROM scenes ORDER BY index_in_manuscript, created_at, id"
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    let from = order
+        .iter()
+        .position(|(id, _)| id == scene_id)
+        .ok_or_else(|| AppError::not_found_with_id("Scene", scene_id))?;
+    let (moved_id, _) = order.remove(from);
+
+    let insert_at = match order.iter().position(|(_, chapter)| *chapter == Some(target_chapter)) {
+        Some(start) => {
+            let run = order[start..].iter().take_while(|(_, chapter)| *chapter == Some(target_chapter)).count();
+            start + (position as usize).min(run)
+        }
+        None => order
+            .iter()
+            .position(|(_, chapter)| chapter.is_some_and(|n| n > target_chapter))
+            .unwrap_or(order.len()),
+    };
+    order.insert(insert_at, (moved_id, Some(target_chapter)));
+
+    let mut scene_number = 0;
+    for (index, (id, chapter)) in order.iter().enumerate() {
+        let continues = index > 0 && order[index - 1].1 == *chapter;
+        scene_number = if continues { scene_number + 1 } else { 1 };
+
+        // Scenes outside chapters keep their own numbering and flags
+        match chapter {
+            Some(_) => {
+                let chapter_end = order.get(index + 1).map_or(true, |next| next.1 != *chapter);
+                sqlx::query("UPDATE scenes SET index_in_manuscript = ?, chapter_number = ?, scene_number_in_chapter = ?, is_chapter_end = ?, is_opening = ? WHERE id = ?")
+                    .bind(index as i64)
+                    .bind(chapter)
+                    .bind(scene_number)
+                    .bind(chapter_end)
+                    .bind(index == 0)
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            None => {
+                sqlx::query("UPDATE scenes SET index_in_manuscript = ?, is_opening = ? WHERE id = ?")
+                    .bind(index as i64)
+                    .bind(index == 0)
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+// Single-manuscript mode keeps one manuscript per database, so a copy can't sit
+// beside its source. Branching writes it to a new database file instead: an
+// exact copy (comments, notes, analysis and all) under the new title, with a
+// fresh manuscript id and fresh scene ids so the two can't be confused when
+// compared or merged.
+pub async fn duplicate_manuscript(
+    pool: &SqlitePool,
+    destination: &Path,
+    new_title: &str,
+    now: i64
+) -> AppResult<ManuscriptCopy> {
+    let new_title = new_title.trim();
+    if new_title.is_empty() {
+        return Err(AppError::validation_field("Manuscript title cannot be empty", "new_title", ""));
+    }
+    if destination.exists() {
+        return Err(AppError::file_system_with_path(
+            "A file already exists at the copy destination",
+            "duplicate_manuscript",
+            destination.to_path_buf()
+        ));
+    }
+
+    // VACUUM INTO writes a consistent snapshot without blocking other readers
+    sqlx::query("VACUUM INTO ?")
+        .bind(destination.to_string_lossy().into_owned())
+        .execute(pool)
+        .await?;
+
+    let config = DatabaseConfig { max_connections: 1, wal_mode: false, ..DatabaseConfig::default() };
+    let copy = open_pool(destination, &config).await?;
+    let result = rekey_copied_manuscript(&copy, new_title, now).await;
+    copy.close().await;
+
+    if result.is_err() {
+        let _ = std::fs::remove_file(destination);
+    }
+    let (manuscript_id, scene_count) = result?;
+
+    Ok(ManuscriptCopy {
+        path: destination.to_string_lossy().into_owned(),
+        manuscript_id,
+        title: new_title.to_string(),
+        scene_count,
+    })
+}
+
+// (table, column) pairs that hold ids of `parent`: declared foreign keys, plus
+// the listed tables whose `column` refers to it without one
+async fn referencing_columns(
+    conn: &mut SqliteConnection,
+    parent: &str,
+    column: &str,
+    undeclared: &[&str]
+) -> AppResult<Vec<(String, String)>> {
+    let mut references: Vec<(String, String)> = sqlx::query_as(
+        "SELECT m.name, f.\"from\" FROM sqlite_master m JOIN pragma_foreign_key_list(m.name) f WHERE m.type = 'table' AND f.\"table\" = ?"
+    )
+    .bind(parent)
+    .fetch_all(&mut *conn)
+    .await?;
+    for table in undeclared {
+        let reference = (table.to_string(), column.to_string());
+        if table_exists(conn, table).await? && !references.contains(&reference) {
+            references.push(reference);
+        }
+    }
+
+    Ok(references)
+}
+
+async fn rekey_row(
+    conn: &mut SqliteConnection,
+    parent: &str,
+    old_id: &str,
+    new_id: &str,
+    references: &[(String, String)]
+) -> AppResult<()> {
+    sqlx::query(&format!("UPDATE \"{}\" SET id = ? WHERE id = ?", parent))
+        .bind(new_id)
+        .bind(old_id)
+        .execute(&mut *conn)
+        .await?;
+    for (table, column) in references {
+        sqlx::query(&format!("UPDATE \"{}\" SET \"{}\" = ? WHERE \"{}\" = ?", table, column, column))
+            .bind(new_id)
+            .bind(old_id)
+            .execute(&mut *conn)
+            .await?;
+    }
+
+    Ok(())
+}
+
+// Give the manuscript and every scene in a copied database new ids, following
+// them through each column that refers to them, then retitle the manuscript
+async fn rekey_copied_manuscript(pool: &SqlitePool, title: &str, now: i64) -> AppResult<(String, u32)> {
+    let mut conn = pool.acquire().await?;
+
+    let scene_references = referencing_columns(&mut conn, "scenes", "scene_id", SCENE_DEPENDENT_TABLES).await?;
+    let manuscript_references = referencing_columns(&mut conn, "manuscripts", "manuscript_id", MANUSCRIPT_DEPENDENT_TABLES).await?;
+
+    // Ids change in two steps (parent, then children), which foreign keys
+    // would reject midway; the pragma only takes effect outside a transaction
+    sqlx::query("PRAGMA foreign_keys = OFF").execute(&mut *conn).await?;
+
+    let mut tx = sqlx::Connection::begin(&mut *conn).await?;
+    let scene_ids: Vec<String> = sqlx::query_scalar("SELECT id FROM scenes ORDER BY index_in_manuscript")
+        .fetch_all(&mut *tx)
+        .await?;
+    for old_id in &scene_ids {
+        rekey_row(&mut tx, "scenes", old_id, &uuid::Uuid::new_v4().to_string(), &scene_references).await?;
+    }
+
+    let old_manuscript_id: String = sqlx::query_scalar("SELECT id FROM manuscripts")
+        .fetch_one(&mut *tx)
+        .await?;
+    let manuscript_id = uuid::Uuid::new_v4().to_string();
+    rekey_row(&mut tx, "manuscripts", &old_manuscript_id, &manuscript_id, &manuscript_references).await?;
+    sqlx::query("UPDATE manuscripts SET title = ?, updated_at = ? WHERE id = ?")
+        .bind(title)
+        .bind(now)
+        .bind(&manuscript_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let violations: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pragma_foreign_key_check")
+        .fetch_one(&mut *tx)
+        .await?;
+    if violations > 0 {
+        return Err(AppError::database(format!("Copied manuscript has {} broken references", violations)));
+    }
+    tx.commit().await?;
+
+    sqlx::query("PRAGMA foreign_keys = ON").execute(&mut *conn).await?;
+    Ok((manuscript_id, scene_ids.len() as u32))
+}
+
+pub const DEFAULT_MANUSCRIPT_TITLE: &str = "Untitled Manuscript";
+
+// Create the singleton manuscript row if the table is empty. Returns whether a
+// row was created; safe to call on every load.
+pub async fn ensure_singleton_manuscript(pool: &SqlitePool) -> AppResult<bool> {
+    let now = Utc::now().timestamp_millis();
+    let result = sqlx::query(
+        "INSERT INTO manuscripts (id, title, created_at, updated_at, total_word_count) SELECT ?, ?, ?, ?, 0 WHERE NOT EXISTS (SELECT 1 FROM manuscripts)"
+    )
+    .bind(SINGLETON_MANUSCRIPT_ID)
+    .bind(DEFAULT_MANUSCRIPT_TITLE)
+    .bind(now)
+    .bind(now)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+// Per-scene tables to clean up alongside their scenes. Not every database has
+// all of them, depending on which migrations have run.
+const SCENE_DEPENDENT_TABLES: &[&str] = &["module_status", "scene_revisions"];
+
+// Tables keyed by manuscript_id without a declared foreign key
+const MANUSCRIPT_DEPENDENT_TABLES: &[&str] = &["notes", "word_goals", "word_count_snapshots"];
+
+async fn table_exists(conn: &mut SqliteConnection, table: &str) -> AppResult<bool> {
+    let found: Option<String> = sqlx::query_scalar(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name = ?"
+    )
+    .bind(table)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    Ok(found.is_some())
+}
+
+// Remove the manuscript, its scenes and their per-scene rows. Scenes go with the
+// manuscript through the cascade trigger (migration 008); per-scene tables
+// without ON DELETE clauses are cleared first so enforced foreign keys don't
+// block the cascade. Callers run this inside a transaction.
+async fn clear_manuscript(conn: &mut SqliteConnection) -> AppResult<()> {
+    for table in SCENE_DEPENDENT_TABLES {
+        if table_exists(conn, table).await? {
+            sqlx::query(&format!("DELETE FROM {} WHERE scene_id IN (SELECT id FROM scenes)", table))
+                .execute(&mut *conn)
+                .await?;
+        }
+    }
+
+    sqlx::query("DELETE FROM manuscripts").execute(&mut *conn).await?;
+
+    Ok(())
+}
+
+pub async fn delete_manuscript(pool: &SqlitePool) -> AppResult<()> {
+    let mut tx = pool.begin().await?;
+    clear_manuscript(&mut tx).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+// Swap the singleton manuscript and all of its scenes for new ones. Runs in a
+// single transaction, so a failure leaves the previous manuscript untouched.
+pub async fn replace_manuscript_with_scenes(
+    pool: &SqlitePool,
+    manuscript: &Manuscript,
+    scenes: &[Scene]
+) -> AppResult<()> {
+    import_manuscript(pool, manuscript, scenes, true).await
+}
+
+// Like replace_manuscript_with_scenes, but a manuscript that already has
+// scenes is only overwritten when `replace` is set; otherwise it's a Conflict.
+pub async fn import_manuscript(
+    pool: &SqlitePool,
+    manuscript: &Manuscript,
+    scenes: &[Scene],
+    replace: bool
+) -> AppResult<()> {
+    let mut tx = pool.begin().await?;
+
+    if !replace {
+        let existing: Option<String> = sqlx::query_scalar(
+            "SELECT id FROM manuscripts WHERE EXISTS (SELECT 1 FROM scenes) LIMIT 1"
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        if let Some(existing_id) = existing {
+            return Err(AppError::conflict_with_id(
+                "A manuscript with scenes already exists. Confirm replacing it to import.".to_string(),
+                "Manuscript".to_string(),
+                existing_id
+            ));
+        }
+    }
+
+    clear_manuscript(&mut tx).await?;
+
+    sqlx::query(
+        "INSERT INTO manuscripts (id, title, author, genre, target_audience, comp_titles, created_at, updated_at, total_word_count, opening_strength_score, hook_effectiveness) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+    )
+    .bind(&manuscript.id)
+    .bind(&manuscript.title)
+    .bind(&manuscript.author)
+    .bind(&manuscript.genre)
+    .bind(&manuscript.target_audience)
+    .bind(&manuscript.comp_titles)
+    .bind(manuscript.created_at)
+    .bind(manuscript.updated_at)
+    .bind(manuscript.total_word_count)
+    .bind(manuscript.opening_strength_score)
+    .bind(manuscript.hook_effectiveness)
+    .execute(&mut *tx)
+    .await?;
+
+    insert_scenes(&mut tx, scenes).await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+// Analysis modules tracked per scene in module_status
+pub const ANALYSIS_MODULES: &[&str] = &["events", "plants", "state", "beats"];
+
+// Flag one analysis module of a scene for reprocessing. Stale extracted events
+// are dropped with the flag.
+async fn mark_module_dirty(conn: &mut SqliteConnection, scene_id: &str, module: &str) -> AppResult<()> {
+    if !ANALYSIS_MODULES.contains(&module) {
+        return Err(AppError::validation_field("Unknown analysis module", "module", module));
+    }
+
+    if module == "events" && table_exists(conn, "events").await? {
+        sqlx::query("DELETE FROM events WHERE scene_id = ?")
+            .bind(scene_id)
+            .execute(&mut *conn)
+            .await?;
+    }
+
+    sqlx::query(&format!(
+        "INSERT INTO module_status (scene_id, {0}_dirty) VALUES (?, 1) ON CONFLICT(scene_id) DO UPDATE SET {0}_dirty = 1",
+        module
+    ))
+    .bind(scene_id)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn mark_scene_modules_dirty(pool: &SqlitePool, scene_id: &str, modules: &[String]) -> AppResult<()> {
+    let mut tx = pool.begin().await?;
+
+    scene_text(&mut tx, scene_id).await?;
+    for module in modules {
+        mark_module_dirty(&mut tx, scene_id, module).await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+// Replace a scene's extracted events with `events`, numbered in the order given
+pub async fn record_scene_events(pool: &SqlitePool, scene_id: &str, events: &[SceneEvent], now: i64) -> AppResult<()> {
+    if events.iter().any(|event| event.description.trim().is_empty()) {
+        return Err(AppError::validation_field("Event description cannot be empty", "description", ""));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    scene_text(&mut tx, scene_id).await?;
+    replace_scene_events(&mut tx, scene_id, events, now).await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+async fn replace_scene_events(conn: &mut SqliteConnection, scene_id: &str, events: &[SceneEvent], now: i64) -> AppResult<()> {
+    sqlx::query("DELETE FROM events WHERE scene_id = ?")
+        .bind(scene_id)
+        .execute(&mut *conn)
+        .await?;
+
+    for (sequence, event) in events.iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO events (scene_id, sequence, description, characters, time_marker, created_at) VALUES (?, ?, ?, ?, ?, ?)"
+        )
+        .bind(scene_id)
+        .bind(sequence as i64)
+        .bind(event.description.trim())
+        .bind(serde_json::to_string(&event.characters)?)
+        .bind(&event.time_marker)
+        .bind(now)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+// Every recorded event in story order
+pub async fn get_manuscript_events(pool: &SqlitePool) -> AppResult<Vec<ManuscriptEvent>> {
+    let rows: Vec<(String, i64, i64, String, String, Option<String>)> = sqlx::query_as(
+        "SELECT e.scene_id, s.index_in_manuscript, e.sequence, e.description, e.characters, e.time_marker FROM events e JOIN scenes s ON s.id = e.scene_id ORDER BY s.index_in_manuscript, e.sequence"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|(scene_id, index, sequence, description, characters, time_marker)| -> AppResult<ManuscriptEvent> {
+            Ok(ManuscriptEvent {
+                scene_id,
+                index_in_manuscript: index as u32,
+                sequence: sequence as u32,
+                event: SceneEvent {
+                    description,
+                    characters: serde_json::from_str(&characters)?,
+                    time_marker,
+                },
+            })
+        })
+        .collect()
+}
+
+fn validate_state_key(key: &str) -> AppResult<&str> {
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(AppError::validation_field("State key cannot be empty", "key", key));
+    }
+    Ok(key)
+}
+
+// Record the value a state key has at a scene, or remove it with None. The
+// scene's state module is marked dirty.
+pub async fn set_state_at_scene(
+    pool: &SqlitePool,
+    scene_id: &str,
+    key: &str,
+    value: Option<&str>,
+    now: i64
+) -> AppResult<()> {
+    let key = validate_state_key(key)?;
+    let mut tx = pool.begin().await?;
+
+    scene_text(&mut tx, scene_id).await?;
+    match value {
+        Some(value) => {
+            sqlx::query(
+                "INSERT INTO world_state (scene_id, state_key, value, updated_at) VALUES (?, ?, ?, ?) ON CONFLICT(scene_id, state_key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at"
+            )
+            .bind(scene_id)
+            .bind(key)
+            .bind(value)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        }
+        None => {
+            sqlx::query("DELETE FROM world_state WHERE scene_id = ? AND state_key = ?")
+                .bind(scene_id)
+                .bind(key)
+                .execute(&mut *tx)
+                .await?;
+        }
+    }
+
+    mark_module_dirty(&mut tx, scene_id, "state").await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+// How a state key's value progresses through the manuscript
+pub async fn get_state_timeline(pool: &SqlitePool, key: &str) -> AppResult<Vec<StateChange>> {
+    let key = validate_state_key(key)?;
+    let rows: Vec<(String, Option<String>, i64, String)> = sqlx::query_as(
+        "SELECT s.id, s.title, s.index_in_manuscript, w.value FROM world_state w JOIN scenes s ON s.id = w.scene_id WHERE w.state_key = ? ORDER BY s.index_in_manuscript"
+    )
+    .bind(key)
+    .fetch_all(pool)
+    .await?;
+
+    let mut previous_value = None;
+    Ok(rows
+        .into_iter()
+        .map(|(scene_id, scene_title, index, value)| StateChange {
+            scene_id,
+            scene_title,
+            index_in_manuscript: index as u32,
+            previous_value: previous_value.replace(value.clone()),
+            value,
+        })
+        .collect())
+}
+
+// Recorded in `<module>_v` with the scene revision it was computed from, e.g.
+// "local-1@1700000000000"
+pub const MODULE_ANALYZER_VERSION: &str = "local-1";
+
+// The modules recompute_scene_modules refreshed for one scene
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecomputedScene {
+    pub scene_id: String,
+    pub modules: Vec<String>,
+}
+
+// Scenes with at least one dirty module, in manuscript order
+pub async fn dirty_scene_ids(pool: &SqlitePool) -> AppResult<Vec<String>> {
+    let mut conn = pool.acquire().await?;
+    let ids = sqlx::query_scalar(
+        "SELECT m.scene_id FROM module_status m JOIN scenes s ON s.id = m.scene_id WHERE m.events_dirty = 1 OR m.plants_dirty = 1 OR m.state_dirty = 1 OR m.beats_dirty = 1 ORDER BY s.index_in_manuscript"
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(ids)
+}
+
+// Bring a scene's dirty modules up to date in one transaction: events are
+// re-extracted from the text, plants are clamped to the text's current length,
+// and state and beats (entered by the author) are acknowledged. Each processed
+// module gets a new version string and its dirty flag cleared.
+pub async fn recompute_scene_modules(pool: &SqlitePool, scene_id: &str, now: i64) -> AppResult<RecomputedScene> {
+    let mut tx = pool.begin().await?;
+
+    let flags: Option<(i64, i64, i64, i64)> = sqlx::query_as(
+        "SELECT COALESCE(events_dirty, 0), COALESCE(plants_dirty, 0), COALESCE(state_dirty, 0), COALESCE(beats_dirty, 0) FROM module_status WHERE scene_id = ?"
+    )
+    .bind(scene_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let (events, plants, state, beats) = flags.unwrap_or_default();
+
+    let scene: Option<(String, Option<String>, Option<String>, Option<i64>)> = sqlx::query_as(
+        "SELECT raw_text, pov_character, time_marker, updated_at FROM scenes WHERE id = ?"
+    )
+    .bind(scene_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let (raw_text, pov_character, time_marker, updated_at) = scene
+        .ok_or_else(|| AppError::not_found_with_id("Scene", scene_id))?;
+
+    let mut modules = Vec::new();
+    if events == 1 {
+        let extracted = crate::analysis::extract_scene_events(&raw_text, pov_character.as_deref(), time_marker.as_deref());
+        replace_scene_events(&mut tx, scene_id, &extracted, now).await?;
+        modules.push("events");
+    }
+    if plants == 1 {
+        let text_len = raw_text.chars().count() as i64;
+        sqlx::query(
+            "UPDATE plants SET start_offset = MIN(start_offset, ?1), end_offset = ?1, updated_at = ?2 WHERE scene_id = ?3 AND end_offset > ?1"
+        )
+        .bind(text_len)
+        .bind(now)
+        .bind(scene_id)
+        .execute(&mut *tx)
+        .await?;
+        modules.push("plants");
+    }
+    if state == 1 {
+        modules.push("state");
+    }
+    if beats == 1 {
+        modules.push("beats");
+    }
+
+    let version = format!("{}@{}", MODULE_ANALYZER_VERSION, updated_at.unwrap_or(now));
+    for module in &modules {
+        sqlx::query(&format!(
+            "UPDATE module_status SET {0}_dirty = 0, {0}_v = ?, last_processed = CURRENT_TIMESTAMP WHERE scene_id = ?",
+            module
+        ))
+        .bind(&version)
+        .bind(scene_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(RecomputedScene {
+        scene_id: scene_id.to_string(),
+        modules: modules.into_iter().map(str::to_string).collect(),
+    })
+}
+
+pub async fn recompute_dirty_modules(pool: &SqlitePool, now: i64) -> AppResult<Vec<RecomputedScene>> {
+    let mut recomputed = Vec::new();
+    for scene_id in dirty_scene_ids(pool).await? {
+        recomputed.push(recompute_scene_modules(pool, &scene_id, now).await?);
+    }
+    Ok(recomputed)
+}
+
+pub async fn scene_module_status(pool: &SqlitePool, scene_id: &str) -> AppResult<Option<ModuleStatus>> {
+    let mut conn = pool.acquire().await?;
+    let row: Option<(String, Option<String>, i32, Option<String>, i32, Option<String>, i32, Option<String>, i32, String)> = sqlx::query_as(
+        "SELECT scene_id, events_v, COALESCE(events_dirty, 0), plants_v, COALESCE(plants_dirty, 0), state_v, COALESCE(state_dirty, 0), beats_v, COALESCE(beats_dirty, 0), COALESCE(CAST(last_processed AS TEXT), '') FROM module_status WHERE scene_id = ?"
+    )
+    .bind(scene_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    Ok(row.map(|(scene_id, events_v, events_dirty, plants_v, plants_dirty, state_v, state_dirty, beats_v, beats_dirty, last_processed)| ModuleStatus {
+        scene_id,
+        events_v,
+        events_dirty,
+        plants_v,
+        plants_dirty,
+        state_v,
+        state_dirty,
+        beats_v,
+        beats_dirty,
+        last_processed,
+    }))
+}
+
+// Record the version a module was processed at and whether it still needs work
+pub async fn set_scene_module_status(pool: &SqlitePool, request: &UpdateModuleStatusRequest) -> AppResult<()> {
+    let module = request.module.as_str();
+    if !ANALYSIS_MODULES.contains(&module) {
+        return Err(AppError::validation_field("Unknown analysis module", "module", module));
+    }
+
+    let mut tx = pool.begin().await?;
+    scene_text(&mut tx, &request.scene_id).await?;
+
+    sqlx::query(&format!(
+        "INSERT INTO module_status (scene_id, {0}_v, {0}_dirty, last_processed) VALUES (?1, ?2, ?3, CURRENT_TIMESTAMP) ON CONFLICT(scene_id) DO UPDATE SET {0}_v = ?2, {0}_dirty = ?3, last_processed = CURRENT_TIMESTAMP",
+        module
+    ))
+    .bind(&request.scene_id)
+    .bind(&request.version)
+    .bind(request.dirty as i32)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+pub async fn clear_module_dirty_flags(pool: &SqlitePool) -> AppResult<()> {
+    sqlx::query("UPDATE module_status SET events_dirty = 0, plants_dirty = 0, state_dirty = 0, beats_dirty = 0")
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn add_comment(
+    pool: &SqlitePool,
+    scene_id: &str,
+    position: u32,
+    text: &str,
+    author: Option<&str>,
+    now: i64
+) -> AppResult<SceneComment> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(AppError::validation_field("Comment text cannot be empty", "text", ""));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let text_len = scene_text(&mut tx, scene_id).await?.chars().count() as u32;
+    if position > text_len {
+        return Err(AppError::validation_field(
+            "Comment position is past the end of the scene".to_string(),
+            "position".to_string(),
+            format!("{} of {}", position, text_len)
+        ));
+    }
+
+    let comment = SceneComment {
+        id: uuid::Uuid::new_v4().to_string(),
+        scene_id: scene_id.to_string(),
+        position,
+        text: text.to_string(),
+        author: author.map(str::trim).filter(|name| !name.is_empty()).map(str::to_string),
+        created_at: now,
+    };
+    sqlx::query("INSERT INTO comments (id, scene_id, position, text, author, created_at) VALUES (?, ?, ?, ?, ?, ?)")
+        .bind(&comment.id)
+        .bind(&comment.scene_id)
+        .bind(comment.position)
+        .bind(&comment.text)
+        .bind(&comment.author)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(comment)
+}
+
+type CommentRow = (String, String, i64, String, Option<String>, i64);
+
+fn comment_from_row((id, scene_id, position, text, author, created_at): CommentRow) -> SceneComment {
+    SceneComment { id, scene_id, position: position as u32, text, author, created_at }
+}
+
+// A scene's comments in reading order
+pub async fn get_scene_comments(pool: &SqlitePool, scene_id: &str) -> AppResult<Vec<SceneComment>> {
+    let rows: Vec<CommentRow> = sqlx::query_as(
+        "SELECT id, scene_id, position, text, author, created_at FROM comments WHERE scene_id = ? ORDER BY position, created_at"
+    )
+    .bind(scene_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(comment_from_row).collect())
+}
+
+// Every comment in the manuscript, grouped by scene in manuscript order
+pub async fn get_all_comments(pool: &SqlitePool) -> AppResult<Vec<SceneComment>> {
+    let rows: Vec<CommentRow> = sqlx::query_as(
+        "SELECT c.id, c.scene_id, c.position, c.text, c.author, c.created_at FROM comments c JOIN scenes s ON s.id = c.scene_id ORDER BY s.index_in_manuscript, c.position, c.created_at"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(comment_from_row).collect())
+}
+
+// Returns whether a comment was removed
+pub async fn delete_comment(pool: &SqlitePool, comment_id: &str) -> AppResult<bool> {
+    let result = sqlx::query("DELETE FROM comments WHERE id = ?")
+        .bind(comment_id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+// Tags are trimmed with inner whitespace collapsed, so "needs  revision " and
+// "needs revision" are the same tag
+fn normalize_tag(tag: &str) -> AppResult<String> {
+    let tag = tag.split_whitespace().collect::<Vec<_>>().join(" ");
+    if tag.is_empty() {
+        return Err(AppError::validation_field("Tag cannot be empty", "tag", ""));
+    }
+    Ok(tag)
+}
+
+// Tagging a scene twice (in any letter case) is a no-op
+pub async fn add_scene_tag(pool: &SqlitePool, scene_id: &str, tag: &str, now: i64) -> AppResult<()> {
+    let tag = normalize_tag(tag)?;
+    let mut tx = pool.begin().await?;
+
+    scene_text(&mut tx, scene_id).await?;
+    sqlx::query("INSERT OR IGNORE INTO scene_tags (scene_id, tag, created_at) VALUES (?, ?, ?)")
+        .bind(scene_id)
+        .bind(&tag)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+// Returns whether the scene had the tag
+pub async fn remove_scene_tag(pool: &SqlitePool, scene_id: &str, tag: &str) -> AppResult<bool> {
+    let tag = normalize_tag(tag)?;
+    let result = sqlx::query("DELETE FROM scene_tags WHERE scene_id = ? AND tag = ?")
+        .bind(scene_id)
+        .bind(&tag)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+// Scenes carrying a tag, in manuscript order
+pub async fn get_scenes_by_tag(pool: &SqlitePool, tag: &str) -> AppResult<Vec<TaggedScene>> {
+    let tag = normalize_tag(tag)?;
+    let rows: Vec<(String, Option<String>, Option<i32>, i64)> = sqlx::query_as(
+        "SELECT s.id, s.title, s.chapter_number, s.index_in_manuscript FROM scene_tags t JOIN scenes s ON s.id = t.scene_id WHERE t.tag = ? ORDER BY s.index_in_manuscript"
+    )
+    .bind(&tag)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(scene_id, scene_title, chapter_number, index)| TaggedScene {
+            scene_id,
+            scene_title,
+            chapter_number,
+            index_in_manuscript: index as u32,
+        })
+        .collect())
+}
+
+// Every scene's tags, alphabetical within a scene
+pub async fn get_all_scene_tags(pool: &SqlitePool) -> AppResult<HashMap<String, Vec<String>>> {
+    let rows: Vec<(String, String)> = sqlx::query_as("SELECT scene_id, tag FROM scene_tags ORDER BY scene_id, tag")
+        .fetch_all(pool)
+        .await?;
+
+    let mut tags: HashMap<String, Vec<String>> = HashMap::new();
+    for (scene_id, tag) in rows {
+        tags.entry(scene_id).or_default().push(tag);
+    }
+    Ok(tags)
+}
+
+pub async fn set_scene_hook_flags(
+    pool: &SqlitePool,
+    scene_id: &str,
+    opens_with_hook: bool,
+    ends_with_hook: bool,
+    now: i64
+) -> AppResult<()> {
+    let result = sqlx::query(
+        "UPDATE scenes SET opens_with_hook = ?, ends_with_hook = ?, updated_at = ? WHERE id = ?"
+    )
+    .bind(opens_with_hook)
+    .bind(ends_with_hook)
+    .bind(now)
+    .bind(scene_id)
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::not_found_with_id("Scene", scene_id));
+    }
+    Ok(())
+}
+
+pub async fn store_opening_scores(
+    pool: &SqlitePool,
+    manuscript_id: &str,
+    opening_strength: u32,
+    hook_effectiveness: u32,
+    now: i64
+) -> AppResult<()> {
+    let result = sqlx::query(
+        "UPDATE manuscripts SET opening_strength_score = ?, hook_effectiveness = ?, updated_at = ? WHERE id = ?"
+    )
+    .bind(opening_strength)
+    .bind(hook_effectiveness)
+    .bind(now)
+    .bind(manuscript_id)
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::not_found_with_id("Manuscript", manuscript_id));
+    }
+    Ok(())
+}
+
+// Set a goal's target; a target of 0 removes the goal. Changing a target lets
+// it be reached (and announced) again.
+pub async fn set_word_goal(
+    pool: &SqlitePool,
+    manuscript_id: &str,
+    kind: GoalKind,
+    target: u32,
+    now: i64
+) -> AppResult<()> {
+    if target == 0 {
+        sqlx::query("DELETE FROM word_goals WHERE manuscript_id = ? AND kind = ?")
+            .bind(manuscript_id)
+            .bind(kind.as_str())
+            .execute(pool)
+            .await?;
+        return Ok(());
+    }
+
+    sqlx::query(
+        "INSERT INTO word_goals (manuscript_id, kind, target, reached_on, updated_at) VALUES (?, ?, ?, NULL, ?) ON CONFLICT(manuscript_id, kind) DO UPDATE SET target = excluded.target, reached_on = NULL, updated_at = excluded.updated_at"
+    )
+    .bind(manuscript_id)
+    .bind(kind.as_str())
+    .bind(target)
+    .bind(now)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// Progress against the manuscript's goals, plus the goals that were reached
+// since the last check. A project goal that falls back below its target can
+// be reached again; a daily goal is reached at most once per day.
+pub async fn refresh_goal_progress(pool: &SqlitePool, manuscript_id: &str) -> AppResult<(GoalProgress, Vec<GoalKind>)> {
+    let mut tx = pool.begin().await?;
+
+    record_day_start_word_count(&mut tx).await?;
+    let today: String = sqlx::query_scalar("SELECT date('now', 'localtime')").fetch_one(&mut *tx).await?;
+    let total: Option<i64> = sqlx::query_scalar("SELECT COALESCE(total_word_count, 0) FROM manuscripts WHERE id = ?")
+        .bind(manuscript_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+    let total = total.unwrap_or(0).max(0);
+    let day_start: Option<i64> = sqlx::query_scalar(
+        "SELECT starting_word_count FROM word_count_snapshots WHERE manuscript_id = ? AND day = ?"
+    )
+    .bind(manuscript_id)
+    .bind(&today)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let mut progress = GoalProgress {
+        manuscript_id: manuscript_id.to_string(),
+        total_word_count: total as u32,
+        words_today: (total - day_start.unwrap_or(total)).max(0) as u32,
+        project: None,
+        daily: None,
+    };
+
+    let goals: Vec<(String, i64, Option<String>)> = sqlx::query_as(
+        "SELECT kind, target, reached_on FROM word_goals WHERE manuscript_id = ?"
+    )
+    .bind(manuscript_id)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let mut newly_reached = Vec::new();
+    for (kind, target, reached_on) in goals {
+        let Some(kind) = GoalKind::parse(&kind) else {
+            continue;
+        };
+        let status = match kind {
+            GoalKind::Project => GoalStatus::new(target as u32, progress.total_word_count),
+            GoalKind::Daily => GoalStatus::new(target as u32, progress.words_today),
+        };
+
+        let marked = match kind {
+            GoalKind::Project => reached_on.is_some(),
+            GoalKind::Daily => reached_on.as_deref() == Some(today.as_str()),
+        };
+        if status.reached != marked {
+            sqlx::query("UPDATE word_goals SET reached_on = ? WHERE manuscript_id = ? AND kind = ?")
+                .bind(status.reached.then_some(today.as_str()))
+                .bind(manuscript_id)
+                .bind(kind.as_str())
+                .execute(&mut *tx)
+                .await?;
+            if status.reached {
+                newly_reached.push(kind);
+            }
+        }
+
+        match kind {
+            GoalKind::Project => progress.project = Some(status),
+            GoalKind::Daily => progress.daily = Some(status),
+        }
+    }
+
+    tx.commit().await?;
+    newly_reached.sort_by_key(|kind| kind.as_str());
+    Ok((progress, newly_reached))
+}
+
+fn validate_note_title(title: &str) -> AppResult<&str> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err(AppError::validation_field("Note title cannot be empty", "title", ""));
+    }
+    Ok(title)
+}
+
+pub async fn create_note(
+    pool: &SqlitePool,
+    manuscript_id: &str,
+    title: &str,
+    body: &str,
+    now: i64
+) -> AppResult<Note> {
+    let note = Note {
+        id: uuid::Uuid::new_v4().to_string(),
+        manuscript_id: manuscript_id.to_string(),
+        title: validate_note_title(title)?.to_string(),
+        body: body.to_string(),
+        created_at: now,
+        updated_at: now,
+    };
+
+    sqlx::query("INSERT INTO notes (id, manuscript_id, title, body, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)")
+        .bind(&note.id)
+        .bind(&note.manuscript_id)
+        .bind(&note.title)
+        .bind(&note.body)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+    Ok(note)
+}
+
+const NOTE_COLUMNS: &str = "id, manuscript_id, title, body, created_at, updated_at";
+
+type NoteRow = (String, String, String, String, i64, i64);
+
+fn note_from_row((id, manuscript_id, title, body, created_at, updated_at): NoteRow) -> Note {
+    Note { id, manuscript_id, title, body, created_at, updated_at }
+}
+
+// A manuscript's notes, most recently edited first
+pub async fn list_notes(pool: &SqlitePool, manuscript_id: &str) -> AppResult<Vec<Note>> {
+    let rows: Vec<NoteRow> = sqlx::query_as(&format!(
+        "SELECT {} FROM notes WHERE manuscript_id = ? ORDER BY updated_at DESC, created_at DESC",
+        NOTE_COLUMNS
+    ))
+    .bind(manuscript_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(note_from_row).collect())
+}
+
+pub async fn update_note(
+    pool: &SqlitePool,
+    manuscript_id: &str,
+    note_id: &str,
+    title: &str,
+    body: &str,
+    now: i64
+) -> AppResult<Note> {
+    let title = validate_note_title(title)?;
+
+    let row: Option<NoteRow> = sqlx::query_as(&format!(
+        "UPDATE notes SET title = ?, body = ?, updated_at = ? WHERE id = ? AND manuscript_id = ? RETURNING {}",
+        NOTE_COLUMNS
+    ))
+    .bind(title)
+    .bind(body)
+    .bind(now)
+    .bind(note_id)
+    .bind(manuscript_id)
+    .fetch_optional(pool)
+    .await?;
+
+    row.map(note_from_row)
+        .ok_or_else(|| AppError::not_found_with_id("Note", note_id))
+}
+
+// Returns whether a note was removed
+pub async fn delete_note(pool: &SqlitePool, manuscript_id: &str, note_id: &str) -> AppResult<bool> {
+    let result = sqlx::query("DELETE FROM notes WHERE id = ? AND manuscript_id = ?")
+        .bind(note_id)
+        .bind(manuscript_id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+// Tag a scene with a beat, or clear its beat with None. The scene's beats
+// module is marked dirty so beat analysis picks up the change.
+pub async fn set_scene_beat(
+    pool: &SqlitePool,
+    scene_id: &str,
+    beat_type: Option<BeatType>,
+    now: i64
+) -> AppResult<()> {
+    let mut tx = pool.begin().await?;
+
+    let found: Option<String> = sqlx::query_scalar("SELECT id FROM scenes WHERE id = ?")
+        .bind(scene_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+    if found.is_none() {
+        return Err(AppError::not_found_with_id("Scene", scene_id));
+    }
+
+    match beat_type {
+        Some(beat_type) => {
+            sqlx::query(
+                "INSERT INTO story_beats (scene_id, beat_type, updated_at) VALUES (?, ?, ?) ON CONFLICT(scene_id) DO UPDATE SET beat_type = excluded.beat_type, updated_at = excluded.updated_at"
+            )
+            .bind(scene_id)
+            .bind(beat_type.as_str())
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        }
+        None => {
+            sqlx::query("DELETE FROM story_beats WHERE scene_id = ?")
+                .bind(scene_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+    }
+
+    mark_module_dirty(&mut tx, scene_id, "beats").await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+// The character range of `old` that an edit producing `new` replaced, found by
+// trimming their common prefix and suffix. None when nothing changed.
+fn edited_range(old: &str, new: &str) -> Option<(usize, usize)> {
+    if old == new {
+        return None;
+    }
+
+    let old: Vec<char> = old.chars().collect();
+    let new: Vec<char> = new.chars().collect();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old.iter().rev()
+        .zip(new.iter().rev())
+        .take(old.len().min(new.len()) - prefix)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    Some((prefix, old.len() - suffix))
+}
+
+// Called before a scene's text is replaced: an edit overlapping (or touching
+// the edge of) a planted span marks the scene's plants module dirty
+async fn mark_plants_touched_by_edit(conn: &mut SqliteConnection, scene_id: &str, new_text: &str) -> AppResult<()> {
+    if !table_exists(conn, "plants").await? {
+        return Ok(());
+    }
+
+    let old_text: Option<String> = sqlx::query_scalar("SELECT raw_text FROM scenes WHERE id = ?")
+        .bind(scene_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+    let (start, end) = match old_text.and_then(|old| edited_range(&old, new_text)) {
+        Some(range) => range,
+        None => return Ok(()),
+    };
+
+    let touched: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM plants WHERE scene_id = ? AND start_offset <= ? AND end_offset >= ?"
+    )
+    .bind(scene_id)
+    .bind(end as i64)
+    .bind(start as i64)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    if touched > 0 {
+        mark_module_dirty(conn, scene_id, "plants").await?;
+    }
+    Ok(())
+}
+
+type PlantRow = (String, String, i64, i64, String, Option<String>, i64, i64);
+
+const PLANT_COLUMNS: &str = "p.id, p.scene_id, p.start_offset, p.end_offset, p.description, p.payoff_scene_id, p.created_at, p.updated_at";
+
+fn plant_from_row(row: PlantRow) -> Plant {
+    let (id, scene_id, start_offset, end_offset, description, payoff_scene_id, created_at, updated_at) = row;
+    Plant {
+        id,
+        scene_id,
+        start_offset: start_offset as u32,
+        end_offset: end_offset as u32,
+        description,
+        payoff_scene_id,
+        created_at,
+        updated_at,
+    }
+}
+
+async fn scene_text(conn: &mut SqliteConnection, scene_id: &str) -> AppResult<String> {
+    let text: Option<String> = sqlx::query_scalar("SELECT raw_text FROM scenes WHERE id = ?")
+        .bind(scene_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+    text.ok_or_else(|| AppError::not_found_with_id("Scene", scene_id))
+}
+
+pub async fn get_scene_text(pool: &SqlitePool, scene_id: &str) -> AppResult<String> {
+    let mut conn = pool.acquire().await?;
+    scene_text(&mut conn, scene_id).await
+}
+
+// Plant a setup at a span of a scene. The span must lie within the scene text.
+pub async fn create_plant(pool: &SqlitePool, plant: &NewPlant, now: i64) -> AppResult<Plant> {
+    let description = plant.description.trim();
+    if description.is_empty() {
+        return Err(AppError::validation_field("Plant description cannot be empty", "description", ""));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let text_len = scene_text(&mut tx, &plant.scene_id).await?.chars().count() as u32;
+    if plant.start_offset > plant.end_offset || plant.end_offset > text_len {
+        return Err(AppError::validation_field(
+            "Plant offsets must be a range within the scene text".to_string(),
+            "end_offset".to_string(),
+            format!("{}..{} of {}", plant.start_offset, plant.end_offset, text_len)
+        ));
+    }
+
+    let created = Plant {
+        id: uuid::Uuid::new_v4().to_string(),
+        scene_id: plant.scene_id.clone(),
+        start_offset: plant.start_offset,
+        end_offset: plant.end_offset,
+        description: description.to_string(),
+        payoff_scene_id: None,
+        created_at: now,
+        updated_at: now,
+    };
+    sqlx::query(
+        "INSERT INTO plants (id, scene_id, start_offset, end_offset, description, payoff_scene_id, created_at, updated_at) VALUES (?, ?, ?, ?, ?, NULL, ?, ?)"
+    )
+    .bind(&created.id)
+    .bind(&created.scene_id)
+    .bind(created.start_offset)
+    .bind(created.end_offset)
+    .bind(&created.description)
+    .bind(now)
+    .bind(now)
+    .execute(&mut *tx)
+    .await?;
+
+    mark_module_dirty(&mut tx, &created.scene_id, "plants").await?;
+
+    tx.commit().await?;
+    Ok(created)
+}
+
+// Link the scene that pays a plant off, or unlink it with None
+pub async fn set_plant_payoff(
+    pool: &SqlitePool,
+    plant_id: &str,
+    payoff_scene_id: Option<&str>,
+    now: i64
+) -> AppResult<()> {
+    let mut tx = pool.begin().await?;
+
+    if let Some(payoff_scene_id) = payoff_scene_id {
+        scene_text(&mut tx, payoff_scene_id).await?;
+    }
+
+    let planted_in: Option<String> = sqlx::query_scalar(
+        "UPDATE plants SET payoff_scene_id = ?, updated_at = ? WHERE id = ? RETURNING scene_id"
+    )
+    .bind(payoff_scene_id)
+    .bind(now)
+    .bind(plant_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let planted_in = planted_in.ok_or_else(|| AppError::not_found_with_id("Plant", plant_id))?;
+
+    mark_module_dirty(&mut tx, &planted_in, "plants").await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+// Setups still waiting for a payoff, in manuscript order
+pub async fn get_unpaid_plants(pool: &SqlitePool) -> AppResult<Vec<Plant>> {
+    let rows: Vec<PlantRow> = sqlx::query_as(&format!(
+        "SELECT {} FROM plants p JOIN scenes s ON s.id = p.scene_id WHERE p.payoff_scene_id IS NULL ORDER BY s.index_in_manuscript, p.start_offset",
+        PLANT_COLUMNS
+    ))
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(plant_from_row).collect())
+}
+
+// Tagged scenes in manuscript order. Beat types this version doesn't know are
+// left out rather than failing the whole map.
+pub async fn get_manuscript_beats(pool: &SqlitePool) -> AppResult<Vec<SceneBeat>> {
+    let rows: Vec<(String, Option<String>, Option<i32>, i64, String)> = sqlx::query_as(
+        "SELECT s.id, s.title, s.chapter_number, s.index_in_manuscript, b.beat_type FROM story_beats b JOIN scenes s ON s.id = b.scene_id ORDER BY s.index_in_manuscript"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(scene_id, scene_title, chapter_number, index, beat_type)| {
+            Some(SceneBeat {
+                scene_id,
+                scene_title,
+                chapter_number,
+                index_in_manuscript: index as u32,
+                beat_type: BeatType::parse(&beat_type)?,
+            })
+        })
+        .collect())
+}
+
+// PLACEHOLDER IMPLEMENTATIONS - TODO: Replace with SQLx
+
+// MANUSCRIPT OPERATIONS (Single manuscript mode)
+
+pub async fn get_manuscript_impl(app: &AppHandle) -> AppResult<Option<Manuscript>> {
+    let rows = app.state::<DatabaseService>().execute_with_cache(app, MANUSCRIPT_SELECT, &[]).await?;
+    rows_to_manuscript(&rows)
+}
+
+pub async fn update_manuscript_impl(_app: &AppHandle, _manuscript: Manuscript) -> AppResult<()> {
+    // TODO: Implement with SQLx - update the singleton manuscript
+    Err(AppError::database("Database operations not yet implemented"))
+}
+
+// SCENE CRUD OPERATIONS
+
+pub async fn get_all_scenes_impl(app: &AppHandle) -> AppResult<Vec<Scene>> {
+    let rows = app.state::<DatabaseService>().execute_with_cache(app, SCENES_SELECT, &[]).await?;
+    rows_to_scenes(&rows)
+}
+
+pub async fn get_scene_impl(_app: &AppHandle, _id: String) -> AppResult<Option<Scene>> {
+    // TODO: Implement with SQLx
+    Err(AppError::database("Database operations not yet implemented"))
+}
+
+pub async fn create_scene_impl(_app: &AppHandle, _scene: Scene) -> AppResult<String> {
+    // TODO: Implement with SQLx
     Err(AppError::database("Database operations not yet implemented"))
 }
 
-pub async fn get_scene_content_impl(_app: &AppHandle, _scene_id: String) -> AppResult<Option<String>> {
+pub async fn update_scene_impl(_app: &AppHandle, _scene: Scene) -> AppResult<()> {
     // TODO: Implement with SQLx
-    // Query: SELECT raw_text FROM scenes WHERE id = ?
     Err(AppError::database("Database operations not yet implemented"))
 }
 
-pub async fn clear_all_dirty_flags_impl(_app: &AppHandle) -> AppResult<()> {
+pub async fn delete_scene_impl(_app: &AppHandle, _id: String) -> AppResult<()> {
     // TODO: Implement with SQLx
-    // Update: UPDATE module_status SET events_dirty = 0, plants_dirty = 0, state_dirty = 0, beats_dirty = 0
     Err(AppError::database("Database operations not yet implemented"))
 }
 
+pub async fn rename_scene_impl(app: &AppHandle, request: RenameRequest) -> AppResult<()> {
+    crate::commands::validate_scene_id(&request.scene_id)?;
+    let pool = app.state::<DatabaseService>().pool(app).await?;
+    rename_scene_in(&pool, &request, Utc::now().timestamp_millis()).await
+}
+
+pub async fn reorder_scenes_impl(app: &AppHandle, request: ReorderRequest) -> AppResult<()> {
+    crate::commands::validate_scene_id(&request.scene_id)?;
+    let pool = app.state::<DatabaseService>().pool(app).await?;
+    reorder_scene_in(&pool, &request).await
+}
+
+// SEARCH AND UTILITY OPERATIONS
+
+pub async fn search_content_impl(app: &AppHandle, request: SearchRequest) -> AppResult<Vec<SearchResult>> {
+    let pool = app.state::<DatabaseService>().pool(app).await?;
+    search_scenes(&pool, &request).await
+}
+
+pub async fn create_database_backup_impl(_app: &AppHandle) -> AppResult<BackupMetadata> {
+    // TODO: Implement with SQLx
+    Err(AppError::database("Database operations not yet implemented"))
+}
+
+// MODULE STATUS OPERATIONS
+
+pub async fn get_dirty_scenes_impl(app: &AppHandle) -> AppResult<Vec<String>> {
+    let pool = app.state::<DatabaseService>().pool(app).await?;
+    dirty_scene_ids(&pool).await
+}
+
+pub async fn get_module_status_impl(app: &AppHandle, scene_id: String) -> AppResult<Option<ModuleStatus>> {
+    let pool = app.state::<DatabaseService>().pool(app).await?;
+    scene_module_status(&pool, &scene_id).await
+}
+
+pub async fn mark_modules_dirty_impl(app: &AppHandle, scene_id: String, modules: Vec<String>) -> AppResult<()> {
+    let pool = app.state::<DatabaseService>().pool(app).await?;
+    mark_scene_modules_dirty(&pool, &scene_id, &modules).await
+}
+
+pub async fn update_module_status_impl(app: &AppHandle, request: UpdateModuleStatusRequest) -> AppResult<()> {
+    let pool = app.state::<DatabaseService>().pool(app).await?;
+    set_scene_module_status(&pool, &request).await
+}
+
+pub async fn get_scene_content_impl(app: &AppHandle, scene_id: String) -> AppResult<Option<String>> {
+    let pool = app.state::<DatabaseService>().pool(app).await?;
+    match get_scene_text(&pool, &scene_id).await {
+        Ok(text) => Ok(Some(text)),
+        Err(AppError::NotFound { .. }) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+pub async fn clear_all_dirty_flags_impl(app: &AppHandle) -> AppResult<()> {
+    let pool = app.state::<DatabaseService>().pool(app).await?;
+    clear_module_dirty_flags(&pool).await
+}
+
 // TAURI COMMAND WRAPPERS
 
 #[tauri::command]
@@ -377,9 +2903,29 @@ pub async fn reorder_scenes(app: AppHandle, request: ReorderRequest) -> Result<(
 }
 
 #[tauri::command]
-pub async fn search_content(app: AppHandle, request: SearchRequest) -> Result<Vec<SearchResult>, String> {
-    search_content_impl(&app, request).await
-        .map_err(|e| e.to_string())
+pub async fn search_content(app: AppHandle, request: SearchRequest) -> Result<Vec<SearchResult>, String> {
+    search_content_impl(&app, request).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn preview_replace(app: AppHandle, request: ReplaceRequest) -> Result<Vec<ReplacePreview>, String> {
+    let pool = app.state::<DatabaseService>().pool(&app).await.map_err(|e| e.to_string())?;
+    preview_replacements(&pool, &request).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn replace_content(app: AppHandle, request: ReplaceRequest) -> Result<ReplaceOutcome, String> {
+    let db_service = app.state::<DatabaseService>();
+    let pool = db_service.pool(&app).await.map_err(|e| e.to_string())?;
+    let outcome = apply_replacements_to_scenes(&pool, &request, Utc::now().timestamp_millis()).await
+        .map_err(|e| e.to_string())?;
+
+    db_service.invalidate_cache("scenes").await;
+    db_service.invalidate_cache("manuscripts").await;
+
+    Ok(outcome)
 }
 
 #[tauri::command]
@@ -424,4 +2970,1356 @@ pub async fn get_scene_content(app: AppHandle, scene_id: String) -> Result<Optio
 pub async fn clear_all_dirty_flags(app: AppHandle) -> Result<(), String> {
     clear_all_dirty_flags_impl(&app).await
         .map_err(|e| e.to_string())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    // Single manuscript mode tables that the SQLx code paths touch
+    const TEST_SCHEMA: &str = r#"
+        CREATE TABLE manuscripts (
+            id TEXT PRIMARY KEY,
+            title TEXT NOT NULL,
+            author TEXT,
+            genre TEXT,
+            target_audience TEXT,
+            comp_titles TEXT,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL,
+            total_word_count INTEGER DEFAULT 0,
+            opening_strength_score INTEGER,
+            hook_effectiveness INTEGER
+        );
+        CREATE TABLE scenes (
+            id TEXT PRIMARY KEY,
+            chapter_number INTEGER,
+            scene_number_in_chapter INTEGER,
+            index_in_manuscript INTEGER NOT NULL,
+            title TEXT,
+            raw_text TEXT NOT NULL,
+            word_count INTEGER DEFAULT 0,
+            is_opening BOOLEAN DEFAULT 0,
+            is_chapter_end BOOLEAN DEFAULT 0,
+            opens_with_hook BOOLEAN DEFAULT 0,
+            ends_with_hook BOOLEAN DEFAULT 0,
+            pov_character TEXT,
+            location TEXT,
+            time_marker TEXT,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        );
+        CREATE TRIGGER manuscripts_single_record_insert
+            BEFORE INSERT ON manuscripts
+            WHEN (SELECT COUNT(*) FROM manuscripts) >= 1
+        BEGIN
+            SELECT RAISE(FAIL, 'Only one manuscript record allowed');
+        END;
+    "#;
+
+    // In-memory database with the test schema. A single connection that never
+    // expires, since every connection would otherwise get its own database.
+    pub(crate) async fn memory_pool() -> SqlitePool {
+        let options = "sqlite::memory:"
+            .parse::<SqliteConnectOptions>()
+            .unwrap()
+            .foreign_keys(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options)
+            .await
+            .unwrap();
+        sqlx::raw_sql(TEST_SCHEMA).execute(&pool).await.unwrap();
+        sqlx::raw_sql(include_str!("../migrations/008_manuscript_cascade.sql"))
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::raw_sql(include_str!("../migrations/009_story_beats.sql"))
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::raw_sql(include_str!("../migrations/010_plants.sql"))
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::raw_sql(include_str!("../migrations/011_events.sql"))
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::raw_sql(include_str!("../migrations/012_world_state.sql"))
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::raw_sql(include_str!("../migrations/013_comments.sql"))
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::raw_sql(include_str!("../migrations/014_notes.sql"))
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::raw_sql(include_str!("../migrations/015_scene_tags.sql"))
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::raw_sql(include_str!("../migrations/016_word_goals.sql"))
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::raw_sql(include_str!("../migrations/017_module_status.sql"))
+            .execute(&pool)
+            .await
+            .unwrap();
+        pool
+    }
+
+    pub(crate) fn manuscript(title: &str) -> Manuscript {
+        Manuscript {
+            id: SINGLETON_MANUSCRIPT_ID.to_string(),
+            title: title.to_string(),
+            author: None,
+            genre: None,
+            target_audience: None,
+            comp_titles: None,
+            created_at: 1,
+            updated_at: 1,
+            total_word_count: 0,
+            opening_strength_score: None,
+            hook_effectiveness: None,
+        }
+    }
+
+    async fn count(pool: &SqlitePool, table: &str) -> i64 {
+        sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", table))
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_replace_manuscript_swaps_singleton_and_scenes() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            let mut scene = crate::analysis::tests::scene(0, Some(1), "<p>Old.</p>");
+            scene.id = "old-scene".to_string();
+            replace_manuscript_with_scenes(&pool, &manuscript("Old"), &[scene]).await.unwrap();
+
+            let scenes: Vec<Scene> = (0..3)
+                .map(|i| crate::analysis::tests::scene(i, Some(1), "<p>New.</p>"))
+                .collect();
+            replace_manuscript_with_scenes(&pool, &manuscript("New"), &scenes).await.unwrap();
+
+            assert_eq!(count(&pool, "manuscripts").await, 1);
+            assert_eq!(count(&pool, "scenes").await, 3);
+            let title: String = sqlx::query_scalar("SELECT title FROM manuscripts")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(title, "New");
+        });
+    }
+
+    #[test]
+    fn test_append_scenes_continues_indices_and_rolls_back_on_failure() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            let mut first = vec![crate::analysis::tests::scene(0, None, "<p>One.</p>")];
+            append_scenes(&pool, &mut first).await.unwrap();
+
+            let mut batch: Vec<Scene> = (10..13)
+                .map(|i| crate::analysis::tests::scene(i, None, "<p>More.</p>"))
+                .collect();
+            append_scenes(&pool, &mut batch).await.unwrap();
+            let indices: Vec<u32> = batch.iter().map(|s| s.index_in_manuscript).collect();
+            assert_eq!(indices, vec![1, 2, 3]);
+
+            // The second scene reuses an existing id, so the first must not stick either
+            let mut failing = vec![
+                crate::analysis::tests::scene(20, None, "<p>New.</p>"),
+                crate::analysis::tests::scene(0, None, "<p>Duplicate.</p>"),
+            ];
+            assert!(append_scenes(&pool, &mut failing).await.is_err());
+            assert_eq!(count(&pool, "scenes").await, 4);
+        });
+    }
+
+    #[test]
+    fn test_delete_manuscript_leaves_no_orphans() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            let scenes: Vec<Scene> = (0..3)
+                .map(|i| crate::analysis::tests::scene(i, Some(1), "<p>Text.</p>"))
+                .collect();
+            replace_manuscript_with_scenes(&pool, &manuscript("Doomed"), &scenes).await.unwrap();
+            for scene in &scenes {
+                sqlx::query("INSERT INTO module_status (scene_id) VALUES (?)")
+                    .bind(&scene.id)
+                    .execute(&pool)
+                    .await
+                    .unwrap();
+            }
+
+            delete_manuscript(&pool).await.unwrap();
+
+            assert_eq!(count(&pool, "manuscripts").await, 0);
+            assert_eq!(count(&pool, "scenes").await, 0);
+            assert_eq!(count(&pool, "module_status").await, 0);
+        });
+    }
+
+    #[test]
+    fn test_deleting_manuscript_cascades_to_scenes() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            let scenes: Vec<Scene> = (0..2)
+                .map(|i| crate::analysis::tests::scene(i, None, "<p>Text.</p>"))
+                .collect();
+            replace_manuscript_with_scenes(&pool, &manuscript("Gone"), &scenes).await.unwrap();
+
+            let enabled: i64 = sqlx::query_scalar("PRAGMA foreign_keys").fetch_one(&pool).await.unwrap();
+            assert_eq!(enabled, 1);
+
+            sqlx::query("DELETE FROM manuscripts").execute(&pool).await.unwrap();
+            assert_eq!(count(&pool, "scenes").await, 0);
+        });
+    }
+
+    #[test]
+    fn test_concurrent_writes_wait_for_the_lock() {
+        tokio_test::block_on(async {
+            let temp = tempfile::tempdir().unwrap();
+            let config = DatabaseConfig { max_connections: 8, ..DatabaseConfig::default() };
+            let pool = open_pool(&temp.path().join("concurrent.db"), &config).await.unwrap();
+            sqlx::query("CREATE TABLE writes (writer INTEGER NOT NULL, n INTEGER NOT NULL)")
+                .execute(&pool)
+                .await
+                .unwrap();
+
+            let writers: Vec<_> = (0..16)
+                .map(|writer| {
+                    let pool = pool.clone();
+                    tokio::spawn(async move {
+                        for n in 0..10 {
+                            let mut tx = pool.begin().await?;
+                            sqlx::query("INSERT INTO writes (writer, n) VALUES (?, ?)")
+                                .bind(writer)
+                                .bind(n)
+                                .execute(&mut *tx)
+                                .await?;
+                            tx.commit().await?;
+                        }
+                        Ok::<(), sqlx::Error>(())
+                    })
+                })
+                .collect();
+
+            for writer in writers {
+                writer.await.unwrap().unwrap();
+            }
+            assert_eq!(count(&pool, "writes").await, 160);
+
+            let journal: String = sqlx::query_scalar("PRAGMA journal_mode").fetch_one(&pool).await.unwrap();
+            assert_eq!(journal.to_lowercase(), "wal");
+        });
+    }
+
+    fn cached_service(pool: SqlitePool, ttl: Duration) -> DatabaseService {
+        DatabaseService {
+            config: DatabaseConfig { cache_ttl: ttl, ..DatabaseConfig::default() },
+            ..DatabaseService::with_pool(pool)
+        }
+    }
+
+    #[test]
+    fn test_hook_flags_update_existing_scene_only() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            let scene = crate::analysis::tests::scene(0, Some(1), "<p>Hook.</p>");
+            replace_manuscript_with_scenes(&pool, &manuscript("Draft"), &[scene.clone()]).await.unwrap();
+
+            set_scene_hook_flags(&pool, &scene.id, true, false, 9).await.unwrap();
+            let flags: (bool, bool, i64) = sqlx::query_as("SELECT opens_with_hook, ends_with_hook, updated_at FROM scenes")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(flags, (true, false, 9));
+
+            let missing = set_scene_hook_flags(&pool, "no-such-scene", true, true, 9).await;
+            assert!(matches!(missing, Err(AppError::NotFound { .. })));
+        });
+    }
+
+    #[test]
+    fn test_repeated_database_failures_open_the_circuit() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            let service = DatabaseService {
+                breaker: CircuitBreaker::new(2, Duration::from_secs(60)),
+                ..DatabaseService::with_pool(pool)
+            };
+            let calls = std::sync::atomic::AtomicU32::new(0);
+            let failing = || {
+                calls.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                async { Err::<(), _>(AppError::database("database is locked")) }
+            };
+
+            // Each call retries per the database preset before counting once
+            for _ in 0..2 {
+                assert!(matches!(service.with_retry(failing).await, Err(AppError::Database { .. })));
+            }
+            let attempts = RetryConfig::database().max_attempts;
+            assert_eq!(calls.load(std::sync::atomic::Ordering::Relaxed), 2 * attempts);
+
+            let refused = service.with_retry(failing).await.unwrap_err();
+            assert_eq!(refused.code(), Some(crate::error::ErrorCode::CircuitOpen.as_str()));
+            assert_eq!(calls.load(std::sync::atomic::Ordering::Relaxed), 2 * attempts);
+
+            // Errors that aren't about the database's health don't trip it
+            let healthy = DatabaseService::with_pool(memory_pool().await);
+            for _ in 0..10 {
+                let result = healthy.with_retry(|| async { Err::<(), _>(AppError::not_found("Scene")) }).await;
+                assert!(matches!(result, Err(AppError::NotFound { .. })));
+            }
+            assert!(healthy.with_retry(|| async { Ok(()) }).await.is_ok());
+        });
+    }
+
+    #[test]
+    fn test_select_results_are_cached_within_ttl() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            let service = cached_service(pool.clone(), Duration::from_secs(60));
+            let query = "SELECT COUNT(*) AS total FROM scenes";
+
+            let first = service.execute_on(&pool, query, &[]).await.unwrap();
+            assert_eq!(first, serde_json::json!([{ "total": 0 }]));
+
+            // Written behind the service's back, so only the TTL can expire it
+            append_scenes(&pool, &mut [crate::analysis::tests::scene(0, None, "<p>x</p>")]).await.unwrap();
+            assert_eq!(service.execute_on(&pool, query, &[]).await.unwrap(), first);
+        });
+    }
+
+    #[test]
+    fn test_cached_select_expires_after_ttl() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            let service = cached_service(pool.clone(), Duration::from_millis(20));
+            let query = "SELECT COUNT(*) AS total FROM scenes";
+
+            service.execute_on(&pool, query, &[]).await.unwrap();
+            append_scenes(&pool, &mut [crate::analysis::tests::scene(0, None, "<p>x</p>")]).await.unwrap();
+            std::thread::sleep(Duration::from_millis(50));
+
+            let refreshed = service.execute_on(&pool, query, &[]).await.unwrap();
+            assert_eq!(refreshed, serde_json::json!([{ "total": 1 }]));
+        });
+    }
+
+    #[test]
+    fn test_writes_invalidate_cached_reads() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            let service = cached_service(pool.clone(), Duration::from_secs(60));
+            let select = "SELECT id, title, word_count FROM scenes WHERE id = ?";
+            let id = vec!["s1".to_string()];
+
+            assert_eq!(service.execute_on(&pool, select, &id).await.unwrap(), serde_json::json!([]));
+
+            let result = service.execute_on(
+                &pool,
+                "INSERT INTO scenes (id, index_in_manuscript, title, raw_text, word_count, created_at, updated_at) VALUES (?, 0, ?, 'Text', ?, 0, 0)",
+                &["s1".to_string(), "Opening".to_string(), "12".to_string()]
+            ).await.unwrap();
+            assert_eq!(result["rows_affected"], 1);
+
+            assert_eq!(
+                service.execute_on(&pool, select, &id).await.unwrap(),
+                serde_json::json!([{ "id": "s1", "title": "Opening", "word_count": 12 }])
+            );
+        });
+    }
+
+    #[test]
+    fn test_failed_replace_keeps_previous_manuscript() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            let scene = crate::analysis::tests::scene(0, None, "<p>Kept.</p>");
+            replace_manuscript_with_scenes(&pool, &manuscript("Kept"), &[scene.clone()]).await.unwrap();
+
+            // Duplicate scene ids violate the primary key part way through
+            let result = replace_manuscript_with_scenes(&pool, &manuscript("Lost"), &[scene.clone(), scene]).await;
+            assert!(matches!(result, Err(AppError::Database { .. })));
+
+            assert_eq!(count(&pool, "scenes").await, 1);
+            let title: String = sqlx::query_scalar("SELECT title FROM manuscripts")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(title, "Kept");
+        });
+    }
+
+    fn search_request(query: &str, regex: bool) -> SearchRequest {
+        SearchRequest {
+            query: query.to_string(),
+            case_sensitive: false,
+            whole_words: false,
+            regex,
+            wildcard: false,
+            context_chars: None,
+        }
+    }
+    
+    #[test]
+    fn test_invalid_search_regex_is_validation_error() {
+        let result = build_search_regex(&search_request("chapter (", true));
+        assert!(matches!(result, Err(AppError::Validation { .. })));
+    }
+    
+    #[test]
+    fn test_literal_search_escapes_metacharacters() {
+        let regex = build_search_regex(&search_request("chapter (", false)).unwrap();
+        assert!(regex.is_match("See CHAPTER ( one"));
+    }
+
+    async fn insert_texts(pool: &SqlitePool, texts: &[&str]) {
+        let mut scenes: Vec<Scene> = texts
+            .iter()
+            .enumerate()
+            .map(|(i, text)| crate::analysis::tests::scene(i as u32, None, text))
+            .collect();
+        append_scenes(pool, &mut scenes).await.unwrap();
+    }
+
+    #[test]
+    fn test_search_treats_like_wildcards_and_quotes_literally() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            insert_texts(&pool, &[
+                "<p>She was 100% sure it wasn't Mia's.</p>",
+                "<p>He was 1000 sure of nothing.</p>",
+            ]).await;
+
+            let results = search_scenes(&pool, &search_request("100% sure it wasn't", false)).await.unwrap();
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0].scene_id, "scene-0");
+            assert_eq!(results[0].matches[0].matched_text, "100% sure it wasn't");
+
+            assert!(search_scenes(&pool, &search_request("'; DROP TABLE scenes; --", false)).await.unwrap().is_empty());
+            assert!(search_scenes(&pool, &search_request("_", false)).await.unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn test_wildcard_search_is_opt_in() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            insert_texts(&pool, &["<p>One 100% day.</p>", "<p>One 1000 days.</p>"]).await;
+
+            let request = SearchRequest { wildcard: true, ..search_request("1_00", false) };
+            let results = search_scenes(&pool, &request).await.unwrap();
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0].scene_id, "scene-1");
+            assert_eq!(search_like_pattern(&search_request("1_0%", false)), "%1\\_0\\%%");
+        });
+    }
+
+    #[test]
+    fn test_search_offsets_point_into_stored_html() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            let html = "<p>Tom &amp; Jerry ran.</p><p>Then Tom hid.</p>";
+            insert_texts(&pool, &[html]).await;
+
+            let results = search_scenes(&pool, &search_request("tom & jerry", false)).await.unwrap();
+            let found = &results[0].matches[0];
+            let source: Vec<char> = html.chars().collect();
+            let stored: String = source[found.start_offset as usize..found.end_offset as usize].iter().collect();
+            assert_eq!(stored, "Tom &amp; Jerry");
+
+            let results = search_scenes(&pool, &search_request("hid", false)).await.unwrap();
+            assert_eq!(results[0].matches[0].line_number, 2);
+        });
+    }
+
+    #[test]
+    fn test_search_line_numbers_count_visible_lines() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            insert_texts(&pool, &[
+                "<p>First line.</p>\n<p>Second<br>third with Mara.</p>\n",
+                "Plain one\nplain two\nplain Mara",
+            ]).await;
+
+            let results = search_scenes(&pool, &search_request("mara", false)).await.unwrap();
+            for result in &results {
+                let found = &result.matches[0];
+                assert_eq!(found.line_number, 3, "{}", result.scene_id);
+            }
+
+            let found = &results[1].matches[0];
+            assert_eq!((found.text_start_offset, found.text_end_offset), (26, 30));
+        });
+    }
+
+    #[test]
+    fn test_search_context_respects_window_and_char_boundaries() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            insert_texts(&pool, &["<p>Café crème — naïve señor 日本語の文章 Mara 日本語の文章 über ünd ëlse.</p>"]).await;
+
+            let request = SearchRequest { context_chars: Some(5), ..search_request("mara", false) };
+            let results = search_scenes(&pool, &request).await.unwrap();
+            let found = &results[0].matches[0];
+            assert_eq!(found.context_before, "語の文章 ");
+            assert_eq!(found.context_before.chars().count(), 5);
+            assert_eq!(found.context_after, " 日本語の");
+            assert_eq!(found.context_after.chars().count(), 5);
+
+            let results = search_scenes(&pool, &search_request("mara", false)).await.unwrap();
+            assert_eq!(results[0].matches[0].context_before, "Café crème — naïve señor 日本語の文章 ");
+
+            let request = SearchRequest { context_chars: Some(usize::MAX), ..search_request("mara", false) };
+            assert_eq!(request.context_window(), MAX_SEARCH_CONTEXT_CHARS);
+        });
+    }
+
+    #[test]
+    fn test_missing_singleton_is_created_once() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            let service = DatabaseService::with_pool(pool.clone());
+
+            assert!(ensure_singleton_manuscript(&pool).await.unwrap());
+            assert!(!ensure_singleton_manuscript(&pool).await.unwrap());
+            assert_eq!(count(&pool, "manuscripts").await, 1);
+
+            let rows = service.execute_on(&pool, "SELECT id, title FROM manuscripts LIMIT 1", &[]).await.unwrap();
+            assert_eq!(rows, serde_json::json!([{
+                "id": SINGLETON_MANUSCRIPT_ID,
+                "title": DEFAULT_MANUSCRIPT_TITLE,
+            }]));
+        });
+    }
+
+    #[test]
+    fn test_existing_manuscript_is_left_alone() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            replace_manuscript_with_scenes(&pool, &manuscript("Mine"), &[]).await.unwrap();
+
+            assert!(!ensure_singleton_manuscript(&pool).await.unwrap());
+            let title: String = sqlx::query_scalar("SELECT title FROM manuscripts").fetch_one(&pool).await.unwrap();
+            assert_eq!(title, "Mine");
+        });
+    }
+
+    #[test]
+    fn test_typed_scene_mapping_matches_json_rows() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            let service = DatabaseService::with_pool(pool.clone());
+
+            let mut scene = crate::analysis::tests::scene(0, Some(3), "<p>Opening.</p>");
+            scene.title = Some("Arrival".to_string());
+            scene.is_chapter_end = true;
+            scene.pov_character = Some("Mia".to_string());
+            scene.word_count = 1;
+            replace_manuscript_with_scenes(&pool, &manuscript("Typed"), &[scene.clone()]).await.unwrap();
+
+            let rows = service.execute_on(&pool, SCENES_SELECT, &[]).await.unwrap();
+            let typed = rows_to_scenes(&rows).unwrap();
+            assert_eq!(typed.len(), 1);
+
+            let row = &rows[0];
+            assert_eq!(row["id"], typed[0].id.as_str());
+            assert_eq!(row["chapter_number"], 3);
+            assert_eq!(typed[0].chapter_number, Some(3));
+            assert_eq!(row["is_chapter_end"], 1);
+            assert!(typed[0].is_chapter_end);
+            assert!(row["location"].is_null());
+            assert_eq!(typed[0].location, None);
+            assert_eq!(serde_json::to_value(&typed[0]).unwrap(), serde_json::to_value(&scene).unwrap());
+        });
+    }
+
+    #[test]
+    fn test_typed_manuscript_mapping() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            let service = DatabaseService::with_pool(pool.clone());
+            assert!(rows_to_manuscript(&service.execute_on(&pool, MANUSCRIPT_SELECT, &[]).await.unwrap()).unwrap().is_none());
+
+            let mut expected = manuscript("Typed");
+            expected.author = Some("A. Writer".to_string());
+            expected.opening_strength_score = Some(72);
+            replace_manuscript_with_scenes(&pool, &expected, &[]).await.unwrap();
+            service.invalidate_cache("manuscripts").await;
+
+            let rows = service.execute_on(&pool, MANUSCRIPT_SELECT, &[]).await.unwrap();
+            let typed = rows_to_manuscript(&rows).unwrap().unwrap();
+            assert_eq!(rows[0]["opening_strength_score"], 72);
+            assert_eq!(serde_json::to_value(&typed).unwrap(), serde_json::to_value(&expected).unwrap());
+        });
+    }
+
+    #[test]
+    fn test_scene_writes_move_manuscript_total_by_delta() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            replace_manuscript_with_scenes(&pool, &manuscript("Draft"), &[]).await.unwrap();
+            async fn total(pool: &SqlitePool) -> i64 {
+                sqlx::query_scalar("SELECT total_word_count FROM manuscripts")
+                    .fetch_one(pool)
+                    .await
+                    .unwrap()
+            }
+
+            let mut scenes = vec![
+                crate::analysis::tests::scene(0, None, "<p>One two three.</p>"),
+                crate::analysis::tests::scene(1, None, "<p>Four five.</p>"),
+            ];
+            scenes[0].word_count = 3;
+            scenes[1].word_count = 2;
+            append_scenes(&pool, &mut scenes).await.unwrap();
+            assert_eq!(total(&pool).await, 5);
+
+            let count = update_scene_text(&pool, "scene-0", "<p>Just <em>one</em> more word.</p>", None, 10).await.unwrap();
+            assert_eq!(count, 4);
+            assert_eq!(total(&pool).await, 6);
+
+            let missing = update_scene_text(&pool, "no-such-scene", "<p>Ignored.</p>", None, 10).await;
+            assert!(matches!(missing, Err(AppError::NotFound { .. })));
+            assert_eq!(total(&pool).await, 6);
+
+            assert!(remove_scene(&pool, "scene-1").await.unwrap());
+            assert!(!remove_scene(&pool, "scene-1").await.unwrap());
+            assert_eq!(total(&pool).await, 4);
+        });
+    }
+
+    #[test]
+    fn test_replace_preview_matches_apply() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            replace_manuscript_with_scenes(&pool, &manuscript("Draft"), &[]).await.unwrap();
+            insert_texts(&pool, &[
+                "<p>Mara ran. Then mara hid.</p>",
+                "<p>Nothing here.</p>",
+                "<p>Ma<em>ra</em> waited &amp; Mara left.</p>",
+            ]).await;
+
+            let request = ReplaceRequest {
+                search: search_request("mara", false),
+                replacement: "Jo & Mae".to_string(),
+            };
+            let preview = preview_replacements(&pool, &request).await.unwrap();
+            let previewed: u32 = preview.iter().map(|p| p.total_matches).sum();
+            assert_eq!(previewed, 3);
+            assert_eq!(preview[0].matches[0].replacement_text, "Jo & Mae");
+            assert_eq!(count(&pool, "scenes").await, 3);
+            let unchanged: String = sqlx::query_scalar("SELECT raw_text FROM scenes WHERE id = 'scene-0'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(unchanged, "<p>Mara ran. Then mara hid.</p>");
+
+            let outcome = apply_replacements_to_scenes(&pool, &request, 5).await.unwrap();
+            assert_eq!(outcome.replacements, previewed);
+            assert_eq!(outcome.scenes_changed, 2);
+
+            let texts: Vec<String> = sqlx::query_scalar("SELECT raw_text FROM scenes ORDER BY index_in_manuscript")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+            assert_eq!(texts[0], "<p>Jo &amp; Mae ran. Then Jo &amp; Mae hid.</p>");
+            assert_eq!(texts[2], "<p>Ma<em>ra</em> waited &amp; Jo &amp; Mae left.</p>");
+            let total: i64 = sqlx::query_scalar("SELECT total_word_count FROM manuscripts").fetch_one(&pool).await.unwrap();
+            assert_eq!(total, 12);
+        });
+    }
+
+    #[test]
+    fn test_regex_replace_expands_groups() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            insert_texts(&pool, &["<p>Smith, John and Doe, Jane</p>"]).await;
+
+            let request = ReplaceRequest {
+                search: search_request(r"(\w+), (\w+)", true),
+                replacement: "$2 $1".to_string(),
+            };
+            let preview = preview_replacements(&pool, &request).await.unwrap();
+            let proposed: Vec<&str> = preview[0].matches.iter().map(|m| m.replacement_text.as_str()).collect();
+            assert_eq!(proposed, vec!["John Smith", "Jane Doe"]);
+
+            apply_replacements_to_scenes(&pool, &request, 5).await.unwrap();
+            let text: String = sqlx::query_scalar("SELECT raw_text FROM scenes").fetch_one(&pool).await.unwrap();
+            assert_eq!(text, "<p>John Smith and Jane Doe</p>");
+        });
+    }
+
+    #[test]
+    fn test_scene_writes_reject_stale_versions() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            insert_texts(&pool, &["<p>Original.</p>"]).await;
+
+            update_scene_text(&pool, "scene-0", "<p>First window.</p>", Some(0), 10).await.unwrap();
+
+            let stale = update_scene_text(&pool, "scene-0", "<p>Second window.</p>", Some(0), 20).await;
+            assert!(matches!(stale, Err(AppError::Conflict { .. })));
+            let stale = update_scene_title(&pool, "scene-0", "Renamed", Some(0), 20).await;
+            assert!(matches!(stale, Err(AppError::Conflict { .. })));
+
+            let stored: (String, Option<String>, i64) = sqlx::query_as("SELECT raw_text, title, updated_at FROM scenes WHERE id = 'scene-0'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(stored, ("<p>First window.</p>".to_string(), None, 10));
+
+            update_scene_title(&pool, "scene-0", "Renamed", Some(10), 30).await.unwrap();
+            update_scene_text(&pool, "scene-0", "<p>Reloaded.</p>", Some(30), 40).await.unwrap();
+        });
+    }
+
+    #[test]
+    fn test_scene_text_and_title_update_together() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            insert_texts(&pool, &["<p>Original.</p>"]).await;
+
+            update_scene_fields(&pool, "scene-0", Some("<p>Two words.</p>"), Some("Retitled"), Some(0), 10).await.unwrap();
+            let stored: (String, Option<String>, i64, i64) = sqlx::query_as("SELECT raw_text, title, word_count, updated_at FROM scenes WHERE id = 'scene-0'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(stored, ("<p>Two words.</p>".to_string(), Some("Retitled".to_string()), 2, 10));
+
+            // A stale version writes neither field
+            let stale = update_scene_fields(&pool, "scene-0", Some("<p>Lost.</p>"), Some("Lost"), Some(0), 20).await;
+            assert!(matches!(stale, Err(AppError::Conflict { .. })));
+            let title: Option<String> = sqlx::query_scalar("SELECT title FROM scenes WHERE id = 'scene-0'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(title.as_deref(), Some("Retitled"));
+
+            // Title only, against the version the combined write left
+            update_scene_fields(&pool, "scene-0", None, Some("Final"), Some(10), 30).await.unwrap();
+        });
+    }
+
+    #[test]
+    fn test_scene_beats_build_beat_map() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            insert_texts(&pool, &["Opening", "Middle", "End"]).await;
+
+            set_scene_beat(&pool, "scene-2", Some(BeatType::Climax), 5).await.unwrap();
+            set_scene_beat(&pool, "scene-0", Some(BeatType::Hook), 5).await.unwrap();
+            set_scene_beat(&pool, "scene-1", Some(BeatType::IncitingIncident), 5).await.unwrap();
+            set_scene_beat(&pool, "scene-1", Some(BeatType::Midpoint), 6).await.unwrap();
+
+            let beats: Vec<(String, BeatType)> = get_manuscript_beats(&pool).await.unwrap()
+                .into_iter()
+                .map(|beat| (beat.scene_id, beat.beat_type))
+                .collect();
+            assert_eq!(beats, vec![
+                ("scene-0".to_string(), BeatType::Hook),
+                ("scene-1".to_string(), BeatType::Midpoint),
+                ("scene-2".to_string(), BeatType::Climax),
+            ]);
+
+            let dirty: i64 = sqlx::query_scalar("SELECT beats_dirty FROM module_status WHERE scene_id = 'scene-1'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(dirty, 1);
+
+            set_scene_beat(&pool, "scene-0", None, 7).await.unwrap();
+            remove_scene(&pool, "scene-2").await.unwrap();
+            assert_eq!(get_manuscript_beats(&pool).await.unwrap().len(), 1);
+            assert_eq!(count(&pool, "story_beats").await, 1);
+
+            let missing = set_scene_beat(&pool, "nope", Some(BeatType::Hook), 8).await;
+            assert!(matches!(missing, Err(AppError::NotFound { .. })));
+        });
+    }
+
+    fn new_plant(scene_id: &str, start_offset: u32, end_offset: u32, description: &str) -> NewPlant {
+        NewPlant {
+            scene_id: scene_id.to_string(),
+            start_offset,
+            end_offset,
+            description: description.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_unpaid_plants_exclude_paid_setups() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            insert_texts(&pool, &["<p>The gun on the wall.</p>", "<p>A letter arrives.</p>", "<p>Bang.</p>"]).await;
+
+            let gun = create_plant(&pool, &new_plant("scene-0", 7, 10, "The gun"), 1).await.unwrap();
+            let letter = create_plant(&pool, &new_plant("scene-1", 5, 11, "The letter"), 1).await.unwrap();
+            set_plant_payoff(&pool, &gun.id, Some("scene-2"), 2).await.unwrap();
+
+            let unpaid: Vec<String> = get_unpaid_plants(&pool).await.unwrap().into_iter().map(|p| p.id).collect();
+            assert_eq!(unpaid, vec![letter.id.clone()]);
+
+            set_plant_payoff(&pool, &gun.id, None, 3).await.unwrap();
+            assert_eq!(get_unpaid_plants(&pool).await.unwrap().len(), 2);
+
+            let outside = create_plant(&pool, &new_plant("scene-2", 0, 99, "Too long"), 4).await;
+            assert!(matches!(outside, Err(AppError::Validation { .. })));
+            let missing = set_plant_payoff(&pool, "nope", Some("scene-2"), 4).await;
+            assert!(matches!(missing, Err(AppError::NotFound { .. })));
+        });
+    }
+
+    async fn plants_dirty(pool: &SqlitePool) -> i64 {
+        sqlx::query_scalar("SELECT plants_dirty FROM module_status WHERE scene_id = 'scene-0'")
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_edits_touching_a_plant_mark_plants_dirty() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            insert_texts(&pool, &["<p>The gun on the wall.</p><p>Later.</p>"]).await;
+            create_plant(&pool, &new_plant("scene-0", 7, 10, "The gun"), 1).await.unwrap();
+
+            assert_eq!(plants_dirty(&pool).await, 1);
+            sqlx::query("UPDATE module_status SET plants_dirty = 0").execute(&pool).await.unwrap();
+
+            update_scene_text(&pool, "scene-0", "<p>The gun on the wall.</p><p>Much later.</p>", None, 2).await.unwrap();
+            assert_eq!(plants_dirty(&pool).await, 0);
+
+            update_scene_text(&pool, "scene-0", "<p>The rifle on the wall.</p><p>Much later.</p>", None, 3).await.unwrap();
+            assert_eq!(plants_dirty(&pool).await, 1);
+        });
+    }
+
+    fn event(description: &str, characters: &[&str]) -> SceneEvent {
+        SceneEvent {
+            description: description.to_string(),
+            characters: characters.iter().map(|name| name.to_string()).collect(),
+            time_marker: None,
+        }
+    }
+
+    #[test]
+    fn test_events_come_back_in_story_order() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            insert_texts(&pool, &["<p>One.</p>", "<p>Two.</p>"]).await;
+
+            record_scene_events(&pool, "scene-1", &[event("Storm hits", &[]), event("Ship sinks", &["Ana"])], 1).await.unwrap();
+            record_scene_events(&pool, "scene-0", &[event("Ana boards", &["Ana"]), event("Ship sails", &[])], 1).await.unwrap();
+
+            let order = |events: Vec<ManuscriptEvent>| -> Vec<(String, u32, String)> {
+                events.into_iter().map(|e| (e.scene_id, e.sequence, e.event.description)).collect()
+            };
+            let events = get_manuscript_events(&pool).await.unwrap();
+            assert_eq!(events[3].event.characters, vec!["Ana".to_string()]);
+            assert_eq!(order(events), vec![
+                ("scene-0".to_string(), 0, "Ana boards".to_string()),
+                ("scene-0".to_string(), 1, "Ship sails".to_string()),
+                ("scene-1".to_string(), 0, "Storm hits".to_string()),
+                ("scene-1".to_string(), 1, "Ship sinks".to_string()),
+            ]);
+
+            // Re-recording replaces, and a dirty events module drops the stale events
+            record_scene_events(&pool, "scene-0", &[event("Ana stays ashore", &["Ana"])], 2).await.unwrap();
+            mark_scene_modules_dirty(&pool, "scene-1", &["events".to_string()]).await.unwrap();
+            assert_eq!(order(get_manuscript_events(&pool).await.unwrap()), vec![
+                ("scene-0".to_string(), 0, "Ana stays ashore".to_string()),
+            ]);
+
+            let unknown = mark_scene_modules_dirty(&pool, "scene-0", &["vibes".to_string()]).await;
+            assert!(matches!(unknown, Err(AppError::Validation { .. })));
+        });
+    }
+
+    #[test]
+    fn test_state_timeline_follows_manuscript_order() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            insert_texts(&pool, &["<p>One.</p>", "<p>Two.</p>", "<p>Three.</p>"]).await;
+
+            set_state_at_scene(&pool, "scene-2", "sword.location", Some("the lake"), 1).await.unwrap();
+            set_state_at_scene(&pool, "scene-0", " sword.location ", Some("the forge"), 1).await.unwrap();
+            set_state_at_scene(&pool, "scene-1", "Ana.injury", Some("broken arm"), 1).await.unwrap();
+            set_state_at_scene(&pool, "scene-1", "sword.location", Some("Ana's belt"), 1).await.unwrap();
+            set_state_at_scene(&pool, "scene-1", "sword.location", None, 2).await.unwrap();
+
+            let timeline: Vec<(String, String, Option<String>)> = get_state_timeline(&pool, "sword.location").await.unwrap()
+                .into_iter()
+                .map(|change| (change.scene_id, change.value, change.previous_value))
+                .collect();
+            assert_eq!(timeline, vec![
+                ("scene-0".to_string(), "the forge".to_string(), None),
+                ("scene-2".to_string(), "the lake".to_string(), Some("the forge".to_string())),
+            ]);
+
+            let dirty: i64 = sqlx::query_scalar("SELECT state_dirty FROM module_status WHERE scene_id = 'scene-1'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(dirty, 1);
+            assert!(matches!(get_state_timeline(&pool, " ").await, Err(AppError::Validation { .. })));
+        });
+    }
+
+    #[test]
+    fn test_recompute_clears_dirty_modules() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            insert_texts(&pool, &["<p>Ana boards the ship. It rains.</p><p>The ship sails.</p>", "<p>Calm seas.</p>"]).await;
+            sqlx::query("INSERT INTO module_status (scene_id, events_dirty, plants_dirty, state_dirty, beats_dirty) VALUES ('scene-0', 0, 0, 0, 0)")
+                .execute(&pool)
+                .await
+                .unwrap();
+
+            mark_scene_modules_dirty(&pool, "scene-0", &["events".to_string(), "beats".to_string()]).await.unwrap();
+            create_plant(&pool, &new_plant("scene-1", 3, 7, "Calm"), 1).await.unwrap();
+            assert_eq!(dirty_scene_ids(&pool).await.unwrap(), vec!["scene-0", "scene-1"]);
+
+            let recomputed = recompute_dirty_modules(&pool, 5).await.unwrap();
+            assert_eq!(recomputed[0].modules, vec!["events", "beats"]);
+            assert_eq!(recomputed[1].modules, vec!["events", "plants", "state", "beats"]);
+            assert!(dirty_scene_ids(&pool).await.unwrap().is_empty());
+
+            let (events_v, plants_v): (Option<String>, Option<String>) = sqlx::query_as(
+                "SELECT events_v, plants_v FROM module_status WHERE scene_id = 'scene-0'"
+            )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            assert_eq!(events_v.as_deref(), Some("local-1@0"));
+            assert_eq!(plants_v, None);
+
+            let descriptions: Vec<String> = get_manuscript_events(&pool).await.unwrap()
+                .into_iter()
+                .map(|e| e.event.description)
+                .collect();
+            assert_eq!(descriptions, vec!["Ana boards the ship.", "The ship sails.", "Calm seas."]);
+        });
+    }
+
+    #[test]
+    fn test_module_status_is_updated_cleared_and_dropped_with_scene() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            insert_texts(&pool, &["<p>One.</p>", "<p>Two.</p>"]).await;
+            assert!(scene_module_status(&pool, "scene-0").await.unwrap().is_none());
+
+            let request = |scene_id: &str, module: &str, dirty| UpdateModuleStatusRequest {
+                scene_id: scene_id.to_string(),
+                module: module.to_string(),
+                version: "v2".to_string(),
+                dirty,
+            };
+            set_scene_module_status(&pool, &request("scene-0", "plants", false)).await.unwrap();
+            set_scene_module_status(&pool, &request("scene-1", "events", true)).await.unwrap();
+
+            let status = scene_module_status(&pool, "scene-0").await.unwrap().unwrap();
+            assert_eq!(status.plants_v.as_deref(), Some("v2"));
+            assert_eq!((status.events_dirty, status.plants_dirty), (1, 0));
+            assert!(!status.last_processed.is_empty());
+
+            assert!(matches!(
+                set_scene_module_status(&pool, &request("scene-0", "tone", true)).await,
+                Err(AppError::Validation { .. })
+            ));
+            assert!(matches!(
+                set_scene_module_status(&pool, &request("missing", "events", true)).await,
+                Err(AppError::NotFound { .. })
+            ));
+
+            clear_module_dirty_flags(&pool).await.unwrap();
+            assert!(dirty_scene_ids(&pool).await.unwrap().is_empty());
+
+            sqlx::query("DELETE FROM scenes WHERE id = 'scene-1'").execute(&pool).await.unwrap();
+            assert!(scene_module_status(&pool, "scene-1").await.unwrap().is_none());
+        });
+    }
+
+    #[test]
+    fn test_comments_are_listed_by_position_and_deleted() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            insert_texts(&pool, &["<p>The gun on the wall.</p>", "<p>Later.</p>"]).await;
+
+            let late = add_comment(&pool, "scene-0", 20, "Is this too obvious?", Some("Jo"), 1).await.unwrap();
+            let early = add_comment(&pool, "scene-0", 7, "Which gun?", None, 2).await.unwrap();
+            add_comment(&pool, "scene-1", 0, "Cut this scene", Some(" "), 3).await.unwrap();
+
+            let listed = get_scene_comments(&pool, "scene-0").await.unwrap();
+            assert_eq!(listed, vec![early.clone(), late.clone()]);
+            assert_eq!(listed[1].author.as_deref(), Some("Jo"));
+            assert_eq!(get_all_comments(&pool).await.unwrap()[2].author, None);
+
+            assert!(delete_comment(&pool, &early.id).await.unwrap());
+            assert!(!delete_comment(&pool, &early.id).await.unwrap());
+            assert_eq!(get_scene_comments(&pool, "scene-0").await.unwrap(), vec![late]);
+
+            let past_end = add_comment(&pool, "scene-1", 99, "Too far", None, 4).await;
+            assert!(matches!(past_end, Err(AppError::Validation { .. })));
+        });
+    }
+
+    #[test]
+    fn test_notes_crud_is_scoped_to_manuscript() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+
+            let first = create_note(&pool, SINGLETON_MANUSCRIPT_ID, " Timeline ", "Day 1: arrival", 1).await.unwrap();
+            let second = create_note(&pool, SINGLETON_MANUSCRIPT_ID, "Names", "", 2).await.unwrap();
+            let other = create_note(&pool, "other-manuscript", "Elsewhere", "Not ours", 3).await.unwrap();
+            assert_eq!(first.title, "Timeline");
+
+            let listed = list_notes(&pool, SINGLETON_MANUSCRIPT_ID).await.unwrap();
+            assert_eq!(listed, vec![second.clone(), first.clone()]);
+
+            let updated = update_note(&pool, SINGLETON_MANUSCRIPT_ID, &first.id, "Timeline", "Day 2: storm", 4).await.unwrap();
+            assert_eq!((updated.body.as_str(), updated.created_at, updated.updated_at), ("Day 2: storm", 1, 4));
+            assert_eq!(list_notes(&pool, SINGLETON_MANUSCRIPT_ID).await.unwrap()[0].id, first.id);
+
+            let cross = update_note(&pool, SINGLETON_MANUSCRIPT_ID, &other.id, "Stolen", "", 5).await;
+            assert!(matches!(cross, Err(AppError::NotFound { .. })));
+            assert!(!delete_note(&pool, SINGLETON_MANUSCRIPT_ID, &other.id).await.unwrap());
+            assert_eq!(list_notes(&pool, "other-manuscript").await.unwrap(), vec![other]);
+
+            let untitled = update_note(&pool, SINGLETON_MANUSCRIPT_ID, &second.id, "  ", "", 6).await;
+            assert!(matches!(untitled, Err(AppError::Validation { .. })));
+
+            assert!(delete_note(&pool, SINGLETON_MANUSCRIPT_ID, &second.id).await.unwrap());
+            assert_eq!(list_notes(&pool, SINGLETON_MANUSCRIPT_ID).await.unwrap(), vec![updated]);
+        });
+    }
+
+    #[test]
+    fn test_scene_tags_filter_scenes() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            insert_texts(&pool, &["<p>One.</p>", "<p>Two.</p>", "<p>Three.</p>"]).await;
+
+            add_scene_tag(&pool, "scene-2", "Flashback", 1).await.unwrap();
+            add_scene_tag(&pool, "scene-0", " flashback ", 2).await.unwrap();
+            add_scene_tag(&pool, "scene-0", "needs   revision", 3).await.unwrap();
+            add_scene_tag(&pool, "scene-0", "FLASHBACK", 4).await.unwrap();
+            assert_eq!(count(&pool, "scene_tags").await, 3);
+
+            let flashbacks: Vec<String> = get_scenes_by_tag(&pool, "flashback").await.unwrap()
+                .into_iter()
+                .map(|scene| scene.scene_id)
+                .collect();
+            assert_eq!(flashbacks, vec!["scene-0", "scene-2"]);
+
+            let tags = get_all_scene_tags(&pool).await.unwrap();
+            assert_eq!(tags["scene-0"], vec!["flashback", "needs revision"]);
+            assert!(!tags.contains_key("scene-1"));
+
+            assert!(remove_scene_tag(&pool, "scene-0", "Flashback").await.unwrap());
+            assert!(!remove_scene_tag(&pool, "scene-0", "Flashback").await.unwrap());
+            assert_eq!(get_scenes_by_tag(&pool, "flashback").await.unwrap().len(), 1);
+
+            let missing = add_scene_tag(&pool, "scene-9", "flashback", 5).await;
+            assert!(matches!(missing, Err(AppError::NotFound { .. })));
+            let blank = add_scene_tag(&pool, "scene-1", "  ", 5).await;
+            assert!(matches!(blank, Err(AppError::Validation { .. })));
+        });
+    }
+
+    #[test]
+    fn test_reindex_repairs_corrupted_indices_and_counts() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            replace_manuscript_with_scenes(&pool, &manuscript("Draft"), &[]).await.unwrap();
+            let mut scenes = vec![
+                crate::analysis::tests::scene(0, None, "<p>A prologue.</p>"),
+                crate::analysis::tests::scene(1, Some(3), "<p>One two three.</p>"),
+                crate::analysis::tests::scene(2, Some(3), "<p>Four five.</p>"),
+                crate::analysis::tests::scene(3, Some(7), "<p>Six.</p>"),
+            ];
+            for scene in &mut scenes {
+                scene.word_count = crate::text::word_count(&scene.raw_text, true);
+            }
+            append_scenes(&pool, &mut scenes).await.unwrap();
+
+            sqlx::raw_sql(
+                "UPDATE scenes SET index_in_manuscript = index_in_manuscript * 10 + 5;
+                 UPDATE scenes SET word_count = 99 WHERE id = 'scene-1';
+                 UPDATE manuscripts SET total_word_count = 1234;"
+            )
+            .execute(&pool)
+            .await
+            .unwrap();
+
+            let summary = reindex_manuscript(&pool).await.unwrap();
+            assert_eq!(summary, ReindexSummary {
+                scenes: 4,
+                indices_changed: 4,
+                word_counts_changed: 1,
+                chapter_numbers_changed: 3,
+                previous_total_word_count: 1234,
+                total_word_count: 8,
+            });
+
+            let rows: Vec<(String, i64, i64, Option<i64>, Option<i64>, bool)> = sqlx::query_as(
+                "SELECT id, index_in_manuscript, word_count, chapter_number, scene_number_in_chapter, is_chapter_end FROM scenes ORDER BY index_in_manuscript"
+            )
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+            assert_eq!(rows, vec![
+                ("scene-0".to_string(), 0, 2, None, None, false),
+                ("scene-1".to_string(), 1, 3, Some(1), Some(1), false),
+                ("scene-2".to_string(), 2, 2, Some(1), Some(2), true),
+                ("scene-3".to_string(), 3, 1, Some(2), Some(1), true),
+            ]);
+            let total: i64 = sqlx::query_scalar("SELECT total_word_count FROM manuscripts").fetch_one(&pool).await.unwrap();
+            assert_eq!(total, 8);
+
+            let again = reindex_manuscript(&pool).await.unwrap();
+            assert_eq!((again.indices_changed, again.word_counts_changed, again.chapter_numbers_changed), (0, 0, 0));
+        });
+    }
+
+    #[test]
+    fn test_vacuum_reclaims_deleted_pages() {
+        tokio_test::block_on(async {
+            let temp = tempfile::tempdir().unwrap();
+            let pool = open_pool(&temp.path().join("vacuum.db"), &DatabaseConfig::default()).await.unwrap();
+            sqlx::raw_sql(
+                "CREATE TABLE revisions (id INTEGER PRIMARY KEY, body BLOB);
+                 WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 200)
+                 INSERT INTO revisions (body) SELECT randomblob(4000) FROM n;
+                 DELETE FROM revisions;"
+            )
+            .execute(&pool)
+            .await
+            .unwrap();
+
+            let size = get_database_size(&pool).await.unwrap();
+            assert_eq!(size.size_bytes, size.page_size * size.page_count);
+            assert!(size.free_pages > 100);
+
+            let result = vacuum_database(&pool).await.unwrap();
+            assert_eq!(result.before, size);
+            assert_eq!(result.after.free_pages, 0);
+            assert!(result.after.size_bytes > 0);
+            assert!(result.after.size_bytes < result.before.size_bytes / 10);
+            assert_eq!(get_database_size(&pool).await.unwrap(), result.after);
+        });
+    }
+
+    #[test]
+    fn test_integrity_check_reports_ok_and_orphans() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            insert_texts(&pool, &["<p>Fine.</p>"]).await;
+            add_comment(&pool, "scene-0", 0, "Keep", None, 1).await.unwrap();
+
+            let report = check_database_integrity(&pool).await.unwrap();
+            assert_eq!(report, IntegrityReport { ok: true, integrity_errors: Vec::new(), foreign_key_violations: Vec::new() });
+
+            // The test pool has a single connection, so the pragma sticks
+            sqlx::query("PRAGMA foreign_keys = OFF").execute(&pool).await.unwrap();
+            sqlx::query("INSERT INTO comments (id, scene_id, position, text, created_at) VALUES ('orphan', 'gone', 0, 'Lost', 1)")
+                .execute(&pool)
+                .await
+                .unwrap();
+
+            let report = check_database_integrity(&pool).await.unwrap();
+            assert!(!report.ok);
+            assert!(report.integrity_errors.is_empty());
+            assert_eq!(report.foreign_key_violations.len(), 1);
+            assert_eq!((report.foreign_key_violations[0].table.as_str(), report.foreign_key_violations[0].parent.as_str()), ("comments", "scenes"));
+        });
+    }
+
+    #[test]
+    fn test_project_and_daily_goal_progress() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            replace_manuscript_with_scenes(&pool, &manuscript("Draft"), &[]).await.unwrap();
+            // Written on earlier days, so it doesn't count toward today
+            sqlx::query("UPDATE manuscripts SET total_word_count = 10").execute(&pool).await.unwrap();
+            async fn write(pool: &SqlitePool, index: u32, words: u32) {
+                let mut scene = crate::analysis::tests::scene(index, None, "<p>Words.</p>");
+                scene.word_count = words;
+                append_scenes(pool, &mut [scene]).await.unwrap();
+            }
+
+            set_word_goal(&pool, SINGLETON_MANUSCRIPT_ID, GoalKind::Project, 20, 1).await.unwrap();
+            set_word_goal(&pool, SINGLETON_MANUSCRIPT_ID, GoalKind::Daily, 5, 1).await.unwrap();
+            write(&pool, 0, 6).await;
+
+            let (progress, reached) = refresh_goal_progress(&pool, SINGLETON_MANUSCRIPT_ID).await.unwrap();
+            assert_eq!((progress.total_word_count, progress.words_today), (16, 6));
+            assert_eq!(progress.project, Some(GoalStatus { target: 20, words: 16, percent: 80.0, reached: false }));
+            assert_eq!(progress.daily, Some(GoalStatus { target: 5, words: 6, percent: 120.0, reached: true }));
+            assert_eq!(reached, vec![GoalKind::Daily]);
+
+            let (_, reached) = refresh_goal_progress(&pool, SINGLETON_MANUSCRIPT_ID).await.unwrap();
+            assert!(reached.is_empty());
+
+            write(&pool, 1, 4).await;
+            let (progress, reached) = refresh_goal_progress(&pool, SINGLETON_MANUSCRIPT_ID).await.unwrap();
+            assert_eq!(progress.project.unwrap().percent, 100.0);
+            assert_eq!(reached, vec![GoalKind::Project]);
+
+            set_word_goal(&pool, SINGLETON_MANUSCRIPT_ID, GoalKind::Daily, 0, 2).await.unwrap();
+            let (progress, _) = refresh_goal_progress(&pool, SINGLETON_MANUSCRIPT_ID).await.unwrap();
+            assert_eq!((progress.daily, progress.words_today), (None, 10));
+        });
+    }
+
+    #[test]
+    fn test_duplicate_manuscript_copies_scenes_with_new_ids() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            replace_manuscript_with_scenes(&pool, &manuscript("Draft"), &[]).await.unwrap();
+            insert_texts(&pool, &["<p>The gun on the wall.</p>", "<p>It fired.</p>"]).await;
+            add_comment(&pool, "scene-1", 3, "Earned?", None, 1).await.unwrap();
+            add_scene_tag(&pool, "scene-0", "setup", 1).await.unwrap();
+            create_note(&pool, SINGLETON_MANUSCRIPT_ID, "Timeline", "Day 1", 1).await.unwrap();
+
+            let temp = tempfile::tempdir().unwrap();
+            let destination = temp.path().join("branch.db");
+            let copy = duplicate_manuscript(&pool, &destination, " Draft (revision) ", 9).await.unwrap();
+            assert_ne!(copy.manuscript_id, SINGLETON_MANUSCRIPT_ID);
+            assert_eq!((copy.title.as_str(), copy.scene_count), ("Draft (revision)", 2));
+
+            let branch = open_pool(&destination, &DatabaseConfig::default()).await.unwrap();
+            let copied: Vec<(String, String)> = sqlx::query_as("SELECT id, raw_text FROM scenes ORDER BY index_in_manuscript")
+                .fetch_all(&branch)
+                .await
+                .unwrap();
+            assert_eq!(copied.len(), 2);
+            assert!(copied.iter().all(|(id, _)| !id.starts_with("scene-")));
+            assert_eq!(copied[1].1, "<p>It fired.</p>");
+
+            let comment_scene: String = sqlx::query_scalar("SELECT scene_id FROM comments").fetch_one(&branch).await.unwrap();
+            assert_eq!(comment_scene, copied[1].0);
+            assert_eq!(get_scenes_by_tag(&branch, "setup").await.unwrap()[0].scene_id, copied[0].0);
+            let (copy_id, title): (String, String) = sqlx::query_as("SELECT id, title FROM manuscripts").fetch_one(&branch).await.unwrap();
+            assert_eq!((copy_id.as_str(), title.as_str()), (copy.manuscript_id.as_str(), "Draft (revision)"));
+            assert_eq!(list_notes(&branch, &copy.manuscript_id).await.unwrap()[0].title, "Timeline");
+            assert!(check_database_integrity(&branch).await.unwrap().ok);
+
+            let (source_id, source_title): (String, String) = sqlx::query_as("SELECT id, title FROM manuscripts").fetch_one(&pool).await.unwrap();
+            assert_eq!((source_id.as_str(), source_title.as_str()), (SINGLETON_MANUSCRIPT_ID, "Draft"));
+            assert_eq!(list_notes(&pool, SINGLETON_MANUSCRIPT_ID).await.unwrap().len(), 1);
+            assert_eq!(get_scene_comments(&pool, "scene-1").await.unwrap().len(), 1);
+
+            let again = duplicate_manuscript(&pool, &destination, "Draft", 10).await;
+            assert!(matches!(again, Err(AppError::FileSystem { .. })));
+        });
+    }
+
+    #[test]
+    fn test_move_scene_between_chapters_updates_boundaries() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            let mut scenes = vec![
+                crate::analysis::tests::scene(0, Some(1), "<p>A.</p>"),
+                crate::analysis::tests::scene(1, Some(1), "<p>B.</p>"),
+                crate::analysis::tests::scene(2, Some(2), "<p>C.</p>"),
+                crate::analysis::tests::scene(3, Some(2), "<p>D.</p>"),
+            ];
+            append_scenes(&pool, &mut scenes).await.unwrap();
+            async fn layout(pool: &SqlitePool) -> Vec<(String, Option<i64>, Option<i64>, bool, bool)> {
+                sqlx::query_as(
+                    "SELECT id, chapter_number, scene_number_in_chapter, is_chapter_end, is_opening FROM scenes ORDER BY index_in_manuscript"
+                )
+                .fetch_all(pool)
+                .await
+                .unwrap()
+            }
+            let row = |id: &str, chapter: i64, number: i64, end: bool, opening: bool| {
+                (id.to_string(), Some(chapter), Some(number), end, opening)
+            };
+
+            move_scene_to_chapter(&pool, "scene-1", 2, 1).await.unwrap();
+            assert_eq!(layout(&pool).await, vec![
+                row("scene-0", 1, 1, true, true),
+                row("scene-2", 2, 1, false, false),
+                row("scene-1", 2, 2, false, false),
+                row("scene-3", 2, 3, true, false),
+            ]);
+
+            // Chapter 1 empties; the new chapter 3 sorts after chapter 2
+            move_scene_to_chapter(&pool, "scene-0", 3, 99).await.unwrap();
+            assert_eq!(layout(&pool).await, vec![
+                row("scene-2", 2, 1, false, true),
+                row("scene-1", 2, 2, false, false),
+                row("scene-3", 2, 3, true, false),
+                row("scene-0", 3, 1, true, false),
+            ]);
+            let indices: Vec<i64> = sqlx::query_scalar("SELECT index_in_manuscript FROM scenes ORDER BY index_in_manuscript")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+            assert_eq!(indices, vec![0, 1, 2, 3]);
+
+            let missing = move_scene_to_chapter(&pool, "scene-9", 1, 0).await;
+            assert!(matches!(missing, Err(AppError::NotFound { .. })));
+            let invalid = move_scene_to_chapter(&pool, "scene-0", 0, 0).await;
+            assert!(matches!(invalid, Err(AppError::Validation { .. })));
+        });
+    }
+
+    #[test]
+    fn test_reorder_and_rename_reject_scenes_outside_the_manuscript() {
+        tokio_test::block_on(async {
+            let pool = memory_pool().await;
+            insert_texts(&pool, &["<p>One.</p>", "<p>Two.</p>", "<p>Three.</p>"]).await;
+            let reorder = |manuscript_id: Option<&str>, scene_id: &str, new_index: u32| ReorderRequest {
+                manuscript_id: manuscript_id.map(str::to_string),
+                scene_id: scene_id.to_string(),
+                new_index,
+            };
+            let rename = |manuscript_id: Option<&str>, scene_id: &str| RenameRequest {
+                manuscript_id: manuscript_id.map(str::to_string),
+                scene_id: scene_id.to_string(),
+                new_title: "Renamed".to_string(),
+            };
+
+            let other_manuscript = reorder_scene_in(&pool, &reorder(Some("other-manuscript"), "scene-2", 0)).await;
+            assert!(matches!(other_manuscript, Err(AppError::NotFound { .. })));
+            let foreign_scene = reorder_scene_in(&pool, &reorder(Some(SINGLETON_MANUSCRIPT_ID), "scene-9", 0)).await;
+            assert!(matches!(foreign_scene, Err(AppError::NotFound { .. })));
+            let renamed_elsewhere = rename_scene_in(&pool, &rename(Some("other-manuscript"), "scene-0"), 5).await;
+            assert!(matches!(renamed_elsewhere, Err(AppError::NotFound { .. })));
+            let blank_id = rename_scene_in(&pool, &rename(None, ""), 5).await;
+            assert!(matches!(blank_id, Err(AppError::Validation { .. })));
+
+            let titles: Vec<Option<String>> = sqlx::query_scalar("SELECT title FROM scenes").fetch_all(&pool).await.unwrap();
+            assert!(titles.iter().all(Option::is_none));
+
+            reorder_scene_in(&pool, &reorder(Some(SINGLETON_MANUSCRIPT_ID), "scene-2", 0)).await.unwrap();
+            rename_scene_in(&pool, &rename(None, "scene-2"), 5).await.unwrap();
+            let rows: Vec<(String, Option<String>, bool)> = sqlx::query_as(
+                "SELECT id, title, is_opening FROM scenes ORDER BY index_in_manuscript"
+            )
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+            assert_eq!(rows, vec![
+                ("scene-2".to_string(), Some("Renamed".to_string()), true),
+                ("scene-0".to_string(), None, false),
+                ("scene-1".to_string(), None, false),
+            ]);
+        });
+    }
+}
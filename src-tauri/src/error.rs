@@ -1,9 +1,15 @@
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use chrono::{DateTime, Utc};
-use std::fs::OpenOptions;
-use std::io::Write;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 #[derive(Error, Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", content = "data")]
@@ -22,6 +28,8 @@ pub enum AppError {
         message: String, 
         path: Option<PathBuf>,
         operation: String,
+        #[serde(default)]
+        code: Option<String>,
         #[serde(with = "chrono::serde::ts_milliseconds")]
         timestamp: DateTime<Utc>,
     },
@@ -40,6 +48,8 @@ pub enum AppError {
         message: String, 
         field: Option<String>,
         value: Option<String>,
+        #[serde(default)]
+        code: Option<String>,
         #[serde(with = "chrono::serde::ts_milliseconds")]
         timestamp: DateTime<Utc>,
     },
@@ -120,11 +130,55 @@ pub enum AppError {
     },
 }
 
+// Stable codes carried by AppError (see AppError::code) so the frontend can
+// branch on them rather than on message text, which is written for people
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    DbError,
+    DbLocked,
+    DbConstraint,
+    DbRowNotFound,
+    DbPoolTimeout,
+    FileError,
+    FileNotFound,
+    PermissionDenied,
+    FileTooLarge,
+    UnsupportedFormat,
+    ValidationFailed,
+    ContentTooLarge,
+    Internal,
+    Panic,
+    CircuitOpen,
+}
+
+impl ErrorCode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::DbError => "DB_ERROR",
+            ErrorCode::DbLocked => "DB_LOCKED",
+            ErrorCode::DbConstraint => "DB_CONSTRAINT",
+            ErrorCode::DbRowNotFound => "DB_ROW_NOT_FOUND",
+            ErrorCode::DbPoolTimeout => "DB_POOL_TIMEOUT",
+            ErrorCode::FileError => "FILE_ERROR",
+            ErrorCode::FileNotFound => "FILE_NOT_FOUND",
+            ErrorCode::PermissionDenied => "PERMISSION_DENIED",
+            ErrorCode::FileTooLarge => "FILE_TOO_LARGE",
+            ErrorCode::UnsupportedFormat => "UNSUPPORTED_FORMAT",
+            ErrorCode::ValidationFailed => "VALIDATION_FAILED",
+            ErrorCode::ContentTooLarge => "CONTENT_TOO_LARGE",
+            ErrorCode::Internal => "INTERNAL",
+            ErrorCode::Panic => "PANIC",
+            ErrorCode::CircuitOpen => "CIRCUIT_OPEN",
+        }
+    }
+}
+
 impl AppError {
     pub fn database<S: Into<String>>(message: S) -> Self {
         Self::Database {
             message: message.into(),
-            code: None,
+            code: Some(ErrorCode::DbError.as_str().to_string()),
             query: None,
             timestamp: Utc::now(),
         }
@@ -133,7 +187,7 @@ impl AppError {
     pub fn database_with_query<S: Into<String>>(message: S, query: S) -> Self {
         Self::Database {
             message: message.into(),
-            code: None,
+            code: Some(ErrorCode::DbError.as_str().to_string()),
             query: Some(query.into()),
             timestamp: Utc::now(),
         }
@@ -144,6 +198,7 @@ impl AppError {
             message: message.into(),
             path: None,
             operation: operation.into(),
+            code: Some(ErrorCode::FileError.as_str().to_string()),
             timestamp: Utc::now(),
         }
     }
@@ -153,6 +208,7 @@ impl AppError {
             message: message.into(),
             path: Some(path),
             operation: operation.into(),
+            code: Some(ErrorCode::FileError.as_str().to_string()),
             timestamp: Utc::now(),
         }
     }
@@ -180,6 +236,7 @@ impl AppError {
             message: message.into(),
             field: None,
             value: None,
+            code: Some(ErrorCode::ValidationFailed.as_str().to_string()),
             timestamp: Utc::now(),
         }
     }
@@ -189,6 +246,7 @@ impl AppError {
             message: message.into(),
             field: Some(field.into()),
             value: Some(value.into()),
+            code: Some(ErrorCode::ValidationFailed.as_str().to_string()),
             timestamp: Utc::now(),
         }
     }
@@ -202,6 +260,14 @@ impl AppError {
         }
     }
     
+    pub fn configuration<S: Into<String>>(message: S, setting: S) -> Self {
+        Self::Configuration {
+            message: message.into(),
+            setting: Some(setting.into()),
+            timestamp: Utc::now(),
+        }
+    }
+
     pub fn not_found<S: Into<String>>(resource: S) -> Self {
         Self::NotFound {
             resource: resource.into(),
@@ -218,14 +284,46 @@ impl AppError {
         }
     }
     
+    pub fn conflict_with_id<S: Into<String>>(message: S, resource: S, existing_id: S) -> Self {
+        Self::Conflict {
+            message: message.into(),
+            resource: resource.into(),
+            existing_id: Some(existing_id.into()),
+            timestamp: Utc::now(),
+        }
+    }
+    
     pub fn internal<S: Into<String>>(message: S) -> Self {
         Self::Internal {
             message: message.into(),
-            error_code: None,
+            error_code: Some(ErrorCode::Internal.as_str().to_string()),
             timestamp: Utc::now(),
         }
     }
     
+    // Replace the variant's code with a more specific one; variants without a
+    // code field are returned unchanged
+    pub fn with_code(mut self, new_code: ErrorCode) -> Self {
+        match &mut self {
+            AppError::Database { code, .. }
+            | AppError::FileSystem { code, .. }
+            | AppError::Validation { code, .. }
+            | AppError::Internal { error_code: code, .. } => *code = Some(new_code.as_str().to_string()),
+            _ => {}
+        }
+        self
+    }
+    
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            AppError::Database { code, .. }
+            | AppError::FileSystem { code, .. }
+            | AppError::Validation { code, .. }
+            | AppError::Internal { error_code: code, .. } => code.as_deref(),
+            _ => None,
+        }
+    }
+    
     pub fn timeout<S: Into<String>>(message: S, timeout_ms: u64, operation: S) -> Self {
         Self::Timeout {
             message: message.into(),
@@ -265,41 +363,51 @@ impl AppError {
         }
     }
     
-    // Get user-friendly error message
-    pub fn user_message(&self) -> String {
+    // User-facing message as a translation key plus arguments, so the
+    // frontend can localize it
+    pub fn localized_message(&self) -> UserMessage {
         match self {
-            AppError::Database { .. } => {
-                "There was a problem accessing the database. Please try again.".to_string()
-            },
+            AppError::Database { .. } => UserMessage::new("error.database"),
             AppError::FileSystem { operation, .. } => {
-                format!("Unable to {} the file. Please check file permissions and try again.", operation)
-            },
-            AppError::Network { .. } => {
-                "Network connection problem. Please check your internet connection.".to_string()
+                UserMessage::new("error.file_system").arg("operation", operation)
             },
-            AppError::Validation { field, .. } => {
-                if let Some(field_name) = field {
-                    format!("Please check the {} field and try again.", field_name)
-                } else {
-                    "Please check your input and try again.".to_string()
-                }
-            },
-            AppError::Export { format, .. } => {
-                format!("Unable to export as {}. Please try a different format.", format)
-            },
-            AppError::NotFound { resource, .. } => {
-                format!("The {} could not be found.", resource)
+            AppError::Network { .. } => UserMessage::new("error.network"),
+            AppError::Validation { field, .. } => match field {
+                Some(field_name) => UserMessage::new("error.validation.field").arg("field", field_name),
+                None => UserMessage::new("error.validation"),
             },
+            AppError::Export { format, .. } => UserMessage::new("error.export").arg("format", format),
+            AppError::NotFound { resource, .. } => UserMessage::new("error.not_found").arg("resource", resource),
             AppError::Permission { required_permission, .. } => {
-                format!("Permission required: {}. Please check your access rights.", required_permission)
-            },
-            AppError::Timeout { operation, .. } => {
-                format!("The {} operation timed out. Please try again.", operation)
+                UserMessage::new("error.permission").arg("permission", required_permission)
             },
-            AppError::RateLimit { .. } => {
-                "Too many requests. Please wait a moment and try again.".to_string()
-            },
-            _ => "An unexpected error occurred. Please try again.".to_string(),
+            AppError::Timeout { operation, .. } => UserMessage::new("error.timeout").arg("operation", operation),
+            AppError::RateLimit { .. } => UserMessage::new("error.rate_limit"),
+            _ => UserMessage::new("error.unexpected"),
+        }
+    }
+    
+    // Get user-friendly error message, in English
+    pub fn user_message(&self) -> String {
+        self.localized_message().to_english()
+    }
+    
+    // Get the serialized variant tag (matches the `type` field in logged JSON)
+    pub fn error_type(&self) -> &'static str {
+        match self {
+            AppError::Database { .. } => "Database",
+            AppError::FileSystem { .. } => "FileSystem",
+            AppError::Network { .. } => "Network",
+            AppError::Validation { .. } => "Validation",
+            AppError::Export { .. } => "Export",
+            AppError::Window { .. } => "Window",
+            AppError::Permission { .. } => "Permission",
+            AppError::Configuration { .. } => "Configuration",
+            AppError::NotFound { .. } => "NotFound",
+            AppError::Conflict { .. } => "Conflict",
+            AppError::RateLimit { .. } => "RateLimit",
+            AppError::Timeout { .. } => "Timeout",
+            AppError::Internal { .. } => "Internal",
         }
     }
     
@@ -319,7 +427,56 @@ impl AppError {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// English text for each message key; `{name}` is replaced by the argument of
+// that name. Translations live in the frontend under the same keys.
+const ENGLISH_USER_MESSAGES: &[(&str, &str)] = &[
+    ("error.database", "There was a problem accessing the database. Please try again."),
+    ("error.file_system", "Unable to {operation} the file. Please check file permissions and try again."),
+    ("error.network", "Network connection problem. Please check your internet connection."),
+    ("error.validation", "Please check your input and try again."),
+    ("error.validation.field", "Please check the {field} field and try again."),
+    ("error.export", "Unable to export as {format}. Please try a different format."),
+    ("error.not_found", "The {resource} could not be found."),
+    ("error.permission", "Permission required: {permission}. Please check your access rights."),
+    ("error.timeout", "The {operation} operation timed out. Please try again."),
+    ("error.rate_limit", "Too many requests. Please wait a moment and try again."),
+    ("error.unexpected", "An unexpected error occurred. Please try again."),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserMessage {
+    pub key: String,
+    pub args: BTreeMap<String, String>,
+}
+
+impl UserMessage {
+    fn new(key: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            args: BTreeMap::new(),
+        }
+    }
+    
+    fn arg(mut self, name: &str, value: &str) -> Self {
+        self.args.insert(name.to_string(), value.to_string());
+        self
+    }
+    
+    pub fn to_english(&self) -> String {
+        // Unknown keys (e.g. from a newer log) fall back to the generic message
+        let template = ENGLISH_USER_MESSAGES
+            .iter()
+            .find(|(key, _)| *key == self.key)
+            .or_else(|| ENGLISH_USER_MESSAGES.iter().find(|(key, _)| *key == "error.unexpected"))
+            .map_or("", |(_, template)| *template);
+        
+        self.args.iter().fold(template.to_string(), |message, (name, value)| {
+            message.replace(&format!("{{{}}}", name), value)
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum ErrorSeverity {
     Low,
     Medium,
@@ -330,9 +487,18 @@ pub enum ErrorSeverity {
 // Enhanced Result type with error context
 pub type AppResult<T> = std::result::Result<T, AppError>;
 
+// Newest entries kept in memory while the log file can't be opened
+const FALLBACK_LOG_CAPACITY: usize = 500;
+
+type FallbackLog = Arc<Mutex<VecDeque<ErrorLogEntry>>>;
+
+// Shared by every default logger, since commands create a new one per call
+static DEFAULT_FALLBACK_LOG: OnceLock<FallbackLog> = OnceLock::new();
+
 // Error logger
 pub struct ErrorLogger {
     log_path: PathBuf,
+    fallback: FallbackLog,
 }
 
 impl ErrorLogger {
@@ -340,26 +506,60 @@ impl ErrorLogger {
         let mut log_path = std::env::temp_dir();
         log_path.push("narrative_surgeon_errors.log");
         
-        Self { log_path }
+        Self {
+            log_path,
+            fallback: DEFAULT_FALLBACK_LOG.get_or_init(FallbackLog::default).clone(),
+        }
     }
     
     pub fn with_path(log_path: PathBuf) -> Self {
-        Self { log_path }
+        Self {
+            log_path,
+            fallback: FallbackLog::default(),
+        }
+    }
+    
+    pub fn log_path(&self) -> &std::path::Path {
+        &self.log_path
     }
     
+    // An error identical to the previous entry (same error and context,
+    // ignoring when it happened) replaces that entry with a running count
+    // instead of adding a line, so a failure repeating every second doesn't
+    // flood the log. If the file can't be opened (an unwritable temp dir,
+    // say) the entry goes to an in-memory ring buffer instead, which the
+    // readers below merge back in.
     pub fn log_error(&self, error: &AppError, context: Option<&str>) -> Result<(), std::io::Error> {
-        let mut file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&self.log_path)?;
-            
-        let log_entry = ErrorLogEntry {
-            timestamp: Utc::now(),
+        let now = Utc::now();
+        let mut log_entry = ErrorLogEntry {
+            timestamp: now,
+            first_timestamp: Some(now),
+            occurrences: 1,
             error: error.clone(),
             context: context.map(|s| s.to_string()),
             severity: error.severity(),
         };
         
+        let mut file = match OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&self.log_path)
+        {
+            Ok(file) => file,
+            Err(_) => {
+                self.log_to_memory(log_entry);
+                return Ok(());
+            }
+        };
+        
+        if let Some((offset, previous)) = last_log_entry(&mut file)? {
+            if previous.is_repeat_of(&log_entry) {
+                log_entry.record_repeat_of(&previous);
+                file.set_len(offset)?;
+            }
+        }
+        
         writeln!(file, "{}", serde_json::to_string(&log_entry).unwrap_or_else(|_| {
             format!("[{}] ERROR: {}", log_entry.timestamp.format("%Y-%m-%d %H:%M:%S"), error)
         }))?;
@@ -367,40 +567,365 @@ impl ErrorLogger {
         Ok(())
     }
     
-    pub fn get_recent_errors(&self, limit: usize) -> Result<Vec<ErrorLogEntry>, std::io::Error> {
-        let content = std::fs::read_to_string(&self.log_path)?;
-        let lines: Vec<&str> = content.lines().rev().take(limit).collect();
-        
-        let mut errors = Vec::new();
-        for line in lines {
-            if let Ok(entry) = serde_json::from_str::<ErrorLogEntry>(line) {
-                errors.push(entry);
+    fn log_to_memory(&self, mut log_entry: ErrorLogEntry) {
+        let mut fallback = self.fallback.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(previous) = fallback.back() {
+            if previous.is_repeat_of(&log_entry) {
+                log_entry.record_repeat_of(previous);
+                fallback.pop_back();
             }
         }
+        if fallback.len() == FALLBACK_LOG_CAPACITY {
+            fallback.pop_front();
+        }
+        fallback.push_back(log_entry);
+    }
+    
+    // Every entry, oldest first. Entries held in memory are merged in by
+    // time; an unreadable file only counts as an error when there are none.
+    fn all_entries(&self) -> Result<Vec<ErrorLogEntry>, std::io::Error> {
+        let fallback: Vec<ErrorLogEntry> = self.fallback
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .cloned()
+            .collect();
+        
+        let mut errors: Vec<ErrorLogEntry> = match std::fs::read_to_string(&self.log_path) {
+            Ok(content) => content
+                .lines()
+                .filter_map(|line| serde_json::from_str::<ErrorLogEntry>(line).ok())
+                .collect(),
+            Err(_) if !fallback.is_empty() => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        
+        if !fallback.is_empty() {
+            errors.extend(fallback);
+            errors.sort_by_key(|entry| entry.timestamp);
+        }
+        Ok(errors)
+    }
+    
+    pub fn get_recent_errors(&self, limit: usize) -> Result<Vec<ErrorLogEntry>, std::io::Error> {
+        let mut errors = self.all_entries()?;
+        errors.drain(..errors.len().saturating_sub(limit));
+        Ok(errors)
+    }
+    
+    // Like get_recent_errors, but filters before applying the limit so the
+    // newest `limit` matching entries are returned
+    pub fn get_recent_errors_filtered(&self, limit: usize, filter: &ErrorLogFilter) -> Result<Vec<ErrorLogEntry>, std::io::Error> {
+        let mut errors: Vec<ErrorLogEntry> = self.all_entries()?
+            .into_iter()
+            .filter(|entry| filter.matches(entry))
+            .collect();
         
-        errors.reverse();
+        errors.drain(..errors.len().saturating_sub(limit));
         Ok(errors)
     }
     
+    // Aggregate over the whole log; a log that doesn't exist yet has no errors
+    pub fn error_statistics(&self) -> Result<ErrorStats, std::io::Error> {
+        match self.get_recent_errors(usize::MAX) {
+            Ok(entries) => Ok(ErrorStats::from_entries(&entries)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(ErrorStats::default()),
+            Err(e) => Err(e),
+        }
+    }
+    
     pub fn clear_logs(&self) -> Result<(), std::io::Error> {
+        self.fallback.lock().unwrap_or_else(|e| e.into_inner()).clear();
         std::fs::write(&self.log_path, "")?;
         Ok(())
     }
 }
 
+// Only the tail of the log is read when looking for the previous entry
+const LOG_TAIL_BYTES: u64 = 64 * 1024;
+
+// The last entry in the log and the byte offset its line starts at
+fn last_log_entry(file: &mut File) -> Result<Option<(u64, ErrorLogEntry)>, std::io::Error> {
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(LOG_TAIL_BYTES);
+    file.seek(SeekFrom::Start(start))?;
+    let mut tail = Vec::new();
+    file.read_to_end(&mut tail)?;
+    
+    let trimmed = tail.strip_suffix(b"\n").unwrap_or(&tail[..]);
+    let line_start = match trimmed.iter().rposition(|&b| b == b'\n') {
+        Some(newline) => newline + 1,
+        None if start == 0 => 0,
+        None => return Ok(None), // Line longer than the tail window
+    };
+    
+    Ok(serde_json::from_slice::<ErrorLogEntry>(&trimmed[line_start..])
+        .ok()
+        .map(|entry| (start + line_start as u64, entry)))
+}
+
+// Follows the error log, returning the entries written since the last poll.
+// log_error rewrites the last line in place when it coalesces a repeat, so the
+// last line read is remembered and reported again once its count changes.
+pub struct ErrorLogTail {
+    path: PathBuf,
+    last_line_start: u64,
+    last_line: Vec<u8>,
+}
+
+impl ErrorLogTail {
+    // Starts at the end of the log: only entries written from now on are returned
+    pub fn new(path: PathBuf) -> Self {
+        let mut tail = Self {
+            path,
+            last_line_start: 0,
+            last_line: Vec::new(),
+        };
+        let _ = tail.poll();
+        tail
+    }
+    
+    pub fn poll(&mut self) -> Result<Vec<ErrorLogEntry>, std::io::Error> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        
+        // A log shorter than where we were has been cleared
+        if file.metadata()?.len() < self.last_line_start {
+            self.last_line_start = 0;
+            self.last_line.clear();
+        }
+        
+        file.seek(SeekFrom::Start(self.last_line_start))?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+        
+        // Complete lines only; a line still being written is picked up next time
+        let mut lines = Vec::new();
+        let mut start = 0;
+        while let Some(newline) = bytes[start..].iter().position(|&b| b == b'\n') {
+            lines.push((start, &bytes[start..start + newline]));
+            start += newline + 1;
+        }
+        let Some(&(last_start, last_line)) = lines.last() else {
+            return Ok(Vec::new());
+        };
+        
+        let entries = lines
+            .iter()
+            .enumerate()
+            .filter(|(index, (_, line))| *index > 0 || self.last_line.is_empty() || *line != self.last_line.as_slice())
+            .filter_map(|(_, (_, line))| serde_json::from_slice::<ErrorLogEntry>(line).ok())
+            .collect();
+        
+        self.last_line_start += last_start as u64;
+        self.last_line = last_line.to_vec();
+        Ok(entries)
+    }
+}
+
+// Polls the error log on a background thread and passes new entries to
+// `on_entry` until dropped. Reads wait until the log's size has held for a
+// whole interval (up to a few intervals), so a burst of writes arrives together.
+pub struct ErrorLogWatcher {
+    stop: Arc<AtomicBool>,
+}
+
+const MAX_DEFERRED_POLLS: u32 = 4;
+
+impl ErrorLogWatcher {
+    pub fn spawn<F>(path: PathBuf, interval: Duration, mut on_entry: F) -> Self
+    where
+        F: FnMut(ErrorLogEntry) + Send + 'static,
+    {
+        let log_len = |path: &std::path::Path| std::fs::metadata(path).map(|metadata| metadata.len()).ok();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+        let mut tail = ErrorLogTail::new(path.clone());
+        let mut last_len = log_len(&path);
+        
+        std::thread::spawn(move || {
+            let mut deferred = 0;
+            while !stopped.load(Ordering::Relaxed) {
+                std::thread::sleep(interval);
+                
+                let len = log_len(&path);
+                if len != last_len && deferred < MAX_DEFERRED_POLLS {
+                    last_len = len;
+                    deferred += 1;
+                    continue;
+                }
+                
+                deferred = 0;
+                last_len = len;
+                if let Ok(entries) = tail.poll() {
+                    entries.into_iter().for_each(&mut on_entry);
+                }
+            }
+        });
+        
+        Self { stop }
+    }
+}
+
+impl Drop for ErrorLogWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorLogEntry {
     #[serde(with = "chrono::serde::ts_milliseconds")]
-    pub timestamp: DateTime<Utc>,
+    pub timestamp: DateTime<Utc>, // Latest occurrence
+    #[serde(default, with = "chrono::serde::ts_milliseconds_option")]
+    pub first_timestamp: Option<DateTime<Utc>>, // Missing in entries logged before deduplication
+    #[serde(default = "default_occurrences")]
+    pub occurrences: u32,
     pub error: AppError,
     pub context: Option<String>,
     pub severity: ErrorSeverity,
 }
 
+fn default_occurrences() -> u32 {
+    1
+}
+
+impl ErrorLogEntry {
+    pub fn first_seen(&self) -> DateTime<Utc> {
+        self.first_timestamp.unwrap_or(self.timestamp)
+    }
+    
+    // Same error and context, ignoring the error's own timestamp
+    fn is_repeat_of(&self, other: &ErrorLogEntry) -> bool {
+        let without_timestamp = |error: &AppError| {
+            let mut value = serde_json::to_value(error).ok()?;
+            value.get_mut("data")?.as_object_mut()?.remove("timestamp");
+            Some(value)
+        };
+        
+        self.context == other.context
+            && without_timestamp(&self.error).is_some()
+            && without_timestamp(&self.error) == without_timestamp(&other.error)
+    }
+    
+    // Fold an earlier identical entry into this one
+    fn record_repeat_of(&mut self, previous: &ErrorLogEntry) {
+        self.occurrences = previous.occurrences + 1;
+        self.first_timestamp = Some(previous.first_seen());
+    }
+}
+
+// Log panics as Internal errors, then run the previous hook as usual, so a
+// command that dies mid-operation still leaves a trace for post-mortems
+pub fn install_panic_hook(logger: ErrorLogger) {
+    let previous_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = info.payload()
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "non-string panic payload".to_string());
+        let location = info.location()
+            .map(|location| format!("{}:{}:{}", location.file(), location.line(), location.column()))
+            .unwrap_or_else(|| "unknown location".to_string());
+        
+        let error = AppError::Internal {
+            message: format!("Panic at {}: {}", location, message),
+            error_code: Some(ErrorCode::Panic.as_str().to_string()),
+            timestamp: Utc::now(),
+        };
+        let thread = std::thread::current();
+        let _ = logger.log_error(&error, Some(&format!("thread '{}'", thread.name().unwrap_or("unnamed"))));
+        
+        previous_hook(info);
+    }));
+}
+
+// Summary of an install's error log for support diagnostics. Counts include
+// repeats that were coalesced into a single entry.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ErrorStats {
+    pub total: u32,
+    pub by_type: BTreeMap<String, u32>,
+    pub by_severity: BTreeMap<ErrorSeverity, u32>,
+    pub most_recent_critical: Option<ErrorLogEntry>,
+}
+
+impl ErrorStats {
+    pub fn from_entries(entries: &[ErrorLogEntry]) -> Self {
+        let mut stats = ErrorStats::default();
+        for entry in entries {
+            stats.total += entry.occurrences;
+            *stats.by_type.entry(entry.error.error_type().to_string()).or_default() += entry.occurrences;
+            *stats.by_severity.entry(entry.severity).or_default() += entry.occurrences;
+            
+            let is_latest = match &stats.most_recent_critical {
+                Some(latest) => entry.timestamp >= latest.timestamp,
+                None => true,
+            };
+            if entry.severity == ErrorSeverity::Critical && is_latest {
+                stats.most_recent_critical = Some(entry.clone());
+            }
+        }
+        stats
+    }
+}
+
+// Filter criteria for querying the error log; all fields are optional and combined with AND
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ErrorLogFilter {
+    #[serde(default)]
+    pub min_severity: Option<ErrorSeverity>,
+    #[serde(default)]
+    pub error_type: Option<String>,
+    #[serde(default, with = "chrono::serde::ts_milliseconds_option")]
+    pub since: Option<DateTime<Utc>>,
+    #[serde(default, with = "chrono::serde::ts_milliseconds_option")]
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl ErrorLogFilter {
+    pub fn matches(&self, entry: &ErrorLogEntry) -> bool {
+        if let Some(min_severity) = self.min_severity {
+            if entry.severity < min_severity {
+                return false;
+            }
+        }
+        
+        if let Some(error_type) = &self.error_type {
+            if !entry.error.error_type().eq_ignore_ascii_case(error_type) {
+                return false;
+            }
+        }
+        
+        if let Some(since) = self.since {
+            if entry.timestamp < since {
+                return false;
+            }
+        }
+        
+        if let Some(until) = self.until {
+            if entry.timestamp > until {
+                return false;
+            }
+        }
+        
+        true
+    }
+}
+
 // Convert from common error types
 impl From<std::io::Error> for AppError {
     fn from(error: std::io::Error) -> Self {
-        AppError::file_system(error.to_string(), "file operation")
+        let code = match error.kind() {
+            std::io::ErrorKind::NotFound => ErrorCode::FileNotFound,
+            std::io::ErrorKind::PermissionDenied => ErrorCode::PermissionDenied,
+            _ => ErrorCode::FileError,
+        };
+        AppError::file_system(error.to_string(), "file operation").with_code(code)
     }
 }
 
@@ -410,18 +935,76 @@ impl From<serde_json::Error> for AppError {
     }
 }
 
+impl From<regex::Error> for AppError {
+    fn from(error: regex::Error) -> Self {
+        AppError::validation(format!("Invalid regular expression: {}", error))
+    }
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(error: sqlx::Error) -> Self {
+        // SQLite result codes; extended codes keep the primary code in the low byte
+        let sqlite_code = error
+            .as_database_error()
+            .and_then(|db_error| db_error.code())
+            .and_then(|code| code.parse::<i32>().ok())
+            .map(|code| code & 0xFF);
+        let code = match (&error, sqlite_code) {
+            (sqlx::Error::RowNotFound, _) => ErrorCode::DbRowNotFound,
+            (sqlx::Error::PoolTimedOut, _) => ErrorCode::DbPoolTimeout,
+            (_, Some(5 | 6)) => ErrorCode::DbLocked, // SQLITE_BUSY, SQLITE_LOCKED
+            (_, Some(19)) => ErrorCode::DbConstraint,
+            _ => ErrorCode::DbError,
+        };
+
+        AppError::Database {
+            message: error.to_string(),
+            code: Some(code.as_str().to_string()),
+            query: None,
+            timestamp: Utc::now(),
+        }
+    }
+}
+
 impl From<tauri_plugin_sql::Error> for AppError {
     fn from(error: tauri_plugin_sql::Error) -> Self {
         AppError::database(error.to_string())
     }
 }
 
-// Retry utility
+// Retry utility. Pick the preset for the kind of operation being retried;
+// Default suits anything that fits neither.
+#[derive(Debug, Clone, PartialEq)]
 pub struct RetryConfig {
     pub max_attempts: u32,
     pub initial_delay_ms: u64,
     pub max_delay_ms: u64,
     pub backoff_multiplier: f64,
+    pub jitter: Jitter,
+    pub jitter_seed: Option<u64>, // Fixed seed for reproducible delays; None seeds from the OS
+}
+
+// Randomizes each retry delay so operations that failed together don't all
+// retry at the same moment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Jitter {
+    #[default]
+    None,
+    Full,  // Anywhere from 0 up to the delay
+    Equal, // Half the delay, plus up to the other half
+}
+
+impl Jitter {
+    pub fn apply<R: Rng + ?Sized>(self, delay_ms: u64, rng: &mut R) -> u64 {
+        match self {
+            Jitter::None => delay_ms,
+            Jitter::Full => rng.gen_range(0..=delay_ms),
+            Jitter::Equal => {
+                let half = delay_ms / 2;
+                half + rng.gen_range(0..=delay_ms - half)
+            }
+        }
+    }
 }
 
 impl Default for RetryConfig {
@@ -431,6 +1014,35 @@ impl Default for RetryConfig {
             initial_delay_ms: 1000,
             max_delay_ms: 10000,
             backoff_multiplier: 2.0,
+            jitter: Jitter::None,
+            jitter_seed: None,
+        }
+    }
+}
+
+impl RetryConfig {
+    // SQLite already waits out locks for busy_timeout, so a failing query is
+    // retried once, quickly, rather than replayed several times
+    pub fn database() -> Self {
+        Self {
+            max_attempts: 2,
+            initial_delay_ms: 250,
+            max_delay_ms: 2000,
+            backoff_multiplier: 2.0,
+            jitter: Jitter::None,
+            jitter_seed: None,
+        }
+    }
+
+    // Connectivity problems and rate limits take a while to clear
+    pub fn network() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_delay_ms: 1000,
+            max_delay_ms: 30000,
+            backoff_multiplier: 2.0,
+            jitter: Jitter::Equal,
+            jitter_seed: None,
         }
     }
 }
@@ -445,6 +1057,10 @@ where
 {
     let mut last_error = None;
     let mut delay_ms = config.initial_delay_ms;
+    let mut rng = match config.jitter_seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
     
     for attempt in 1..=config.max_attempts {
         match operation().await {
@@ -453,7 +1069,7 @@ where
                 last_error = Some(error.clone());
                 
                 if attempt < config.max_attempts && error.is_retryable() {
-                    let actual_delay = error.retry_delay_ms().unwrap_or(delay_ms);
+                    let actual_delay = config.jitter.apply(next_retry_delay(&error, delay_ms), &mut rng);
                     tokio::time::sleep(tokio::time::Duration::from_millis(actual_delay)).await;
                     delay_ms = (delay_ms as f64 * config.backoff_multiplier).min(config.max_delay_ms as f64) as u64;
                 } else {
@@ -466,9 +1082,104 @@ where
     Err(last_error.unwrap())
 }
 
+// The backoff schedule decides the wait, except that a rate limit's own
+// retry_after is honored
+fn next_retry_delay(error: &AppError, backoff_delay_ms: u64) -> u64 {
+    match error {
+        AppError::RateLimit { retry_after: Some(retry_after), .. } => *retry_after,
+        _ => backoff_delay_ms,
+    }
+}
+
+// Fails fast on an operation that keeps failing. After `failure_threshold`
+// consecutive failures of a labeled operation, calls are refused for
+// `cooldown` instead of retrying against a resource that is down. The first
+// call after the cooldown goes through: success closes the circuit, another
+// failure opens it again. Only retryable errors count as failures; a
+// validation or not-found error says nothing about the resource's health.
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    cooldown: Duration,
+    circuits: Mutex<HashMap<String, CircuitState>>,
+}
+
+#[derive(Debug, Default)]
+struct CircuitState {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            circuits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub async fn call<F, T, Fut>(&self, operation: &str, f: F) -> AppResult<T>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = AppResult<T>>,
+    {
+        self.check_at(operation, Instant::now())?;
+
+        let result = f().await;
+        match &result {
+            Ok(_) => self.record_success(operation),
+            Err(error) => self.record_failure_at(operation, error, Instant::now()),
+        }
+        result
+    }
+
+    pub fn is_open(&self, operation: &str) -> bool {
+        self.check_at(operation, Instant::now()).is_err()
+    }
+
+    pub fn record_success(&self, operation: &str) {
+        self.circuits.lock().unwrap_or_else(|e| e.into_inner()).remove(operation);
+    }
+
+    pub fn record_failure(&self, operation: &str, error: &AppError) {
+        self.record_failure_at(operation, error, Instant::now());
+    }
+
+    fn check_at(&self, operation: &str, now: Instant) -> AppResult<()> {
+        let circuits = self.circuits.lock().unwrap_or_else(|e| e.into_inner());
+        match circuits.get(operation).and_then(|state| state.open_until) {
+            Some(open_until) if now < open_until => Err(AppError::Internal {
+                message: format!(
+                    "{} failed {} times in a row; not retrying for another {} ms",
+                    operation,
+                    circuits[operation].consecutive_failures,
+                    (open_until - now).as_millis()
+                ),
+                error_code: Some(ErrorCode::CircuitOpen.as_str().to_string()),
+                timestamp: Utc::now(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn record_failure_at(&self, operation: &str, error: &AppError, now: Instant) {
+        if !error.is_retryable() {
+            return;
+        }
+
+        let mut circuits = self.circuits.lock().unwrap_or_else(|e| e.into_inner());
+        let state = circuits.entry(operation.to_string()).or_default();
+        state.consecutive_failures += 1;
+        if state.consecutive_failures >= self.failure_threshold {
+            state.open_until = Some(now + self.cooldown);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serial_test::serial;
     
     #[test]
     fn test_error_creation() {
@@ -477,6 +1188,55 @@ mod tests {
         assert_eq!(error.user_message(), "There was a problem accessing the database. Please try again.");
     }
     
+    #[test]
+    fn test_constructors_set_stable_codes() {
+        assert_eq!(AppError::database("Connection failed").code(), Some("DB_ERROR"));
+        assert_eq!(AppError::validation("Invalid input").code(), Some("VALIDATION_FAILED"));
+        assert_eq!(AppError::internal("Invariant broken").code(), Some("INTERNAL"));
+        assert_eq!(AppError::file_system("Disk full", "write").code(), Some("FILE_ERROR"));
+        assert_eq!(AppError::not_found("Scene").code(), None);
+        assert_eq!(
+            AppError::validation_field("Too big", "file_size", "1 bytes").with_code(ErrorCode::FileTooLarge).code(),
+            Some("FILE_TOO_LARGE")
+        );
+        
+        let missing = std::io::Error::new(std::io::ErrorKind::NotFound, "gone");
+        assert_eq!(AppError::from(missing).code(), Some("FILE_NOT_FOUND"));
+        assert_eq!(AppError::from(sqlx::Error::RowNotFound).code(), Some("DB_ROW_NOT_FOUND"));
+        assert_eq!(AppError::from(sqlx::Error::PoolTimedOut).code(), Some("DB_POOL_TIMEOUT"));
+        
+        let constraint = tokio_test::block_on(async {
+            let pool = sqlx::SqlitePool::connect("sqlite::memory:").await.unwrap();
+            sqlx::query("CREATE TABLE t (x TEXT UNIQUE)").execute(&pool).await.unwrap();
+            sqlx::query("INSERT INTO t VALUES ('a')").execute(&pool).await.unwrap();
+            sqlx::query("INSERT INTO t VALUES ('a')").execute(&pool).await.unwrap_err()
+        });
+        assert_eq!(AppError::from(constraint).code(), Some("DB_CONSTRAINT"));
+        
+        // Codes travel with the serialized error
+        let json = serde_json::to_value(AppError::database("x").with_code(ErrorCode::DbLocked)).unwrap();
+        assert_eq!(json["data"]["code"], "DB_LOCKED");
+        assert_eq!(serde_json::to_value(ErrorCode::UnsupportedFormat).unwrap(), "UNSUPPORTED_FORMAT");
+    }
+    
+    #[test]
+    fn test_localized_message_key_and_args() {
+        let error = AppError::validation_field("Title is required", "title", "");
+        let message = error.localized_message();
+        assert_eq!(message.key, "error.validation.field");
+        assert_eq!(message.args.len(), 1);
+        assert_eq!(message.args.get("field").map(String::as_str), Some("title"));
+        assert_eq!(error.user_message(), "Please check the title field and try again.");
+        
+        let message = AppError::validation("Invalid input").localized_message();
+        assert_eq!(message.key, "error.validation");
+        assert!(message.args.is_empty());
+        
+        let json = serde_json::to_value(AppError::not_found("Scene").localized_message()).unwrap();
+        assert_eq!(json, serde_json::json!({"key": "error.not_found", "args": {"resource": "Scene"}}));
+        assert_eq!(AppError::internal("boom").user_message(), "An unexpected error occurred. Please try again.");
+    }
+    
     #[test]
     fn test_error_retryable() {
         let network_error = AppError::network_with_status("Server error", 500);
@@ -503,4 +1263,338 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(attempts, 3);
     }
+    
+    #[test]
+    fn test_retry_presets_differ_and_limit_attempts() {
+        let database = RetryConfig::database();
+        let network = RetryConfig::network();
+        assert_ne!(database, network);
+        assert_ne!(database, RetryConfig::default());
+        assert!(database.max_attempts < RetryConfig::default().max_attempts);
+        assert!(network.max_attempts > RetryConfig::default().max_attempts);
+        assert!(network.max_delay_ms > database.max_delay_ms);
+
+        let attempts = std::cell::Cell::new(0);
+        let result: AppResult<()> = tokio_test::block_on(retry_with_backoff(|| {
+            attempts.set(attempts.get() + 1);
+            async { Err(AppError::database("database is locked")) }
+        }, RetryConfig::database()));
+
+        assert!(matches!(result, Err(AppError::Database { .. })));
+        assert_eq!(attempts.get(), database.max_attempts);
+    }
+    
+    #[test]
+    fn test_retry_delays_follow_the_config() {
+        assert_eq!(next_retry_delay(&AppError::database("locked"), 250), 250);
+        assert_eq!(next_retry_delay(&AppError::network("offline"), 40), 40);
+        let rate_limited = AppError::RateLimit {
+            message: "Slow down".to_string(),
+            retry_after: Some(5000),
+            timestamp: Utc::now(),
+        };
+        assert_eq!(next_retry_delay(&rate_limited, 40), 5000);
+        
+        let elapsed_failing = |config: RetryConfig| {
+            let start = Instant::now();
+            let _: AppResult<()> = tokio_test::block_on(retry_with_backoff(
+                || async { Err(AppError::database("database is locked")) },
+                config
+            ));
+            start.elapsed()
+        };
+        
+        // One wait at the preset's initial delay (250ms), not a fixed 500ms
+        let database = elapsed_failing(RetryConfig::database());
+        assert!(database >= Duration::from_millis(250) && database < Duration::from_millis(500), "{:?}", database);
+        
+        // 20ms, then 40ms capped to 30ms
+        let custom = elapsed_failing(RetryConfig {
+            max_attempts: 3,
+            initial_delay_ms: 20,
+            max_delay_ms: 30,
+            backoff_multiplier: 2.0,
+            ..RetryConfig::default()
+        });
+        assert!(custom >= Duration::from_millis(50) && custom < Duration::from_millis(500), "{:?}", custom);
+    }
+    
+    #[test]
+    fn test_jittered_delay_stays_within_bounds() {
+        let base = 1000;
+        let mut rng = StdRng::seed_from_u64(7);
+
+        assert_eq!(Jitter::None.apply(base, &mut rng), base);
+        let full: Vec<u64> = (0..200).map(|_| Jitter::Full.apply(base, &mut rng)).collect();
+        assert!(full.iter().all(|&delay| delay <= base));
+        let equal: Vec<u64> = (0..200).map(|_| Jitter::Equal.apply(base, &mut rng)).collect();
+        assert!(equal.iter().all(|&delay| (base / 2..=base).contains(&delay)));
+        // Actually random, not pinned to one end of the range
+        assert!(full.iter().any(|&delay| delay < base / 2) && full.iter().any(|&delay| delay > base / 2));
+
+        // The same seed gives the same delays
+        let mut first = StdRng::seed_from_u64(42);
+        let mut second = StdRng::seed_from_u64(42);
+        let a: Vec<u64> = (0..10).map(|_| Jitter::Full.apply(base, &mut first)).collect();
+        let b: Vec<u64> = (0..10).map(|_| Jitter::Full.apply(base, &mut second)).collect();
+        assert_eq!(a, b);
+        assert_eq!(Jitter::Equal.apply(0, &mut first), 0);
+    }
+    
+    #[test]
+    fn test_circuit_breaker_opens_after_threshold_and_closes_after_cooldown() {
+        let breaker = CircuitBreaker::new(3, Duration::from_secs(30));
+        let start = Instant::now();
+        let failure = AppError::database("database is locked");
+
+        for _ in 0..2 {
+            breaker.record_failure_at("db", &failure, start);
+        }
+        assert!(breaker.check_at("db", start).is_ok());
+        // Non-transient errors don't count toward the threshold
+        breaker.record_failure_at("db", &AppError::validation("Bad input"), start);
+        assert!(breaker.check_at("db", start).is_ok());
+
+        breaker.record_failure_at("db", &failure, start);
+        let open = breaker.check_at("db", start + Duration::from_secs(29));
+        assert!(matches!(
+            open,
+            Err(ref error) if error.code() == Some(ErrorCode::CircuitOpen.as_str())
+        ));
+        assert!(!open.unwrap_err().is_retryable());
+        assert!(breaker.check_at("export", start).is_ok(), "circuits are per operation");
+
+        // After the cooldown one attempt goes through; failing again reopens
+        let after_cooldown = start + Duration::from_secs(30);
+        assert!(breaker.check_at("db", after_cooldown).is_ok());
+        breaker.record_failure_at("db", &failure, after_cooldown);
+        assert!(breaker.check_at("db", after_cooldown + Duration::from_secs(1)).is_err());
+
+        // A success closes the circuit and resets the count
+        breaker.record_success("db");
+        assert!(breaker.check_at("db", after_cooldown).is_ok());
+        breaker.record_failure_at("db", &failure, after_cooldown);
+        assert!(breaker.check_at("db", after_cooldown).is_ok());
+    }
+
+    #[test]
+    fn test_circuit_breaker_call_short_circuits() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(60));
+        let calls = std::cell::Cell::new(0);
+        let failing = || {
+            calls.set(calls.get() + 1);
+            async { Err::<(), _>(AppError::database("unavailable")) }
+        };
+
+        assert!(matches!(tokio_test::block_on(breaker.call("db", failing)), Err(AppError::Database { .. })));
+        assert!(breaker.is_open("db"));
+        assert!(matches!(tokio_test::block_on(breaker.call("db", failing)), Err(AppError::Internal { .. })));
+        assert_eq!(calls.get(), 1);
+    }
+    
+    #[test]
+    fn test_repeated_errors_are_coalesced() {
+        let dir = tempfile::tempdir().unwrap();
+        let logger = ErrorLogger::with_path(dir.path().join("errors.log"));
+        
+        for _ in 0..10 {
+            logger.log_error(&AppError::database("Connection failed"), Some("autosave")).unwrap();
+        }
+        
+        let errors = logger.get_recent_errors(50).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].occurrences, 10);
+        assert!(errors[0].first_seen() <= errors[0].timestamp);
+        assert_eq!(std::fs::read_to_string(dir.path().join("errors.log")).unwrap().lines().count(), 1);
+        
+        // Only consecutive repeats with the same context are merged
+        logger.log_error(&AppError::database("Connection failed"), Some("export")).unwrap();
+        logger.log_error(&AppError::database("Connection failed"), Some("autosave")).unwrap();
+        logger.log_error(&AppError::database("Connection failed"), Some("autosave")).unwrap();
+        let occurrences: Vec<u32> = logger.get_recent_errors(50).unwrap().iter().map(|e| e.occurrences).collect();
+        assert_eq!(occurrences, vec![10, 1, 2]);
+    }
+    
+    #[test]
+    fn test_error_log_tail_reports_new_and_coalesced_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("errors.log");
+        let logger = ErrorLogger::with_path(path.clone());
+        
+        logger.log_error(&AppError::validation("Before tailing"), None).unwrap();
+        let mut tail = ErrorLogTail::new(path.clone());
+        assert!(tail.poll().unwrap().is_empty());
+        
+        logger.log_error(&AppError::database("Connection failed"), None).unwrap();
+        let entries = tail.poll().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(matches!(entries[0].error, AppError::Database { .. }));
+        
+        // The repeat rewrites the last line, which is reported with its new count
+        logger.log_error(&AppError::database("Connection failed"), None).unwrap();
+        logger.log_error(&AppError::internal("Invariant broken"), None).unwrap();
+        let occurrences: Vec<u32> = tail.poll().unwrap().iter().map(|entry| entry.occurrences).collect();
+        assert_eq!(occurrences, vec![2, 1]);
+        assert!(tail.poll().unwrap().is_empty());
+        
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let watcher = ErrorLogWatcher::spawn(path, Duration::from_millis(20), move |entry| {
+            let _ = sender.send(entry);
+        });
+        logger.log_error(&AppError::not_found("Scene"), Some("stream")).unwrap();
+        let streamed = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(matches!(streamed.error, AppError::NotFound { .. }));
+        assert_eq!(streamed.context.as_deref(), Some("stream"));
+        drop(watcher);
+    }
+    
+    #[test]
+    fn test_unwritable_log_falls_back_to_memory() {
+        let dir = tempfile::tempdir().unwrap();
+        let blocker = dir.path().join("not_a_directory");
+        std::fs::write(&blocker, "").unwrap();
+        let logger = ErrorLogger::with_path(blocker.join("errors.log"));
+        
+        logger.log_error(&AppError::database("Connection failed"), None).unwrap();
+        logger.log_error(&AppError::database("Connection failed"), None).unwrap();
+        logger.log_error(&AppError::validation("Title is required"), Some("save")).unwrap();
+        
+        let errors = logger.get_recent_errors(10).unwrap();
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(errors[0].error, AppError::Database { .. }));
+        assert_eq!(errors[0].occurrences, 2);
+        assert_eq!(errors[1].context.as_deref(), Some("save"));
+        
+        let latest = logger.get_recent_errors(1).unwrap();
+        assert!(matches!(latest[0].error, AppError::Validation { .. }));
+        assert_eq!(logger.error_statistics().unwrap().total, 3);
+        
+        // Only an unreadable file with nothing in memory is an error
+        let _ = logger.clear_logs();
+        assert!(logger.get_recent_errors(10).is_err());
+    }
+    
+    #[test]
+    fn test_error_statistics_aggregate_the_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let logger = ErrorLogger::with_path(dir.path().join("errors.log"));
+        assert_eq!(logger.error_statistics().unwrap().total, 0);
+        
+        logger.log_error(&AppError::database("Connection failed"), None).unwrap();
+        logger.log_error(&AppError::database("Connection failed"), None).unwrap();
+        logger.log_error(&AppError::validation("Bad input"), None).unwrap();
+        logger.log_error(&AppError::internal("First invariant"), None).unwrap();
+        logger.log_error(&AppError::database("Locked"), None).unwrap();
+        logger.log_error(&AppError::internal("Second invariant"), Some("export")).unwrap();
+        logger.log_error(&AppError::not_found("Scene"), None).unwrap();
+        
+        let stats = logger.error_statistics().unwrap();
+        assert_eq!(stats.total, 7);
+        assert_eq!(stats.by_type.get("Database"), Some(&3));
+        assert_eq!(stats.by_type.get("Internal"), Some(&2));
+        assert_eq!(stats.by_type.get("Validation"), Some(&1));
+        assert_eq!(stats.by_type.get("NotFound"), Some(&1));
+        assert_eq!(stats.by_severity.get(&ErrorSeverity::High), Some(&3));
+        assert_eq!(stats.by_severity.get(&ErrorSeverity::Critical), Some(&2));
+        assert_eq!(stats.by_severity.get(&ErrorSeverity::Low), Some(&2));
+        assert_eq!(stats.by_severity.get(&ErrorSeverity::Medium), None);
+        
+        let critical = stats.most_recent_critical.unwrap();
+        assert_eq!(critical.context.as_deref(), Some("export"));
+        assert!(critical.error.to_string().contains("Second invariant"));
+        
+        // Severity keys serialize by name for the frontend
+        let json = serde_json::to_value(logger.error_statistics().unwrap()).unwrap();
+        assert_eq!(json["by_severity"]["Critical"], 2);
+    }
+    
+    // Puts back the panic hook that was installed before the test, even when
+    // an assertion fails, so later panics don't write to a deleted log
+    struct RestorePanicHook(Option<Box<dyn Fn(&std::panic::PanicHookInfo<'_>) + Sync + Send + 'static>>);
+
+    impl Drop for RestorePanicHook {
+        fn drop(&mut self) {
+            let _ = std::panic::take_hook();
+            if let Some(previous) = self.0.take() {
+                std::panic::set_hook(previous);
+            }
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_panic_hook_logs_internal_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = dir.path().join("errors.log");
+        let _restore = RestorePanicHook(Some(std::panic::take_hook()));
+        install_panic_hook(ErrorLogger::with_path(log_path.clone()));
+        
+        let result = std::thread::Builder::new()
+            .name("panicking-command".to_string())
+            .spawn(|| panic!("scene index out of range: {}", 42))
+            .unwrap()
+            .join();
+        assert!(result.is_err());
+        
+        let entries = ErrorLogger::with_path(log_path).get_recent_errors(50).unwrap();
+        let entry = entries
+            .iter()
+            .find(|entry| entry.context.as_deref() == Some("thread 'panicking-command'"))
+            .expect("panic was logged");
+        match &entry.error {
+            AppError::Internal { message, error_code, .. } => {
+                assert!(message.contains("scene index out of range: 42"), "{}", message);
+                assert!(message.contains("error.rs:"), "{}", message);
+                assert_eq!(error_code.as_deref(), Some(ErrorCode::Panic.as_str()));
+            }
+            other => panic!("expected an internal error, got {:?}", other),
+        }
+        assert_eq!(entry.severity, ErrorSeverity::Critical);
+    }
+    
+    #[test]
+    fn test_recent_errors_filtering() {
+        let dir = tempfile::tempdir().unwrap();
+        let logger = ErrorLogger::with_path(dir.path().join("errors.log"));
+        
+        logger.log_error(&AppError::validation("Bad input"), None).unwrap();
+        logger.log_error(&AppError::database("Connection failed"), None).unwrap();
+        logger.log_error(&AppError::internal("Invariant broken"), None).unwrap();
+        logger.log_error(&AppError::not_found("Scene"), None).unwrap();
+        
+        let high_and_above = ErrorLogFilter {
+            min_severity: Some(ErrorSeverity::High),
+            ..Default::default()
+        };
+        let errors = logger.get_recent_errors_filtered(50, &high_and_above).unwrap();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|e| e.severity >= ErrorSeverity::High));
+        
+        let database_only = ErrorLogFilter {
+            error_type: Some("Database".to_string()),
+            ..Default::default()
+        };
+        let errors = logger.get_recent_errors_filtered(50, &database_only).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0].error, AppError::Database { .. }));
+        
+        let in_the_future = ErrorLogFilter {
+            since: Some(Utc::now() + chrono::Duration::hours(1)),
+            ..Default::default()
+        };
+        assert!(logger.get_recent_errors_filtered(50, &in_the_future).unwrap().is_empty());
+        
+        // The limit applies after filtering
+        let low_only = ErrorLogFilter {
+            error_type: Some("validation".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(logger.get_recent_errors_filtered(1, &low_only).unwrap().len(), 1);
+    }
+    
+    #[test]
+    fn test_regex_error_conversion() {
+        let result: AppResult<regex::Regex> = regex::Regex::new("(unclosed").map_err(AppError::from);
+        assert!(matches!(result, Err(AppError::Validation { .. })));
+    }
 }
\ No newline at end of file
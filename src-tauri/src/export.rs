@@ -1,10 +1,16 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::fs;
 use chrono::{DateTime, Utc};
 use anyhow::{Result, anyhow};
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use regex::Regex;
+use std::sync::OnceLock;
+use std::io::{Cursor, Write};
+use tauri::{AppHandle, Emitter, State};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use crate::db::{DatabaseService, Manuscript, Scene};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ExportFormat {
     // Industry standard formats
     #[serde(rename = "shunn_manuscript")]
@@ -36,6 +42,98 @@ pub enum ExportFormat {
     FinalDraft,
 }
 
+// What the export picker shows for a format. Formats that aren't fully
+// supported produce a stand-in (e.g. HTML for PDF) and say so in `notes`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportFormatInfo {
+    pub format: ExportFormat,
+    pub display_name: String,
+    pub extension: String,
+    pub fully_supported: bool,
+    pub notes: Option<String>,
+}
+
+impl ExportFormat {
+    // Every format, in the order the picker lists them
+    pub fn all() -> Vec<ExportFormat> {
+        vec![
+            // Industry standard publishing formats
+            ExportFormat::ShunnManuscript,
+            ExportFormat::QueryPackage,
+            ExportFormat::SynopsisShort,
+            ExportFormat::SynopsisLong,
+            ExportFormat::PitchSheet,
+            ExportFormat::BookProposal,
+            ExportFormat::ScreenplayFinal,
+            ExportFormat::StagePlayStandard,
+
+            // General formats
+            ExportFormat::StandardManuscript,
+            ExportFormat::Docx,
+            ExportFormat::PDF,
+            ExportFormat::Markdown,
+            ExportFormat::LaTeX,
+            ExportFormat::Epub,
+            ExportFormat::Mobi,
+            ExportFormat::Scrivener,
+            ExportFormat::FinalDraft,
+        ]
+    }
+
+    // Zip containers and Palm databases; everything else is written as text
+    pub fn is_binary(&self) -> bool {
+        matches!(self, ExportFormat::Docx | ExportFormat::Epub | ExportFormat::Mobi)
+    }
+
+    pub fn info(&self) -> ExportFormatInfo {
+        let (display_name, extension, notes) = match self {
+            ExportFormat::ShunnManuscript => ("Shunn Manuscript", "txt", None),
+            ExportFormat::QueryPackage => ("Query Package", "txt", None),
+            ExportFormat::SynopsisShort => ("Short Synopsis", "txt", None),
+            ExportFormat::SynopsisLong => ("Long Synopsis", "txt", None),
+            ExportFormat::PitchSheet => ("Pitch Sheet", "txt", None),
+            ExportFormat::BookProposal => ("Book Proposal", "txt", None),
+            ExportFormat::ScreenplayFinal => ("Screenplay", "txt", None),
+            ExportFormat::StagePlayStandard => ("Stage Play", "txt", None),
+            ExportFormat::StandardManuscript => ("Standard Manuscript", "txt", None),
+            ExportFormat::Docx => ("Word Document", "docx", None),
+            ExportFormat::Markdown => ("Markdown", "md", None),
+            ExportFormat::LaTeX => ("LaTeX", "tex", None),
+            ExportFormat::PDF => ("PDF Document", "pdf", Some("Saved as print-ready HTML; print it to PDF from a browser")),
+            ExportFormat::Epub => ("EPUB eBook", "epub", None),
+            ExportFormat::Mobi => ("Kindle (MOBI)", "mobi", None),
+            ExportFormat::Scrivener => ("Scrivener", "txt", Some("Plain-text outline for import, not a .scriv project")),
+            ExportFormat::FinalDraft => ("Final Draft", "fdx", Some("Simplified screenplay structure")),
+        };
+
+        ExportFormatInfo {
+            format: self.clone(),
+            display_name: display_name.to_string(),
+            extension: extension.to_string(),
+            fully_supported: notes.is_none(),
+            notes: notes.map(str::to_string),
+        }
+    }
+}
+
+// One line of a generated table of contents. Chapters come from chapter_number
+// transitions and hold the titled scenes inside them; `anchor` is the id the
+// entry links to in the exported document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TocEntry {
+    pub anchor: String,
+    pub label: String,
+    pub children: Vec<TocEntry>,
+}
+
+fn chapter_anchor(chapter_number: u32) -> String {
+    format!("chapter-{}", chapter_number)
+}
+
+fn scene_anchor(index: usize) -> String {
+    format!("scene-{}", index + 1)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExportOptions {
     pub format: ExportFormat,
@@ -47,9 +145,124 @@ pub struct ExportOptions {
     pub header_footer: Option<HeaderFooterOptions>,
     pub font_settings: FontSettings,
     pub page_settings: PageSettings,
+    #[serde(default)]
+    pub paragraph_style: ParagraphStyle,
+    #[serde(default)]
+    pub title_page: TitlePageOption,
+    #[serde(default)]
+    pub latex_document_class: LatexDocumentClass,
+    #[serde(default)]
+    pub normalize_punctuation: bool,  // Straight ASCII quotes and dashes in text exports
+    #[serde(default = "default_scene_break_glyph")]
+    pub scene_break_glyph: String,  // "***", "⁂", "# # #"...; empty for a blank-line break
+    #[serde(default)]
+    pub write_manifest: bool,  // manifest.json listing the files of a multi-file export
     pub output_path: PathBuf,
 }
 
+fn default_scene_break_glyph() -> String {
+    "***".to_string()
+}
+
+impl ExportOptions {
+    // The glyph between scenes, or None when the break is blank space only
+    fn scene_break(&self) -> Option<&str> {
+        let glyph = self.scene_break_glyph.trim();
+        if glyph.is_empty() { None } else { Some(glyph) }
+    }
+}
+
+// Scenes within the same chapter are separated by a scene break; the first
+// scene of a chapter already follows the chapter heading
+fn follows_scene_break(scenes: &[SceneContent], index: usize) -> bool {
+    index > 0 && scenes[index - 1].chapter_number == scenes[index].chapter_number
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum LatexDocumentClass {
+    #[default]
+    Article,  // Short works; chapters become sections
+    Book,     // Book-length works with \chapter
+    Memoir,   // Book-length works, memoir handles spacing itself
+}
+
+impl LatexDocumentClass {
+    fn class_name(&self) -> &'static str {
+        match self {
+            LatexDocumentClass::Article => "article",
+            LatexDocumentClass::Book => "book",
+            LatexDocumentClass::Memoir => "memoir",
+        }
+    }
+
+    // Sectioning commands for chapters and scene titles
+    fn chapter_command(&self) -> &'static str {
+        match self {
+            LatexDocumentClass::Article => "section",
+            LatexDocumentClass::Book | LatexDocumentClass::Memoir => "chapter*",
+        }
+    }
+
+    fn scene_command(&self) -> &'static str {
+        match self {
+            LatexDocumentClass::Article => "subsection",
+            LatexDocumentClass::Book | LatexDocumentClass::Memoir => "section*",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum TitlePageOption {
+    None,            // No title page, e.g. when exporting a single chapter
+    #[default]
+    Standard,        // The exporter's own title page layout
+    Custom(String),  // Emitted verbatim as the title page
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ParagraphStyle {
+    FirstLineIndent(u32),  // Indent the first line by this many spaces
+    BlockWithSpacing,      // No indent, blank line between paragraphs
+}
+
+impl Default for ParagraphStyle {
+    fn default() -> Self {
+        ParagraphStyle::FirstLineIndent(4)
+    }
+}
+
+impl ParagraphStyle {
+    // Lay out plain-text paragraphs (separated by blank lines) in this style
+    pub fn format_text(&self, content: &str) -> String {
+        let indent = match self {
+            ParagraphStyle::FirstLineIndent(spaces) => " ".repeat(*spaces as usize),
+            ParagraphStyle::BlockWithSpacing => String::new(),
+        };
+
+        content
+            .split("\n\n")
+            .map(|paragraph| {
+                if paragraph.trim().is_empty() {
+                    String::new()
+                } else {
+                    format!("{}{}", indent, paragraph.trim())
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    // CSS declarations for body paragraphs in HTML output
+    pub fn css(&self) -> String {
+        match self {
+            ParagraphStyle::FirstLineIndent(spaces) => {
+                format!("text-indent: {}ch; margin: 0;", spaces)
+            }
+            ParagraphStyle::BlockWithSpacing => "text-indent: 0; margin: 0 0 1em 0;".to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HeaderFooterOptions {
     pub header: Option<String>,
@@ -123,10 +336,36 @@ pub struct ManuscriptContent {
     pub title: String,
     pub author: Option<String>,
     pub genre: Option<String>,
+    #[serde(default)]
+    pub contact_info: Option<ContactInfo>,
     pub scenes: Vec<SceneContent>,
     pub metadata: ManuscriptMetadata,
 }
 
+// Author contact block for the first page of a submission manuscript
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContactInfo {
+    pub legal_name: Option<String>,
+    pub address: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub agent: Option<String>,
+}
+
+impl ContactInfo {
+    // Non-empty lines in header order; multi-line addresses keep their lines
+    pub fn lines(&self) -> Vec<String> {
+        [&self.legal_name, &self.address, &self.email, &self.phone, &self.agent]
+            .into_iter()
+            .flatten()
+            .flat_map(|field| field.lines())
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SceneContent {
     pub id: String,
@@ -138,6 +377,8 @@ pub struct SceneContent {
     pub is_chapter_end: bool,
     pub word_count: usize,
     pub comments: Vec<CommentContent>,
+    #[serde(default)]
+    pub notes: Option<String>,
     pub formatting: SceneFormatting,
 }
 
@@ -189,6 +430,28 @@ pub struct ExportResult {
     pub warnings: Vec<String>,
 }
 
+pub const EXPORT_MANIFEST_FILE: &str = "manifest.json";
+
+// Receipt for a multi-file export, written next to the files it lists so a
+// submission package records what went into it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportManifest {
+    pub title: String,
+    pub author: Option<String>,
+    pub generated_at: DateTime<Utc>,
+    pub files: Vec<ManifestEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub format: ExportFormat,
+    pub output_path: PathBuf,
+    pub word_count: usize,
+    pub page_count: Option<usize>,
+    pub file_size: Option<u64>,
+    pub exported_at: DateTime<Utc>,
+}
+
 pub struct ExportService;
 
 impl ExportService {
@@ -225,6 +488,113 @@ impl ExportService {
         }
     }
 
+    // The exported text itself, for copying rather than saving. Exporters write
+    // files, so this runs one into a scratch directory and reads it back.
+    pub async fn render_text_export(
+        &self,
+        content: ManuscriptContent,
+        mut options: ExportOptions,
+    ) -> Result<String> {
+        if options.format.is_binary() {
+            return Err(anyhow!(
+                "{} is a binary format and can't be copied as text",
+                options.format.info().display_name
+            ));
+        }
+
+        let scratch_dir = std::env::temp_dir().join(format!("narrative-surgeon-export-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&scratch_dir)
+            .map_err(|e| anyhow!("Failed to create scratch directory: {}", e))?;
+        options.output_path = scratch_dir.join(format!("export.{}", options.format.info().extension));
+
+        let output_path = options.output_path.clone();
+        let exported = self.export_manuscript(content, options).await;
+        let text = exported.and_then(|_| {
+            fs::read_to_string(&output_path).map_err(|e| anyhow!("Failed to read export: {}", e))
+        });
+        let _ = fs::remove_dir_all(&scratch_dir);
+
+        text
+    }
+
+    // One file per chapter in `output_dir`, e.g. "01 - Chapter 1 - Arrival.txt",
+    // for sending chapters to beta readers. Chapters are runs of scenes sharing
+    // a chapter_number; the chapter's opening scene title names the file. With
+    // `write_manifest` set, manifest.json in the same directory lists the files.
+    pub async fn export_chapters_separately(
+        &self,
+        content: ManuscriptContent,
+        options: ExportOptions,
+        output_dir: &Path,
+    ) -> Result<Vec<ExportResult>> {
+        fs::create_dir_all(output_dir)
+            .map_err(|e| anyhow!("Failed to create export directory: {}", e))?;
+
+        let mut chapters: Vec<Vec<SceneContent>> = Vec::new();
+        for (index, scene) in content.scenes.iter().enumerate() {
+            match chapters.last_mut() {
+                Some(chapter) if index > 0 && content.scenes[index - 1].chapter_number == scene.chapter_number => {
+                    chapter.push(scene.clone());
+                }
+                _ => chapters.push(vec![scene.clone()]),
+            }
+        }
+
+        let extension = options.format.info().extension;
+        let mut results = Vec::with_capacity(chapters.len());
+        let mut manifest_files = Vec::with_capacity(chapters.len());
+        for (position, scenes) in chapters.into_iter().enumerate() {
+            let label = match (scenes[0].chapter_number, &scenes[0].title) {
+                (Some(number), Some(title)) => format!("Chapter {} - {}", number, title),
+                (Some(number), None) => format!("Chapter {}", number),
+                (None, Some(title)) => title.clone(),
+                (None, None) => "Untitled".to_string(),
+            };
+            let file_name = format!("{:02} - {}.{}", position + 1, crate::fs::sanitize_filename(&label), extension);
+
+            let mut chapter_content = content.clone();
+            chapter_content.metadata.word_count = scenes.iter().map(|scene| scene.word_count).sum();
+            chapter_content.scenes = scenes;
+
+            let mut chapter_options = options.clone();
+            chapter_options.title_page = TitlePageOption::None;
+            chapter_options.output_path = output_dir.join(file_name);
+
+            let output_path = chapter_options.output_path.clone();
+            let result = self.export_manuscript(chapter_content, chapter_options).await?;
+            manifest_files.push(ManifestEntry {
+                format: options.format.clone(),
+                output_path: result.output_path.clone().unwrap_or(output_path),
+                word_count: result.word_count,
+                page_count: result.page_count,
+                file_size: result.file_size,
+                exported_at: Utc::now(),
+            });
+            results.push(result);
+        }
+
+        if options.write_manifest {
+            let manifest = ExportManifest {
+                title: content.title.clone(),
+                author: content.author.clone(),
+                generated_at: Utc::now(),
+                files: manifest_files,
+            };
+            self.write_manifest(&manifest, output_dir)?;
+        }
+
+        Ok(results)
+    }
+
+    fn write_manifest(&self, manifest: &ExportManifest, output_dir: &Path) -> Result<PathBuf> {
+        let path = output_dir.join(EXPORT_MANIFEST_FILE);
+        let json = serde_json::to_string_pretty(manifest)
+            .map_err(|e| anyhow!("Failed to serialize export manifest: {}", e))?;
+        fs::write(&path, json)
+            .map_err(|e| anyhow!("Failed to write export manifest: {}", e))?;
+        Ok(path)
+    }
+
     async fn export_standard_manuscript(
         &self,
         content: ManuscriptContent,
@@ -247,16 +617,24 @@ impl ExportService {
         output.push_str(&format!("Approximately {} words\n\n", content.metadata.word_count));
 
         // Title page
-        output.push_str(&format!("{}\n", content.title.to_uppercase()));
-        output.push_str("\n\n");
-        if let Some(author) = &content.author {
-            output.push_str(&format!("by\n\n{}\n", author));
-        }
-        output.push_str("\x0C"); // Form feed for new page
+        self.push_title_page(&mut output, &options.title_page, |output| {
+            output.push_str(&format!("{}\n", content.title.to_uppercase()));
+            output.push_str("\n\n");
+            if let Some(author) = &content.author {
+                output.push_str(&format!("by\n\n{}\n", author));
+            }
+        });
 
         // Content
         let mut current_chapter = 0;
-        for scene in &content.scenes {
+        for (index, scene) in content.scenes.iter().enumerate() {
+            if follows_scene_break(&content.scenes, index) {
+                match options.scene_break() {
+                    Some(glyph) => output.push_str(&format!("{}\n\n", glyph)),
+                    None => output.push_str("\n\n"),
+                }
+            }
+
             // Chapter break handling
             if let Some(chapter_num) = scene.chapter_number {
                 if chapter_num != current_chapter {
@@ -277,7 +655,7 @@ impl ExportService {
             }
 
             // Scene content with proper formatting
-            let formatted_content = self.format_standard_manuscript_text(&scene.content);
+            let formatted_content = self.format_standard_manuscript_text(&self.scene_text(scene, &options), &options.paragraph_style);
             output.push_str(&formatted_content);
             
             // Comments if requested
@@ -288,11 +666,16 @@ impl ExportService {
                 }
             }
 
+            // Notes if requested
+            if let Some(notes) = self.scene_notes(scene, &options) {
+                output.push_str(&format!("\n\n[NOTES]\n{}\n", notes));
+            }
+
             output.push_str("\n\n");
         }
 
         // Write to file
-        let file_size = self.write_text_file(&options.output_path, &output).await?;
+        let file_size = self.write_export_text(&options, &output).await?;
         
         // Calculate page count (standard: ~250 words per page)
         let page_count = (content.metadata.word_count + 249) / 250;
@@ -395,7 +778,16 @@ impl ExportService {
 
         // Content
         let mut current_chapter = 0;
-        for scene in &content.scenes {
+        for (index, scene) in content.scenes.iter().enumerate() {
+            if follows_scene_break(&content.scenes, index) {
+                match options.scene_break() {
+                    // A leading '#' would otherwise start a heading
+                    Some(glyph) if glyph.starts_with('#') => output.push_str(&format!("\\{}\n\n", glyph)),
+                    Some(glyph) => output.push_str(&format!("{}\n\n", glyph)),
+                    None => output.push_str("&nbsp;\n\n"),
+                }
+            }
+
             // Chapter headers
             if let Some(chapter_num) = scene.chapter_number {
                 if chapter_num != current_chapter {
@@ -410,9 +802,17 @@ impl ExportService {
             }
 
             // Scene content
-            output.push_str(&scene.content);
+            output.push_str(&self.scene_text(scene, &options));
             output.push_str("\n\n");
 
+            // Notes as blockquotes
+            if let Some(notes) = self.scene_notes(scene, &options) {
+                for line in notes.lines() {
+                    output.push_str(&format!("> **Note:** {}\n", line));
+                }
+                output.push_str("\n");
+            }
+
             // Comments as blockquotes
             if options.include_comments && !scene.comments.is_empty() {
                 for comment in &scene.comments {
@@ -422,7 +822,7 @@ impl ExportService {
             }
         }
 
-        let file_size = self.write_text_file(&options.output_path, &output).await?;
+        let file_size = self.write_export_text(&options, &output).await?;
 
         Ok(ExportResult {
             success: true,
@@ -445,10 +845,19 @@ impl ExportService {
         let errors = Vec::new();
 
         // Document preamble
-        output.push_str("\\documentclass[12pt,letterpaper]{article}\n");
+        let class = options.latex_document_class;
+        let class_options = match class {
+            LatexDocumentClass::Article => "12pt,letterpaper",
+            LatexDocumentClass::Book => "12pt,letterpaper,openany",
+            LatexDocumentClass::Memoir => "12pt,letterpaper,oneside,openany",
+        };
+        output.push_str(&format!("\\documentclass[{}]{{{}}}\n", class_options, class.class_name()));
         output.push_str("\\usepackage[utf8]{inputenc}\n");
         output.push_str("\\usepackage{geometry}\n");
-        output.push_str("\\usepackage{setspace}\n");
+        if class != LatexDocumentClass::Memoir {
+            // memoir emulates setspace
+            output.push_str("\\usepackage{setspace}\n");
+        }
         output.push_str("\\usepackage{times}\n");
         
         // Page geometry
@@ -459,7 +868,8 @@ impl ExportService {
         ));
         
         // Line spacing
-        output.push_str(&format!("\\setstretch{{{:.1}}}\n", options.font_settings.line_spacing));
+        let spacing_command = if class == LatexDocumentClass::Memoir { "setSpacing" } else { "setstretch" };
+        output.push_str(&format!("\\{}{{{:.1}}}\n", spacing_command, options.font_settings.line_spacing));
         
         // Title and author
         output.push_str(&format!("\\title{{{}}}\n", self.escape_latex(&content.title)));
@@ -475,29 +885,36 @@ impl ExportService {
 
         // Content
         let mut current_chapter = 0;
-        for scene in &content.scenes {
+        for (index, scene) in content.scenes.iter().enumerate() {
+            if follows_scene_break(&content.scenes, index) {
+                match options.scene_break() {
+                    Some(glyph) => output.push_str(&format!("\\begin{{center}}{}\\end{{center}}\n\n", self.escape_latex(glyph))),
+                    None => output.push_str("\\bigskip\n\n"),
+                }
+            }
+
             // Chapter sections
             if let Some(chapter_num) = scene.chapter_number {
                 if chapter_num != current_chapter {
                     current_chapter = chapter_num;
-                    output.push_str(&format!("\\section{{Chapter {}}}\n\n", chapter_num));
+                    output.push_str(&format!("\\{}{{Chapter {}}}\n\n", class.chapter_command(), chapter_num));
                 }
             }
 
             // Scene subsection
             if let Some(title) = &scene.title {
-                output.push_str(&format!("\\subsection{{{}}}\n\n", self.escape_latex(title)));
+                output.push_str(&format!("\\{}{{{}}}\n\n", class.scene_command(), self.escape_latex(title)));
             }
 
             // Scene content
-            let escaped_content = self.escape_latex(&scene.content);
+            let escaped_content = self.escape_latex(&self.scene_text(scene, &options));
             output.push_str(&escaped_content);
             output.push_str("\n\n");
         }
 
         output.push_str("\\end{document}\n");
 
-        let file_size = self.write_text_file(&options.output_path, &output).await?;
+        let file_size = self.write_export_text(&options, &output).await?;
 
         Ok(ExportResult {
             success: true,
@@ -515,21 +932,17 @@ impl ExportService {
         content: ManuscriptContent,
         options: ExportOptions,
     ) -> Result<ExportResult> {
-        let mut warnings = Vec::new();
+        let warnings = Vec::new();
         let errors = Vec::new();
 
-        // EPUB requires complex ZIP structure - this is a simplified implementation
-        warnings.push("EPUB export is simplified - full implementation requires ZIP library".to_string());
-        
-        // Create EPUB structure as HTML for now
-        let html_content = self.build_epub_html(&content, &options)?;
-        let temp_path = options.output_path.with_extension("html");
-        let file_size = self.write_text_file(&temp_path, &html_content).await?;
+        let epub = self.build_epub(&content, &options)?;
+        fs::write(&options.output_path, &epub)
+            .map_err(|e| anyhow!("Failed to write EPUB file: {}", e))?;
 
         Ok(ExportResult {
             success: true,
-            output_path: Some(temp_path),
-            file_size: Some(file_size),
+            output_path: Some(options.output_path.clone()),
+            file_size: Some(epub.len() as u64),
             page_count: Some(self.estimate_page_count(&content)),
             word_count: content.metadata.word_count,
             errors,
@@ -537,24 +950,33 @@ impl ExportService {
         })
     }
 
+    // Written directly as a MOBI 6 file, so no Kindle tools are needed
     async fn export_mobi(
         &self,
         content: ManuscriptContent,
         options: ExportOptions,
     ) -> Result<ExportResult> {
-        // MOBI format requires kindlegen or similar tool
-        let warnings = vec!["MOBI export requires Amazon Kindle tools".to_string()];
+        let warnings = Vec::new();
         let errors = Vec::new();
 
-        // Export as HTML first
-        let html_content = self.build_html_content(&content, &options)?;
-        let temp_path = options.output_path.with_extension("html");
-        let file_size = self.write_text_file(&temp_path, &html_content).await?;
+        let mut html_content = self.build_mobi_html(&content, &options);
+        if options.normalize_punctuation {
+            html_content = normalize_punctuation(&html_content);
+        }
+        let mobi = build_mobi(
+            &content.title,
+            content.author.as_deref(),
+            &html_content,
+            Utc::now().timestamp() as u32
+        );
+
+        fs::write(&options.output_path, &mobi)
+            .map_err(|e| anyhow!("Failed to write MOBI file: {}", e))?;
 
         Ok(ExportResult {
             success: true,
-            output_path: Some(temp_path),
-            file_size: Some(file_size),
+            output_path: Some(options.output_path.clone()),
+            file_size: Some(mobi.len() as u64),
             page_count: Some(self.estimate_page_count(&content)),
             word_count: content.metadata.word_count,
             errors,
@@ -594,11 +1016,14 @@ impl ExportService {
                 output.push_str(&format!("CHAPTER: {}\n", chapter));
             }
             output.push_str("CONTENT:\n");
-            output.push_str(&scene.content);
+            output.push_str(&self.scene_text(scene, &options));
+            if let Some(notes) = self.scene_notes(scene, &options) {
+                output.push_str(&format!("\nNOTES:\n{}", notes));
+            }
             output.push_str("\n\n---\n\n");
         }
 
-        let file_size = self.write_text_file(&options.output_path, &output).await?;
+        let file_size = self.write_export_text(&options, &output).await?;
 
         Ok(ExportResult {
             success: true,
@@ -655,7 +1080,7 @@ impl ExportService {
         output.push_str("  </Content>\n");
         output.push_str("</FinalDraft>\n");
 
-        let file_size = self.write_text_file(&options.output_path, &output).await?;
+        let file_size = self.write_export_text(&options, &output).await?;
 
         Ok(ExportResult {
             success: true,
@@ -669,24 +1094,154 @@ impl ExportService {
     }
 
     // Helper methods
-    fn format_standard_manuscript_text(&self, content: &str) -> String {
-        content
-            .split("\n\n")
-            .map(|paragraph| {
-                if paragraph.trim().is_empty() {
-                    String::new()
-                } else {
-                    format!("    {}", paragraph.trim()) // Indent first line
+    fn format_standard_manuscript_text(&self, content: &str, style: &ParagraphStyle) -> String {
+        style.format_text(content)
+    }
+
+    // Scene body for text-based exports, with inline emphasis removed unless
+    // formatting is preserved
+    fn scene_text(&self, scene: &SceneContent, options: &ExportOptions) -> String {
+        if options.preserve_formatting {
+            return scene.content.clone();
+        }
+
+        static INLINE_TAG_REGEX: OnceLock<Regex> = OnceLock::new();
+        let regex = INLINE_TAG_REGEX.get_or_init(|| {
+            Regex::new(r"(?i)</?(?:strong|em|b|i)(?:\s[^>]*)?>").expect("inline tag pattern is valid")
+        });
+        regex.replace_all(&scene.content, "").into_owned()
+    }
+
+    fn scene_notes<'a>(&self, scene: &'a SceneContent, options: &ExportOptions) -> Option<&'a str> {
+        if !options.include_notes {
+            return None;
+        }
+        scene.notes.as_deref().map(str::trim).filter(|notes| !notes.is_empty())
+    }
+
+    fn table_of_contents(&self, content: &ManuscriptContent) -> Vec<TocEntry> {
+        let mut toc: Vec<TocEntry> = Vec::new();
+        let mut in_chapter = false;
+        let mut current_chapter = 0;
+
+        for (index, scene) in content.scenes.iter().enumerate() {
+            if let Some(chapter_num) = scene.chapter_number {
+                if chapter_num != current_chapter {
+                    current_chapter = chapter_num;
+                    toc.push(TocEntry {
+                        anchor: chapter_anchor(chapter_num),
+                        label: format!("Chapter {}", chapter_num),
+                        children: Vec::new(),
+                    });
                 }
-            })
-            .collect::<Vec<_>>()
-            .join("\n\n")
+                in_chapter = true;
+            } else {
+                in_chapter = false;
+            }
+
+            if let Some(title) = &scene.title {
+                let entry = TocEntry { anchor: scene_anchor(index), label: title.clone(), children: Vec::new() };
+                match toc.last_mut() {
+                    Some(chapter) if in_chapter => chapter.children.push(entry),
+                    _ => toc.push(entry),
+                }
+            }
+        }
+
+        toc
     }
 
-    fn build_docx_content(&self, _content: &ManuscriptContent, _options: &ExportOptions) -> Result<Vec<u8>> {
-        // This would use docx-rs library to create proper DOCX format
-        // For now, return placeholder
-        Ok(b"DOCX content placeholder".to_vec())
+    // Word document with a linked contents list: each entry is a hyperlink to a
+    // bookmark on its chapter or scene heading
+    fn build_docx_content(&self, content: &ManuscriptContent, options: &ExportOptions) -> Result<Vec<u8>> {
+        use docx_rs::{AlignmentType, BreakType, Docx, Hyperlink, HyperlinkType, Paragraph, Run, RunFonts, Style, StyleType};
+
+        let font = options.font_settings.font_family.as_str();
+        let mut docx = Docx::new()
+            .default_fonts(RunFonts::new().ascii(font).hi_ansi(font).cs(font))
+            .default_size(options.font_settings.font_size as usize * 2)  // Half-points
+            .add_style(Style::new("Heading1", StyleType::Paragraph).name("Heading 1").bold().size(32))
+            .add_style(Style::new("Heading2", StyleType::Paragraph).name("Heading 2").bold().size(28));
+
+        docx = docx.add_paragraph(
+            Paragraph::new()
+                .add_run(Run::new().add_text(&content.title).bold().size(36))
+                .align(AlignmentType::Center)
+        );
+        if let Some(author) = &content.author {
+            docx = docx.add_paragraph(
+                Paragraph::new()
+                    .add_run(Run::new().add_text(format!("by {}", author)))
+                    .align(AlignmentType::Center)
+            );
+        }
+
+        // Contents
+        docx = docx.add_paragraph(Paragraph::new().add_run(Run::new().add_text("Contents").bold()));
+        let mut entries: Vec<(&TocEntry, i32)> = Vec::new();
+        let toc = self.table_of_contents(content);
+        for entry in &toc {
+            entries.push((entry, 0));
+            entries.extend(entry.children.iter().map(|child| (child, 720)));
+        }
+        for (entry, indent) in entries {
+            docx = docx.add_paragraph(
+                Paragraph::new()
+                    .add_hyperlink(
+                        Hyperlink::new(&entry.anchor, HyperlinkType::Anchor)
+                            .add_run(Run::new().add_text(&entry.label))
+                    )
+                    .indent(Some(indent), None, None, None)
+            );
+        }
+        docx = docx.add_paragraph(Paragraph::new().add_run(Run::new().add_break(BreakType::Page)));
+
+        // Bookmark ids only need to be unique within the document
+        let mut bookmark_id = 0;
+        let mut heading = |style: &str, anchor: String, text: &str| {
+            bookmark_id += 1;
+            Paragraph::new()
+                .style(style)
+                .add_bookmark_start(bookmark_id, anchor)
+                .add_run(Run::new().add_text(text))
+                .add_bookmark_end(bookmark_id)
+        };
+
+        let mut current_chapter = 0;
+        for (index, scene) in content.scenes.iter().enumerate() {
+            if follows_scene_break(&content.scenes, index) {
+                let scene_break = match options.scene_break() {
+                    Some(glyph) => Paragraph::new().add_run(Run::new().add_text(glyph)).align(AlignmentType::Center),
+                    None => Paragraph::new(),
+                };
+                docx = docx.add_paragraph(scene_break);
+            }
+            if let Some(chapter_num) = scene.chapter_number {
+                if chapter_num != current_chapter {
+                    current_chapter = chapter_num;
+                    let label = format!("Chapter {}", chapter_num);
+                    docx = docx.add_paragraph(heading("Heading1", chapter_anchor(chapter_num), &label));
+                }
+            }
+            if let Some(title) = &scene.title {
+                docx = docx.add_paragraph(heading("Heading2", scene_anchor(index), title));
+            }
+
+            for paragraph in self.scene_text(scene, options).split("\n\n") {
+                if !paragraph.trim().is_empty() {
+                    docx = docx.add_paragraph(Paragraph::new().add_run(Run::new().add_text(paragraph.trim())));
+                }
+            }
+            if let Some(notes) = self.scene_notes(scene, options) {
+                docx = docx.add_paragraph(Paragraph::new().add_run(Run::new().add_text(notes).italic()));
+            }
+        }
+
+        let mut buffer = Cursor::new(Vec::new());
+        docx.build()
+            .pack(&mut buffer)
+            .map_err(|e| anyhow!("Failed to build DOCX: {}", e))?;
+        Ok(buffer.into_inner())
     }
 
     fn build_html_content(&self, content: &ManuscriptContent, options: &ExportOptions) -> Result<String> {
@@ -699,8 +1254,12 @@ impl ExportService {
                              options.font_settings.font_family, 
                              options.font_settings.font_size,
                              options.font_settings.line_spacing));
+        html.push_str(&self.page_css(content, options));
         html.push_str("    .chapter { page-break-before: always; }\n");
         html.push_str("    .scene { margin-bottom: 2em; }\n");
+        html.push_str(&format!("    .scene p {{ {} }}\n", options.paragraph_style.css()));
+        html.push_str("    .scene-break { text-align: center; margin: 0 0 2em 0; }\n");
+        html.push_str("    .scene-break.blank { height: 2em; }\n");
         html.push_str("  </style>\n");
         html.push_str("</head>\n<body>\n");
 
@@ -710,7 +1269,13 @@ impl ExportService {
         }
 
         let mut current_chapter = 0;
-        for scene in &content.scenes {
+        for (index, scene) in content.scenes.iter().enumerate() {
+            if follows_scene_break(&content.scenes, index) {
+                match options.scene_break() {
+                    Some(glyph) => html.push_str(&format!("  <p class=\"scene-break\">{}</p>\n", self.escape_html(glyph))),
+                    None => html.push_str("  <div class=\"scene-break blank\"></div>\n"),
+                }
+            }
             if let Some(chapter_num) = scene.chapter_number {
                 if chapter_num != current_chapter {
                     current_chapter = chapter_num;
@@ -728,6 +1293,9 @@ impl ExportService {
                     html.push_str(&format!("    <p>{}</p>\n", self.escape_html(paragraph.trim())));
                 }
             }
+            if let Some(notes) = self.scene_notes(scene, options) {
+                html.push_str(&format!("    <aside class=\"notes\">{}</aside>\n", self.escape_html(notes)));
+            }
             html.push_str("  </div>\n");
         }
 
@@ -735,9 +1303,249 @@ impl ExportService {
         Ok(html)
     }
 
-    fn build_epub_html(&self, content: &ManuscriptContent, options: &ExportOptions) -> Result<String> {
-        // Simplified EPUB HTML
-        self.build_html_content(content, options)
+    // Print layout: page size and margins, plus running header/footer and page
+    // number boxes. Header and footer text may use {title}, {author} and {page}.
+    fn page_css(&self, content: &ManuscriptContent, options: &ExportOptions) -> String {
+        let settings = &options.page_settings;
+        let (width, height) = match settings.page_size {
+            PageSize::Letter => (8.5, 11.0),
+            PageSize::A4 => (8.27, 11.69),
+            PageSize::Legal => (8.5, 14.0),
+            PageSize::Custom { width, height } => (width, height),
+        };
+        let (width, height) = match settings.orientation {
+            PageOrientation::Portrait => (width, height),
+            PageOrientation::Landscape => (height, width),
+        };
+        let margins = &settings.margins;
+
+        let mut rules = vec![
+            format!("size: {}in {}in;", width, height),
+            format!("margin: {}in {}in {}in {}in;", margins.top, margins.right, margins.bottom, margins.left),
+        ];
+
+        let header_footer = options.header_footer.as_ref();
+        let mut header = header_footer.and_then(|hf| {
+            hf.header.clone().or_else(|| {
+                let mut parts = Vec::new();
+                if hf.include_author {
+                    if let Some(surname) = content.author.as_deref().and_then(|a| a.split_whitespace().last()) {
+                        parts.push(surname.to_string());
+                    }
+                }
+                if hf.include_title {
+                    parts.push(content.title.to_uppercase());
+                }
+                (!parts.is_empty()).then(|| parts.join(" / "))
+            })
+        });
+        let footer = header_footer.and_then(|hf| hf.footer.clone());
+
+        let numbered = options.page_numbers || header_footer.is_some_and(|hf| hf.include_page_numbers);
+        let has_page_token = |text: &Option<String>| text.as_deref().is_some_and(|t| t.contains("{page}"));
+        if numbered && !has_page_token(&header) && !has_page_token(&footer) {
+            header = Some(match header {
+                Some(text) => format!("{} / {{page}}", text),
+                None => "{page}".to_string(),
+            });
+        }
+
+        if let Some(header) = header {
+            rules.push(format!("@top-right {{ content: {}; }}", self.css_content(&header, content)));
+        }
+        if let Some(footer) = footer {
+            rules.push(format!("@bottom-center {{ content: {}; }}", self.css_content(&footer, content)));
+        }
+
+        format!("    @page {{ {} }}\n", rules.join(" "))
+    }
+
+    // CSS `content` value for a header/footer template
+    fn css_content(&self, template: &str, content: &ManuscriptContent) -> String {
+        let expanded = template
+            .replace("{title}", &content.title)
+            .replace("{author}", content.author.as_deref().unwrap_or(""));
+
+        expanded
+            .split("{page}")
+            .map(|text| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\"")))
+            .collect::<Vec<_>>()
+            .join(" counter(page) ")
+    }
+
+    // Mobipocket HTML: the same chapter structure as the EPUB, with Kindle page
+    // breaks in place of CSS
+    fn build_mobi_html(&self, content: &ManuscriptContent, options: &ExportOptions) -> String {
+        let mut html = String::new();
+        html.push_str("<html><head><guide></guide></head><body>\n");
+        html.push_str(&format!("<h1>{}</h1>\n", self.escape_html(&content.title)));
+        if let Some(author) = &content.author {
+            html.push_str(&format!("<p><em>by {}</em></p>\n", self.escape_html(author)));
+        }
+
+        let mut current_chapter = 0;
+        for (index, scene) in content.scenes.iter().enumerate() {
+            if follows_scene_break(&content.scenes, index) {
+                let glyph = options.scene_break().map(|glyph| self.escape_html(glyph));
+                html.push_str(&format!("<p align=\"center\">{}</p>\n", glyph.as_deref().unwrap_or("&nbsp;")));
+            }
+            if let Some(chapter_num) = scene.chapter_number {
+                if chapter_num != current_chapter {
+                    current_chapter = chapter_num;
+                    html.push_str("<mbp:pagebreak/>\n");
+                    html.push_str(&format!("<h2>Chapter {}</h2>\n", chapter_num));
+                }
+            }
+
+            if let Some(title) = &scene.title {
+                html.push_str(&format!("<h3>{}</h3>\n", self.escape_html(title)));
+            }
+            for paragraph in self.scene_text(scene, options).split("\n\n") {
+                if !paragraph.trim().is_empty() {
+                    html.push_str(&format!("<p>{}</p>\n", self.escape_html(paragraph.trim())));
+                }
+            }
+            if let Some(notes) = self.scene_notes(scene, options) {
+                html.push_str(&format!("<blockquote>{}</blockquote>\n", self.escape_html(notes)));
+            }
+        }
+
+        html.push_str("</body></html>");
+        html
+    }
+
+    // EPUB 3 package: the book as one XHTML document plus a navigation document
+    // built from the same table of contents
+    fn build_epub(&self, content: &ManuscriptContent, options: &ExportOptions) -> Result<Vec<u8>> {
+        let toc = self.table_of_contents(content);
+        let mut epub = zip::ZipWriter::new(Cursor::new(Vec::new()));
+
+        // The mimetype entry must come first and be stored uncompressed
+        let stored = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        epub.start_file("mimetype", stored)?;
+        epub.write_all(b"application/epub+zip")?;
+
+        let files = [
+            ("META-INF/container.xml", EPUB_CONTAINER_XML.to_string()),
+            ("OEBPS/content.opf", self.build_epub_package(content)),
+            ("OEBPS/nav.xhtml", self.build_epub_nav(content, &toc)),
+            ("OEBPS/text.xhtml", self.build_epub_html(content, options)),
+        ];
+        for (name, body) in files {
+            epub.start_file(name, zip::write::FileOptions::default())?;
+            epub.write_all(body.as_bytes())?;
+        }
+
+        Ok(epub.finish()?.into_inner())
+    }
+
+    fn build_epub_package(&self, content: &ManuscriptContent) -> String {
+        let mut opf = String::new();
+        opf.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        opf.push_str("<package xmlns=\"http://www.idpf.org/2007/opf\" version=\"3.0\" unique-identifier=\"book-id\">\n");
+        opf.push_str("  <metadata xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n");
+        opf.push_str(&format!("    <dc:identifier id=\"book-id\">urn:uuid:{}</dc:identifier>\n", uuid::Uuid::new_v4()));
+        opf.push_str(&format!("    <dc:title>{}</dc:title>\n", self.escape_xml(&content.title)));
+        if let Some(author) = &content.author {
+            opf.push_str(&format!("    <dc:creator>{}</dc:creator>\n", self.escape_xml(author)));
+        }
+        opf.push_str("    <dc:language>en</dc:language>\n");
+        opf.push_str(&format!(
+            "    <meta property=\"dcterms:modified\">{}</meta>\n",
+            content.metadata.updated_at.format("%Y-%m-%dT%H:%M:%SZ")
+        ));
+        opf.push_str("  </metadata>\n");
+        opf.push_str("  <manifest>\n");
+        opf.push_str("    <item id=\"nav\" href=\"nav.xhtml\" media-type=\"application/xhtml+xml\" properties=\"nav\"/>\n");
+        opf.push_str("    <item id=\"text\" href=\"text.xhtml\" media-type=\"application/xhtml+xml\"/>\n");
+        opf.push_str("  </manifest>\n");
+        opf.push_str("  <spine>\n    <itemref idref=\"text\"/>\n  </spine>\n");
+        opf.push_str("</package>\n");
+        opf
+    }
+
+    fn build_epub_nav(&self, content: &ManuscriptContent, toc: &[TocEntry]) -> String {
+        fn push_entries(service: &ExportService, nav: &mut String, entries: &[TocEntry], depth: usize) {
+            let indent = "  ".repeat(depth + 3);
+            nav.push_str(&format!("{}<ol>\n", indent));
+            for entry in entries {
+                nav.push_str(&format!(
+                    "{}  <li><a href=\"text.xhtml#{}\">{}</a>",
+                    indent, entry.anchor, service.escape_xml(&entry.label)
+                ));
+                if !entry.children.is_empty() {
+                    nav.push('\n');
+                    push_entries(service, nav, &entry.children, depth + 1);
+                    nav.push_str(&format!("{}  ", indent));
+                }
+                nav.push_str("</li>\n");
+            }
+            nav.push_str(&format!("{}</ol>\n", indent));
+        }
+
+        let mut nav = String::new();
+        nav.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        nav.push_str("<html xmlns=\"http://www.w3.org/1999/xhtml\" xmlns:epub=\"http://www.idpf.org/2007/ops\">\n");
+        nav.push_str(&format!("<head><title>{}</title></head>\n", self.escape_xml(&content.title)));
+        nav.push_str("<body>\n  <nav epub:type=\"toc\" id=\"toc\">\n    <h1>Contents</h1>\n");
+        if !toc.is_empty() {
+            push_entries(self, &mut nav, toc, 0);
+        }
+        nav.push_str("  </nav>\n</body>\n</html>\n");
+        nav
+    }
+
+    // The book body as XHTML, with ids matching the table of contents anchors
+    fn build_epub_html(&self, content: &ManuscriptContent, options: &ExportOptions) -> String {
+        let mut html = String::new();
+        html.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        html.push_str("<html xmlns=\"http://www.w3.org/1999/xhtml\">\n<head>\n");
+        html.push_str(&format!("  <title>{}</title>\n", self.escape_xml(&content.title)));
+        html.push_str(&format!("  <style>p {{ {} }} p.scene-break {{ text-align: center; text-indent: 0; margin: 1em 0; }}</style>\n", options.paragraph_style.css()));
+        html.push_str("</head>\n<body>\n");
+        html.push_str(&format!("  <h1>{}</h1>\n", self.escape_xml(&content.title)));
+        if let Some(author) = &content.author {
+            html.push_str(&format!("  <p><em>by {}</em></p>\n", self.escape_xml(author)));
+        }
+
+        let mut current_chapter = 0;
+        for (index, scene) in content.scenes.iter().enumerate() {
+            if follows_scene_break(&content.scenes, index) {
+                // A non-breaking space keeps the blank break from collapsing
+                let glyph = options.scene_break().map(|glyph| self.escape_xml(glyph));
+                html.push_str(&format!("  <p class=\"scene-break\">{}</p>\n", glyph.as_deref().unwrap_or("&#160;")));
+            }
+            if let Some(chapter_num) = scene.chapter_number {
+                if chapter_num != current_chapter {
+                    current_chapter = chapter_num;
+                    html.push_str(&format!("  <h2 id=\"{}\">Chapter {}</h2>\n", chapter_anchor(chapter_num), chapter_num));
+                }
+            }
+            if let Some(title) = &scene.title {
+                html.push_str(&format!("  <h3 id=\"{}\">{}</h3>\n", scene_anchor(index), self.escape_xml(title)));
+            }
+
+            for paragraph in self.scene_text(scene, options).split("\n\n") {
+                if !paragraph.trim().is_empty() {
+                    html.push_str(&format!("  <p>{}</p>\n", self.escape_xml(paragraph.trim())));
+                }
+            }
+            if let Some(notes) = self.scene_notes(scene, options) {
+                html.push_str(&format!("  <aside>{}</aside>\n", self.escape_xml(notes)));
+            }
+        }
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+
+    // Write a text-based export to the configured output path
+    async fn write_export_text(&self, options: &ExportOptions, output: &str) -> Result<u64> {
+        if options.normalize_punctuation {
+            self.write_text_file(&options.output_path, &normalize_punctuation(output)).await
+        } else {
+            self.write_text_file(&options.output_path, output).await
+        }
     }
 
     async fn write_text_file(&self, path: &PathBuf, content: &str) -> Result<u64> {
@@ -772,16 +1580,26 @@ impl ExportService {
     }
 
     fn escape_latex(&self, text: &str) -> String {
-        text.replace("\\", "\\textbackslash{}")
-            .replace("{", "\\{")
-            .replace("}", "\\}")
-            .replace("$", "\\$")
-            .replace("&", "\\&")
-            .replace("%", "\\%")
-            .replace("#", "\\#")
-            .replace("^", "\\textasciicircum{}")
-            .replace("_", "\\_")
-            .replace("~", "\\textasciitilde{}")
+        // Single pass so the braces of one replacement are never escaped again
+        let mut escaped = String::with_capacity(text.len());
+        for ch in text.chars() {
+            match ch {
+                '\\' => escaped.push_str("\\textbackslash{}"),
+                '{' | '}' | '$' | '&' | '%' | '#' | '_' => {
+                    escaped.push('\\');
+                    escaped.push(ch);
+                }
+                '^' => escaped.push_str("\\textasciicircum{}"),
+                '~' => escaped.push_str("\\textasciitilde{}"),
+                '<' => escaped.push_str("\\textless{}"),
+                '>' => escaped.push_str("\\textgreater{}"),
+                '—' => escaped.push_str("---"),
+                '–' => escaped.push_str("--"),
+                '\u{00A0}' => escaped.push('~'),
+                _ => escaped.push(ch),
+            }
+        }
+        escaped
     }
 
     // Industry standard publishing format implementations
@@ -796,27 +1614,41 @@ impl ExportService {
         let errors = Vec::new();
 
         // Shunn manuscript format requirements
-        // 1. Header with author info (upper left)
-        if let Some(author) = &content.author {
+        // 1. Header with author contact info (upper left)
+        let contact_lines = content.contact_info.as_ref().map(ContactInfo::lines).unwrap_or_default();
+        if !contact_lines.is_empty() {
+            for line in &contact_lines {
+                output.push_str(&format!("{}\n", line));
+            }
+        } else if let Some(author) = &content.author {
             output.push_str(&format!("{}\n", author));
         }
         output.push_str(&format!("Approximately {} words\n\n", content.metadata.word_count));
 
         // 2. Title page centered
-        output.push_str("\n\n\n\n\n\n\n\n");
-        output.push_str(&format!("                        {}\n", content.title.to_uppercase()));
-        output.push_str("\n\n");
-        output.push_str("                            by\n\n");
-        if let Some(author) = &content.author {
-            output.push_str(&format!("                        {}\n", author));
-        }
-        output.push_str("\x0C"); // Form feed for new page
+        let has_title_page = self.push_title_page(&mut output, &options.title_page, |output| {
+            output.push_str("\n\n\n\n\n\n\n\n");
+            output.push_str(&format!("                        {}\n", content.title.to_uppercase()));
+            output.push_str("\n\n");
+            output.push_str("                            by\n\n");
+            if let Some(author) = &content.author {
+                output.push_str(&format!("                        {}\n", author));
+            }
+        });
 
         // 3. Content with proper headers and formatting
-        let mut page_count = 2; // Start after title page
+        let mut page_count = if has_title_page { 2 } else { 1 }; // Start after title page
         let mut current_chapter = 0;
         
-        for scene in &content.scenes {
+        for (index, scene) in content.scenes.iter().enumerate() {
+            // Centered scene break
+            if follows_scene_break(&content.scenes, index) {
+                match options.scene_break() {
+                    Some(glyph) => output.push_str(&format!("{}\n\n", format!("{:^60}", glyph).trim_end())),
+                    None => output.push_str("\n\n"),
+                }
+            }
+
             // Chapter handling
             if let Some(chapter_num) = scene.chapter_number {
                 if chapter_num != current_chapter {
@@ -844,14 +1676,14 @@ impl ExportService {
             }
 
             // Scene content with proper indentation
-            let formatted_content = self.format_shunn_text(&scene.content);
+            let formatted_content = self.format_shunn_text(&self.scene_text(scene, &options), &options.paragraph_style);
             output.push_str(&formatted_content);
             output.push_str("\n\n");
 
             page_count += (lines_in_scene + 24) / 25; // Estimate pages
         }
 
-        let file_size = self.write_text_file(&options.output_path, &output).await?;
+        let file_size = self.write_export_text(&options, &output).await?;
 
         Ok(ExportResult {
             success: true,
@@ -905,7 +1737,7 @@ impl ExportService {
         let sample_pages = self.extract_sample_pages(&content, 5)?;
         output.push_str(&sample_pages);
 
-        let file_size = self.write_text_file(&options.output_path, &output).await?;
+        let file_size = self.write_export_text(&options, &output).await?;
 
         Ok(ExportResult {
             success: true,
@@ -940,7 +1772,7 @@ impl ExportService {
         let synopsis = self.generate_synopsis(&content, target_words)?;
         output.push_str(&synopsis);
 
-        let file_size = self.write_text_file(&options.output_path, &output).await?;
+        let file_size = self.write_export_text(&options, &output).await?;
 
         Ok(ExportResult {
             success: true,
@@ -992,7 +1824,7 @@ impl ExportService {
         output.push_str("AUTHOR PLATFORM:\n");
         output.push_str("[Author credentials and platform details]\n");
 
-        let file_size = self.write_text_file(&options.output_path, &output).await?;
+        let file_size = self.write_export_text(&options, &output).await?;
 
         Ok(ExportResult {
             success: true,
@@ -1067,7 +1899,7 @@ impl ExportService {
         let sample = self.extract_sample_pages(&content, 20)?;
         output.push_str(&sample);
 
-        let file_size = self.write_text_file(&options.output_path, &output).await?;
+        let file_size = self.write_export_text(&options, &output).await?;
 
         Ok(ExportResult {
             success: true,
@@ -1090,14 +1922,15 @@ impl ExportService {
         let errors = Vec::new();
 
         // Screenplay title page
-        output.push_str("\n\n\n\n\n\n\n");
-        output.push_str(&format!("                        {}\n", content.title.to_uppercase()));
-        output.push_str("\n\n");
-        output.push_str("                      Written by\n\n");
-        if let Some(author) = &content.author {
-            output.push_str(&format!("                        {}\n", author));
-        }
-        output.push_str("\x0C"); // New page
+        self.push_title_page(&mut output, &options.title_page, |output| {
+            output.push_str("\n\n\n\n\n\n\n");
+            output.push_str(&format!("                        {}\n", content.title.to_uppercase()));
+            output.push_str("\n\n");
+            output.push_str("                      Written by\n\n");
+            if let Some(author) = &content.author {
+                output.push_str(&format!("                        {}\n", author));
+            }
+        });
 
         // Screenplay content
         output.push_str("FADE IN:\n\n");
@@ -1116,7 +1949,7 @@ impl ExportService {
 
         output.push_str("FADE OUT.\n\nTHE END\n");
 
-        let file_size = self.write_text_file(&options.output_path, &output).await?;
+        let file_size = self.write_export_text(&options, &output).await?;
 
         Ok(ExportResult {
             success: true,
@@ -1156,7 +1989,7 @@ impl ExportService {
             output.push_str("\n\n");
         }
 
-        let file_size = self.write_text_file(&options.output_path, &output).await?;
+        let file_size = self.write_export_text(&options, &output).await?;
 
         Ok(ExportResult {
             success: true,
@@ -1171,18 +2004,31 @@ impl ExportService {
 
     // Helper methods for industry formats
 
-    fn format_shunn_text(&self, content: &str) -> String {
-        content.split("\n\n")
-            .map(|paragraph| {
-                if paragraph.trim().is_empty() {
-                    String::new()
-                } else {
-                    // Proper paragraph indentation for Shunn format
-                    format!("    {}", paragraph.trim())
+    // Write the title page chosen in the options followed by a page break.
+    // `standard` renders the exporter's own layout. Returns whether a page was written.
+    fn push_title_page(
+        &self,
+        output: &mut String,
+        title_page: &TitlePageOption,
+        standard: impl FnOnce(&mut String),
+    ) -> bool {
+        match title_page {
+            TitlePageOption::None => return false,
+            TitlePageOption::Standard => standard(output),
+            TitlePageOption::Custom(text) => {
+                output.push_str(text);
+                if !text.ends_with('\n') {
+                    output.push('\n');
                 }
-            })
-            .collect::<Vec<_>>()
-            .join("\n\n")
+            }
+        }
+        output.push_str("\x0C"); // Form feed for new page
+        true
+    }
+
+    fn format_shunn_text(&self, content: &str, style: &ParagraphStyle) -> String {
+        // Shunn format expects indented paragraphs; block style is honored when asked for
+        style.format_text(content)
     }
 
     fn generate_synopsis(&self, content: &ManuscriptContent, target_words: usize) -> Result<String> {
@@ -1290,42 +2136,418 @@ impl ExportService {
     }
 }
 
+// Replace curly quotes, apostrophes and em/en dashes with ASCII equivalents
+pub fn normalize_punctuation(text: &str) -> String {
+    let mut normalized = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '‘' | '’' | '‚' | '‛' => normalized.push('\''),
+            '“' | '”' | '„' | '‟' => normalized.push('"'),
+            '—' => normalized.push_str("--"),
+            '–' => normalized.push('-'),
+            _ => normalized.push(ch),
+        }
+    }
+    normalized
+}
+
+// Export content built from the stored manuscript, for output the backend
+// produces on its own (printing) rather than from what the editor sends
+pub fn manuscript_content_from_scenes(manuscript: &Manuscript, scenes: &[Scene]) -> ManuscriptContent {
+    let chapter = |index: usize| scenes.get(index).and_then(|scene| scene.chapter_number);
+
+    let scenes: Vec<SceneContent> = scenes
+        .iter()
+        .enumerate()
+        .map(|(index, scene)| SceneContent {
+            id: scene.id.clone(),
+            title: scene.title.clone().filter(|title| !title.trim().is_empty()),
+            content: crate::fs::html_to_plain_text(&scene.raw_text),
+            chapter_number: scene.chapter_number.and_then(|n| u32::try_from(n).ok()),
+            scene_number: scene.scene_number_in_chapter.map_or(index as u32 + 1, |n| n as u32),
+            is_chapter_start: index == 0 || chapter(index - 1) != scene.chapter_number,
+            is_chapter_end: scene.is_chapter_end || chapter(index + 1) != scene.chapter_number,
+            word_count: scene.word_count as usize,
+            comments: Vec::new(),
+            notes: None,
+            formatting: SceneFormatting {
+                indent_first_line: true,
+                alignment: TextAlignment::Left,
+                spacing_before: 0.0,
+                spacing_after: 0.0,
+            },
+        })
+        .collect();
+
+    let word_count = scenes.iter().map(|scene| scene.word_count).sum();
+    ManuscriptContent {
+        title: manuscript.title.clone(),
+        author: manuscript.author.clone(),
+        genre: manuscript.genre.clone(),
+        contact_info: None,
+        metadata: ManuscriptMetadata {
+            word_count,
+            character_count: scenes.iter().map(|scene| scene.content.chars().count()).sum(),
+            page_count_estimate: (word_count + 249) / 250,
+            created_at: DateTime::from_timestamp_millis(manuscript.created_at).unwrap_or_default(),
+            updated_at: DateTime::from_timestamp_millis(manuscript.updated_at).unwrap_or_default(),
+            version: "1".to_string(),
+            target_audience: manuscript.target_audience.clone(),
+            comp_titles: manuscript.comp_titles
+                .as_deref()
+                .map(|titles| titles.split(',').map(str::trim).filter(|t| !t.is_empty()).map(str::to_string).collect())
+                .unwrap_or_default(),
+        },
+        scenes,
+    }
+}
+
+// Emitted when print HTML is ready for the webview's print dialog
+pub const PRINT_READY_EVENT: &str = "print-ready";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrintReady {
+    pub manuscript_id: String,
+    pub html: String,
+}
+
+// Merge comments stored in the database into the exported scenes. Comments the
+// frontend already sent (matched by id) are kept as-is.
+pub fn attach_stored_comments(content: &mut ManuscriptContent, stored: Vec<crate::db::SceneComment>) {
+    for comment in stored {
+        let Some(scene) = content.scenes.iter_mut().find(|scene| scene.id == comment.scene_id) else {
+            continue;
+        };
+        if scene.comments.iter().any(|existing| existing.id == comment.id) {
+            continue;
+        }
+        scene.comments.push(CommentContent {
+            id: comment.id,
+            text: comment.text,
+            position: comment.position as usize,
+            author: comment.author,
+            timestamp: DateTime::from_timestamp_millis(comment.created_at).unwrap_or_default(),
+        });
+    }
+
+    for scene in &mut content.scenes {
+        scene.comments.sort_by_key(|comment| comment.position);
+    }
+}
+
+async fn with_stored_comments(
+    app: &AppHandle,
+    db_service: &DatabaseService,
+    mut content: ManuscriptContent,
+    options: &ExportOptions,
+) -> std::result::Result<ManuscriptContent, String> {
+    if options.include_comments {
+        let pool = db_service.pool(app).await.map_err(|e| e.to_string())?;
+        let stored = crate::db::get_all_comments(&pool).await.map_err(|e| e.to_string())?;
+        attach_stored_comments(&mut content, stored);
+    }
+    Ok(content)
+}
+
 // Tauri commands
 #[tauri::command]
 pub async fn export_manuscript(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
     content: ManuscriptContent,
     options: ExportOptions,
 ) -> Result<ExportResult, String> {
+    let content = with_stored_comments(&app, &db_service, content, &options).await?;
     let service = ExportService::new();
     service.export_manuscript(content, options)
         .await
         .map_err(|e| e.to_string())
 }
 
+// Copy a text export to the clipboard; returns the number of characters copied
 #[tauri::command]
-pub async fn get_export_formats() -> Result<Vec<ExportFormat>, String> {
-    Ok(vec![
-        // Industry standard publishing formats
-        ExportFormat::ShunnManuscript,
-        ExportFormat::QueryPackage,
-        ExportFormat::SynopsisShort,
-        ExportFormat::SynopsisLong,
-        ExportFormat::PitchSheet,
-        ExportFormat::BookProposal,
-        ExportFormat::ScreenplayFinal,
-        ExportFormat::StagePlayStandard,
-        
-        // General formats
-        ExportFormat::StandardManuscript,
-        ExportFormat::Docx,
-        ExportFormat::PDF,
-        ExportFormat::Markdown,
-        ExportFormat::LaTeX,
-        ExportFormat::Epub,
-        ExportFormat::Mobi,
-        ExportFormat::Scrivener,
-        ExportFormat::FinalDraft,
-    ])
+pub async fn export_to_clipboard(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    content: ManuscriptContent,
+    options: ExportOptions,
+) -> Result<usize, String> {
+    let content = with_stored_comments(&app, &db_service, content, &options).await?;
+    let text = ExportService::new()
+        .render_text_export(content, options)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let copied = text.chars().count();
+    app.clipboard()
+        .write_text(text)
+        .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+
+    Ok(copied)
+}
+
+// Backs File > Print and Print Preview: the HTML export (with @page size,
+// margins and page numbers from the options) of the stored manuscript
+#[tauri::command]
+pub async fn generate_print_html(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    manuscript_id: Option<String>,
+    options: ExportOptions,
+) -> Result<String, String> {
+    let manuscript_id = crate::analysis::resolve_manuscript_id(manuscript_id).map_err(|e| e.to_string())?;
+    let manuscript = crate::db::get_manuscript_impl(&app)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "There is no manuscript to print".to_string())?;
+    let scenes = crate::analysis::load_manuscript_scenes(&app).await.map_err(|e| e.to_string())?;
+
+    let content = manuscript_content_from_scenes(&manuscript, &scenes);
+    let content = with_stored_comments(&app, &db_service, content, &options).await?;
+    let html = ExportService::new()
+        .build_html_content(&content, &options)
+        .map_err(|e| e.to_string())?;
+
+    app.emit(PRINT_READY_EVENT, PrintReady { manuscript_id, html: html.clone() })
+        .map_err(|e| format!("Failed to emit {}: {}", PRINT_READY_EVENT, e))?;
+
+    Ok(html)
+}
+
+#[tauri::command]
+pub async fn export_chapters(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    content: ManuscriptContent,
+    options: ExportOptions,
+    output_dir: PathBuf,
+) -> Result<Vec<ExportResult>, String> {
+    let content = with_stored_comments(&app, &db_service, content, &options).await?;
+    let service = ExportService::new();
+    service.export_chapters_separately(content, options, &output_dir)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+const EPUB_CONTAINER_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles>
+    <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+  </rootfiles>
+</container>
+"#;
+
+// MOBI 6 container: a Palm database whose first record holds the PalmDOC and
+// MOBI headers plus EXTH metadata, followed by uncompressed text records and
+// the FLIS, FCIS and end-of-file records Kindle readers expect.
+// Layout per https://wiki.mobileread.com/wiki/MOBI
+const MOBI_RECORD_SIZE: usize = 4096;
+const MOBI_HEADER_LENGTH: usize = 232;
+const PALMDOC_HEADER_LENGTH: usize = 16;
+const PDB_HEADER_LENGTH: usize = 78;
+const MOBI_NO_INDEX: u32 = 0xFFFF_FFFF;
+
+const MOBI_FLIS_RECORD: [u8; 36] = [
+    b'F', b'L', b'I', b'S', 0, 0, 0, 8, 0, 0x41, 0, 0, 0, 0, 0, 0,
+    0xFF, 0xFF, 0xFF, 0xFF, 0, 1, 0, 3, 0, 0, 0, 3, 0, 0, 0, 1,
+    0xFF, 0xFF, 0xFF, 0xFF,
+];
+const MOBI_EOF_RECORD: [u8; 4] = [0xE9, 0x8E, 0x0D, 0x0A];
+
+fn push_u16(buffer: &mut Vec<u8>, value: u16) {
+    buffer.extend_from_slice(&value.to_be_bytes());
+}
+
+fn push_u32(buffer: &mut Vec<u8>, value: u32) {
+    buffer.extend_from_slice(&value.to_be_bytes());
+}
+
+fn pad_to_four_bytes(buffer: &mut Vec<u8>) {
+    while buffer.len() % 4 != 0 {
+        buffer.push(0);
+    }
+}
+
+// Text records hold at most MOBI_RECORD_SIZE bytes and never split a character,
+// so no multibyte trailing entries are needed
+fn mobi_text_records(text: &str) -> Vec<&[u8]> {
+    let mut records = Vec::new();
+    let mut start = 0;
+    while start < text.len() {
+        let mut end = (start + MOBI_RECORD_SIZE).min(text.len());
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        records.push(&text.as_bytes()[start..end]);
+        start = end;
+    }
+    records
+}
+
+fn mobi_exth_header(title: &str, author: Option<&str>) -> Vec<u8> {
+    let mut entries: Vec<(u32, &[u8])> = Vec::new();
+    if let Some(author) = author {
+        entries.push((100, author.as_bytes()));  // Creator
+    }
+    entries.push((503, title.as_bytes()));       // Updated title
+    entries.push((524, "en".as_bytes()));        // Language
+
+    let mut records = Vec::new();
+    for (kind, data) in &entries {
+        push_u32(&mut records, *kind);
+        push_u32(&mut records, data.len() as u32 + 8);
+        records.extend_from_slice(data);
+    }
+
+    let mut exth = b"EXTH".to_vec();
+    push_u32(&mut exth, records.len() as u32 + 12);
+    push_u32(&mut exth, entries.len() as u32);
+    exth.extend_from_slice(&records);
+    pad_to_four_bytes(&mut exth);
+    exth
+}
+
+fn mobi_record_zero(title: &str, author: Option<&str>, text_length: usize, text_records: usize, unique_id: u32) -> Vec<u8> {
+    let first_non_book = text_records as u32 + 1;
+    let mut record = Vec::new();
+
+    // PalmDOC header: no compression, no encryption
+    push_u16(&mut record, 1);
+    push_u16(&mut record, 0);
+    push_u32(&mut record, text_length as u32);
+    push_u16(&mut record, text_records as u16);
+    push_u16(&mut record, MOBI_RECORD_SIZE as u16);
+    push_u16(&mut record, 0);
+    push_u16(&mut record, 0);
+
+    // MOBI header
+    record.extend_from_slice(b"MOBI");
+    push_u32(&mut record, MOBI_HEADER_LENGTH as u32);
+    push_u32(&mut record, 2);       // Mobipocket book
+    push_u32(&mut record, 65001);   // UTF-8
+    push_u32(&mut record, unique_id);
+    push_u32(&mut record, 6);       // File version
+    for _ in 0..10 {
+        push_u32(&mut record, MOBI_NO_INDEX);  // Orthographic, inflection, name, key and extra indexes
+    }
+    push_u32(&mut record, first_non_book);
+    let full_name_offset_position = record.len();
+    push_u32(&mut record, 0);       // Full name offset, filled in below
+    push_u32(&mut record, title.len() as u32);
+    push_u32(&mut record, 9);       // Locale: English
+    push_u32(&mut record, 0);       // Input language
+    push_u32(&mut record, 0);       // Output language
+    push_u32(&mut record, 6);       // Minimum reader version
+    push_u32(&mut record, first_non_book);  // First image record (there are none)
+    for _ in 0..4 {
+        push_u32(&mut record, 0);   // Huffman record and table offsets and counts
+    }
+    push_u32(&mut record, 0x40);    // EXTH header present
+    record.extend_from_slice(&[0; 32]);
+    push_u32(&mut record, MOBI_NO_INDEX);
+    push_u32(&mut record, MOBI_NO_INDEX);  // DRM offset, count, size, flags
+    push_u32(&mut record, 0);
+    push_u32(&mut record, 0);
+    push_u32(&mut record, 0);
+    record.extend_from_slice(&[0; 8]);
+    push_u16(&mut record, 1);       // First content record
+    push_u16(&mut record, text_records as u16);
+    push_u32(&mut record, 1);
+    push_u32(&mut record, first_non_book + 1);  // FCIS record
+    push_u32(&mut record, 1);
+    push_u32(&mut record, first_non_book);      // FLIS record
+    push_u32(&mut record, 1);
+    record.extend_from_slice(&[0; 8]);
+    push_u32(&mut record, MOBI_NO_INDEX);
+    push_u32(&mut record, 0);
+    push_u32(&mut record, MOBI_NO_INDEX);
+    push_u32(&mut record, MOBI_NO_INDEX);
+    push_u32(&mut record, 0);       // No trailing entries on text records
+    push_u32(&mut record, MOBI_NO_INDEX);  // No INDX record
+    debug_assert_eq!(record.len(), PALMDOC_HEADER_LENGTH + MOBI_HEADER_LENGTH);
+
+    record.extend_from_slice(&mobi_exth_header(title, author));
+
+    let full_name_offset = record.len() as u32;
+    record[full_name_offset_position..full_name_offset_position + 4].copy_from_slice(&full_name_offset.to_be_bytes());
+    record.extend_from_slice(title.as_bytes());
+    record.extend_from_slice(&[0, 0]);
+    pad_to_four_bytes(&mut record);
+    record
+}
+
+fn mobi_fcis_record(text_length: usize) -> Vec<u8> {
+    let mut record = b"FCIS".to_vec();
+    for value in [0x14, 0x10, 1, 0] {
+        push_u32(&mut record, value);
+    }
+    push_u32(&mut record, text_length as u32);
+    for value in [0, 0x20, 8] {
+        push_u32(&mut record, value);
+    }
+    push_u16(&mut record, 1);
+    push_u16(&mut record, 1);
+    push_u32(&mut record, 0);
+    record
+}
+
+// Palm database names are at most 31 bytes of plain ASCII
+fn pdb_name(title: &str) -> [u8; 32] {
+    let mut name = [0u8; 32];
+    let cleaned: Vec<u8> = title
+        .bytes()
+        .filter(|b| b.is_ascii_alphanumeric() || *b == b' ' || *b == b'-')
+        .map(|b| if b == b' ' { b'_' } else { b })
+        .take(31)
+        .collect();
+    let cleaned = if cleaned.is_empty() { b"book".to_vec() } else { cleaned };
+    name[..cleaned.len()].copy_from_slice(&cleaned);
+    name
+}
+
+pub fn build_mobi(title: &str, author: Option<&str>, html: &str, timestamp: u32) -> Vec<u8> {
+    let text_records = mobi_text_records(html);
+
+    let mut records = vec![mobi_record_zero(title, author, html.len(), text_records.len(), timestamp)];
+    records.extend(text_records.iter().map(|record| record.to_vec()));
+    records.push(MOBI_FLIS_RECORD.to_vec());
+    records.push(mobi_fcis_record(html.len()));
+    records.push(MOBI_EOF_RECORD.to_vec());
+
+    let mut output = Vec::new();
+    output.extend_from_slice(&pdb_name(title));
+    push_u16(&mut output, 0);          // Attributes
+    push_u16(&mut output, 0);          // Version
+    push_u32(&mut output, timestamp);  // Created
+    push_u32(&mut output, timestamp);  // Modified
+    push_u32(&mut output, 0);          // Last backup
+    push_u32(&mut output, 0);          // Modification number
+    push_u32(&mut output, 0);          // App info
+    push_u32(&mut output, 0);          // Sort info
+    output.extend_from_slice(b"BOOKMOBI");
+    push_u32(&mut output, (records.len() * 2 - 1) as u32);  // Unique ID seed
+    push_u32(&mut output, 0);          // Next record list
+    push_u16(&mut output, records.len() as u16);
+    debug_assert_eq!(output.len(), PDB_HEADER_LENGTH);
+
+    // Record list, then two bytes of padding before the first record
+    let mut offset = PDB_HEADER_LENGTH + records.len() * 8 + 2;
+    for (index, record) in records.iter().enumerate() {
+        push_u32(&mut output, offset as u32);
+        push_u32(&mut output, (index * 2) as u32);  // Attributes byte (0) and 3-byte unique ID
+        offset += record.len();
+    }
+    push_u16(&mut output, 0);
+
+    for record in &records {
+        output.extend_from_slice(record);
+    }
+    output
+}
+
+#[tauri::command]
+pub async fn get_export_formats() -> Result<Vec<ExportFormatInfo>, String> {
+    Ok(ExportFormat::all().iter().map(ExportFormat::info).collect())
 }
 
 #[tauri::command]
@@ -1354,4 +2576,625 @@ pub async fn validate_export_options(options: ExportOptions) -> Result<Vec<Strin
     }
 
     Ok(warnings)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(format: ExportFormat, output_path: PathBuf) -> ExportOptions {
+        ExportOptions {
+            format,
+            include_comments: false,
+            include_notes: false,
+            preserve_formatting: true,
+            chapter_breaks: true,
+            page_numbers: false,
+            header_footer: None,
+            font_settings: FontSettings::default(),
+            page_settings: PageSettings {
+                page_size: PageSize::Letter,
+                margins: Margins::default(),
+                orientation: PageOrientation::Portrait,
+            },
+            paragraph_style: ParagraphStyle::default(),
+            title_page: TitlePageOption::default(),
+            latex_document_class: LatexDocumentClass::default(),
+            normalize_punctuation: false,
+            scene_break_glyph: default_scene_break_glyph(),
+            write_manifest: false,
+            output_path,
+        }
+    }
+
+    fn scene(id: &str, chapter_number: u32, content: &str) -> SceneContent {
+        SceneContent {
+            id: id.to_string(),
+            title: None,
+            content: content.to_string(),
+            chapter_number: Some(chapter_number),
+            scene_number: 1,
+            is_chapter_start: true,
+            is_chapter_end: true,
+            word_count: content.split_whitespace().count(),
+            comments: Vec::new(),
+            notes: None,
+            formatting: SceneFormatting {
+                indent_first_line: true,
+                alignment: TextAlignment::Left,
+                spacing_before: 0.0,
+                spacing_after: 0.0,
+            },
+        }
+    }
+
+    fn manuscript(scenes: Vec<SceneContent>) -> ManuscriptContent {
+        let word_count = scenes.iter().map(|s| s.word_count).sum();
+        ManuscriptContent {
+            title: "The Long Night".to_string(),
+            author: Some("Jane Doe".to_string()),
+            genre: None,
+            contact_info: None,
+            scenes,
+            metadata: ManuscriptMetadata {
+                word_count,
+                character_count: 0,
+                page_count_estimate: 1,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                version: "1".to_string(),
+                target_audience: None,
+                comp_titles: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_paragraph_styles_in_text_exports() {
+        let content = "First paragraph.\n\nSecond paragraph.";
+
+        let indented = ParagraphStyle::FirstLineIndent(5).format_text(content);
+        assert_eq!(indented, "     First paragraph.\n\n     Second paragraph.");
+
+        let block = ParagraphStyle::BlockWithSpacing.format_text(content);
+        assert_eq!(block, "First paragraph.\n\nSecond paragraph.");
+    }
+
+    #[test]
+    fn test_standard_manuscript_honors_paragraph_style() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("manuscript.txt");
+        let service = ExportService::new();
+        let content = manuscript(vec![scene("s1", 1, "It was dark.\n\nIt was cold.")]);
+
+        let mut export_options = options(ExportFormat::StandardManuscript, path.clone());
+        tokio_test::block_on(service.export_manuscript(content.clone(), export_options.clone())).unwrap();
+        let output = std::fs::read_to_string(&path).unwrap();
+        assert!(output.contains("    It was dark.\n\n    It was cold."));
+
+        export_options.paragraph_style = ParagraphStyle::BlockWithSpacing;
+        tokio_test::block_on(service.export_manuscript(content, export_options)).unwrap();
+        let output = std::fs::read_to_string(&path).unwrap();
+        assert!(output.contains("\nIt was dark.\n\nIt was cold."));
+        assert!(!output.contains(" It was dark."));
+    }
+
+    #[test]
+    fn test_html_export_paragraph_css() {
+        let service = ExportService::new();
+        let content = manuscript(vec![scene("s1", 1, "One.\n\nTwo.")]);
+        let mut export_options = options(ExportFormat::PDF, PathBuf::from("unused.pdf"));
+
+        let html = service.build_html_content(&content, &export_options).unwrap();
+        assert!(html.contains(".scene p { text-indent: 4ch; margin: 0; }"));
+
+        export_options.paragraph_style = ParagraphStyle::BlockWithSpacing;
+        let html = service.build_html_content(&content, &export_options).unwrap();
+        assert!(html.contains(".scene p { text-indent: 0; margin: 0 0 1em 0; }"));
+    }
+
+    #[test]
+    fn test_include_notes_toggles_scene_notes() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("notes.md");
+        let service = ExportService::new();
+        let mut with_notes = scene("s1", 1, "The door opened.");
+        with_notes.notes = Some("Foreshadow the key here.".to_string());
+        let content = manuscript(vec![with_notes]);
+
+        let mut export_options = options(ExportFormat::Markdown, path.clone());
+        tokio_test::block_on(service.export_manuscript(content.clone(), export_options.clone())).unwrap();
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("Foreshadow"));
+
+        export_options.include_notes = true;
+        tokio_test::block_on(service.export_manuscript(content, export_options)).unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().contains("> **Note:** Foreshadow the key here."));
+    }
+
+    #[test]
+    fn test_preserve_formatting_false_strips_inline_emphasis() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("plain.txt");
+        let service = ExportService::new();
+        let content = manuscript(vec![scene("s1", 1, "She was <strong>very</strong> <em class=\"x\">sure</em>.")]);
+
+        let mut export_options = options(ExportFormat::StandardManuscript, path.clone());
+        tokio_test::block_on(service.export_manuscript(content.clone(), export_options.clone())).unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().contains("<strong>very</strong>"));
+
+        export_options.preserve_formatting = false;
+        tokio_test::block_on(service.export_manuscript(content, export_options)).unwrap();
+        let output = std::fs::read_to_string(&path).unwrap();
+        assert!(output.contains("She was very sure."));
+        assert!(!output.contains('<'));
+    }
+
+    #[test]
+    fn test_html_export_page_rules_and_running_header() {
+        let service = ExportService::new();
+        let content = manuscript(vec![scene("s1", 1, "One.")]);
+        let mut export_options = options(ExportFormat::PDF, PathBuf::from("unused.pdf"));
+
+        let html = service.build_html_content(&content, &export_options).unwrap();
+        assert!(html.contains("@page { size: 8.5in 11in; margin: 1in 1.25in 1in 1.25in; }"));
+        assert!(!html.contains("counter(page)"));
+
+        export_options.page_numbers = true;
+        export_options.header_footer = Some(HeaderFooterOptions {
+            header: None,
+            footer: Some("{title} by {author}".to_string()),
+            include_page_numbers: false,
+            include_title: true,
+            include_author: true,
+        });
+        let html = service.build_html_content(&content, &export_options).unwrap();
+        assert!(html.contains("@top-right { content: \"Doe / THE LONG NIGHT / \" counter(page) \"\"; }"));
+        assert!(html.contains("@bottom-center { content: \"The Long Night by Jane Doe\"; }"));
+    }
+
+    #[test]
+    fn test_title_page_none_omits_title_page() {
+        let temp = tempfile::tempdir().unwrap();
+        let service = ExportService::new();
+        let content = manuscript(vec![scene("s1", 1, "FADE IN on the harbor.")]);
+
+        for format in [ExportFormat::StandardManuscript, ExportFormat::ShunnManuscript, ExportFormat::ScreenplayFinal] {
+            let path = temp.path().join("out.txt");
+            let mut export_options = options(format, path.clone());
+            export_options.title_page = TitlePageOption::None;
+            tokio_test::block_on(service.export_manuscript(content.clone(), export_options)).unwrap();
+
+            let output = std::fs::read_to_string(&path).unwrap();
+            assert!(!output.contains("THE LONG NIGHT\n"), "title page in {}", output);
+            assert!(!output.contains('\x0C'));
+        }
+    }
+
+    #[test]
+    fn test_custom_title_page_is_verbatim() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("custom.txt");
+        let service = ExportService::new();
+        let content = manuscript(vec![scene("s1", 1, "It began.")]);
+        let block = "THE LONG NIGHT\n  Jane Doe\n  jane@example.com\n";
+
+        let mut export_options = options(ExportFormat::StandardManuscript, path.clone());
+        export_options.title_page = TitlePageOption::Custom(block.to_string());
+        tokio_test::block_on(service.export_manuscript(content, export_options)).unwrap();
+
+        let output = std::fs::read_to_string(&path).unwrap();
+        assert!(output.contains(&format!("{}\x0C", block)));
+        assert!(!output.contains("by\n\nJane Doe"));
+    }
+
+    #[test]
+    fn test_shunn_header_lists_contact_info_in_order() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("shunn.txt");
+        let service = ExportService::new();
+        let mut content = manuscript(vec![scene("s1", 1, "It began.")]);
+        content.contact_info = Some(ContactInfo {
+            legal_name: Some("Jane Q. Doe".to_string()),
+            address: Some("12 Elm Street\nSpringfield, IL 62701".to_string()),
+            email: Some("jane@example.com".to_string()),
+            phone: Some("(555) 010-2000".to_string()),
+            agent: Some("Represented by Sam Agent".to_string()),
+        });
+
+        tokio_test::block_on(service.export_manuscript(
+            content.clone(),
+            options(ExportFormat::ShunnManuscript, path.clone()),
+        )).unwrap();
+        let output = std::fs::read_to_string(&path).unwrap();
+        assert!(output.starts_with(
+            "Jane Q. Doe\n12 Elm Street\nSpringfield, IL 62701\njane@example.com\n(555) 010-2000\nRepresented by Sam Agent\nApproximately"
+        ), "got {}", output);
+
+        content.contact_info = None;
+        tokio_test::block_on(service.export_manuscript(
+            content,
+            options(ExportFormat::ShunnManuscript, path.clone()),
+        )).unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().starts_with("Jane Doe\nApproximately"));
+    }
+
+    #[test]
+    fn test_latex_document_class_selects_sectioning() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("book.tex");
+        let service = ExportService::new();
+        let mut titled = scene("s1", 1, "It began.");
+        titled.title = Some("Arrival".to_string());
+        let content = manuscript(vec![titled]);
+
+        let cases = [
+            (LatexDocumentClass::Article, "{article}", "\\section{Chapter 1}", "\\subsection{Arrival}"),
+            (LatexDocumentClass::Book, "{book}", "\\chapter*{Chapter 1}", "\\section*{Arrival}"),
+            (LatexDocumentClass::Memoir, "{memoir}", "\\chapter*{Chapter 1}", "\\section*{Arrival}"),
+        ];
+        for (class, document_class, chapter, scene_heading) in cases {
+            let mut export_options = options(ExportFormat::LaTeX, path.clone());
+            export_options.latex_document_class = class;
+            tokio_test::block_on(service.export_manuscript(content.clone(), export_options)).unwrap();
+
+            let output = std::fs::read_to_string(&path).unwrap();
+            assert!(output.lines().next().unwrap().ends_with(document_class), "got {}", output);
+            assert!(output.contains(chapter), "missing {} in {}", chapter, output);
+            assert!(output.contains(scene_heading), "missing {} in {}", scene_heading, output);
+        }
+
+        let output = std::fs::read_to_string(&path).unwrap();
+        assert!(!output.contains("setspace"));
+        assert!(output.contains("\\setSpacing{2.0}"));
+    }
+
+    #[test]
+    fn test_escape_latex_single_pass() {
+        let service = ExportService::new();
+        assert_eq!(
+            service.escape_latex("a\\b {x} <y> 50% — end–to"),
+            "a\\textbackslash{}b \\{x\\} \\textless{}y\\textgreater{} 50\\% --- end--to"
+        );
+        assert_eq!(service.escape_latex("~^\u{00A0}"), "\\textasciitilde{}\\textasciicircum{}~");
+    }
+
+    #[test]
+    fn test_normalize_punctuation_only_when_requested() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("plain.txt");
+        let service = ExportService::new();
+        let content = manuscript(vec![scene("s1", 1, "“Don’t,” she said — twice – and ‘left’.")]);
+
+        let mut export_options = options(ExportFormat::StandardManuscript, path.clone());
+        tokio_test::block_on(service.export_manuscript(content.clone(), export_options.clone())).unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().contains("“Don’t,” she said — twice – and ‘left’."));
+
+        export_options.normalize_punctuation = true;
+        tokio_test::block_on(service.export_manuscript(content, export_options)).unwrap();
+        let output = std::fs::read_to_string(&path).unwrap();
+        assert!(output.contains("\"Don't,\" she said -- twice - and 'left'."));
+        assert!(output.is_ascii());
+    }
+
+    #[test]
+    fn test_placeholder_formats_are_not_fully_supported() {
+        let formats = tokio_test::block_on(get_export_formats()).unwrap();
+        assert_eq!(formats.len(), ExportFormat::all().len());
+
+        for format in [ExportFormat::PDF] {
+            let info = formats.iter().find(|info| info.format == format).unwrap();
+            assert!(!info.fully_supported, "{:?}", format);
+            assert!(info.notes.is_some());
+        }
+
+        assert!(ExportFormat::Mobi.info().fully_supported);
+        assert!(ExportFormat::Epub.info().fully_supported);
+        let docx = ExportFormat::Docx.info();
+        assert!(docx.fully_supported);
+        assert_eq!(docx.extension, "docx");
+        assert_eq!(docx.notes, None);
+    }
+
+    #[test]
+    fn test_mobi_has_palm_database_and_mobi_headers() {
+        let html = format!("<html><body><p>{}</p></body></html>", "Café ".repeat(1200));
+        let mobi = build_mobi("The Long Night", Some("Jane Doe"), &html, 1_700_000_000);
+
+        assert!(mobi.starts_with(b"The_Long_Night\0"));
+        assert_eq!(&mobi[60..68], b"BOOKMOBI");
+
+        let be_u32 = |at: usize| u32::from_be_bytes(mobi[at..at + 4].try_into().unwrap()) as usize;
+        let record_count = u16::from_be_bytes([mobi[76], mobi[77]]) as usize;
+        let offsets: Vec<usize> = (0..record_count).map(|i| be_u32(PDB_HEADER_LENGTH + i * 8)).collect();
+
+        let record_zero = offsets[0];
+        assert_eq!(&mobi[record_zero + 16..record_zero + 20], b"MOBI");
+        assert_eq!(be_u32(record_zero + 4), html.len());
+        assert_eq!(be_u32(record_zero + 28), 65001);
+        assert_eq!(&mobi[record_zero + 248..record_zero + 252], b"EXTH");
+
+        // Text records split on character boundaries and reassemble exactly
+        let text_records = u16::from_be_bytes([mobi[record_zero + 8], mobi[record_zero + 9]]) as usize;
+        assert_eq!(text_records, 2);
+        let text: Vec<u8> = (1..=text_records)
+            .flat_map(|i| mobi[offsets[i]..offsets[i + 1]].to_vec())
+            .collect();
+        assert_eq!(String::from_utf8(text).unwrap(), html);
+
+        assert_eq!(&mobi[offsets[text_records + 1]..offsets[text_records + 1] + 4], b"FLIS");
+        assert_eq!(&mobi[offsets[text_records + 2]..offsets[text_records + 2] + 4], b"FCIS");
+        assert!(mobi.ends_with(&MOBI_EOF_RECORD));
+    }
+
+    fn toc_manuscript() -> ManuscriptContent {
+        let mut scenes = vec![
+            scene("s1", 1, "They met at dusk."),
+            scene("s2", 1, "The storm broke."),
+            scene("s3", 2, "Morning came."),
+            scene("s4", 3, "The end."),
+        ];
+        scenes[1].title = Some("The Storm & After".to_string());
+        manuscript(scenes)
+    }
+
+    fn zip_entry(bytes: &[u8], name: &str) -> String {
+        use std::io::Read;
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        let mut entry = archive.by_name(name).unwrap();
+        let mut text = String::new();
+        entry.read_to_string(&mut text).unwrap();
+        text
+    }
+
+    #[test]
+    fn test_epub_nav_lists_every_chapter() {
+        let service = ExportService::new();
+        let content = toc_manuscript();
+        let epub = service.build_epub(&content, &options(ExportFormat::Epub, PathBuf::from("unused.epub"))).unwrap();
+
+        let archive = zip::ZipArchive::new(Cursor::new(epub.as_slice())).unwrap();
+        assert_eq!(archive.file_names().count(), 5);
+        assert_eq!(zip_entry(&epub, "mimetype"), "application/epub+zip");
+
+        let nav = zip_entry(&epub, "OEBPS/nav.xhtml");
+        for chapter in 1..=3 {
+            assert!(nav.contains(&format!("<a href=\"text.xhtml#chapter-{0}\">Chapter {0}</a>", chapter)), "{}", nav);
+        }
+        assert!(nav.contains("<a href=\"text.xhtml#scene-2\">The Storm &amp; After</a>"));
+
+        let text = zip_entry(&epub, "OEBPS/text.xhtml");
+        assert!(text.contains("<h2 id=\"chapter-2\">Chapter 2</h2>"));
+        assert!(text.contains("<h3 id=\"scene-2\">The Storm &amp; After</h3>"));
+    }
+
+    #[test]
+    fn test_docx_contains_linked_contents() {
+        let service = ExportService::new();
+        let docx = service.build_docx_content(&toc_manuscript(), &options(ExportFormat::Docx, PathBuf::from("unused.docx"))).unwrap();
+
+        let document = zip_entry(&docx, "word/document.xml");
+        assert!(document.contains("Contents"));
+        for anchor in ["chapter-1", "chapter-2", "chapter-3", "scene-2"] {
+            assert!(document.contains(&format!("w:anchor=\"{}\"", anchor)), "missing link to {}", anchor);
+            assert!(document.contains(&format!("w:name=\"{}\"", anchor)), "missing bookmark {}", anchor);
+        }
+    }
+
+    #[test]
+    fn test_scene_break_glyph_between_scenes_only() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("manuscript.txt");
+        let service = ExportService::new();
+        let content = manuscript(vec![
+            scene("s1", 1, "It was dark."),
+            scene("s2", 1, "It was cold."),
+            scene("s3", 2, "Morning came."),
+        ]);
+
+        let mut export_options = options(ExportFormat::StandardManuscript, path.clone());
+        export_options.scene_break_glyph = "⁂".to_string();
+        tokio_test::block_on(service.export_manuscript(content.clone(), export_options.clone())).unwrap();
+        let output = std::fs::read_to_string(&path).unwrap();
+        assert!(output.contains("It was dark.\n\n⁂\n\n    It was cold."), "{}", output);
+        assert_eq!(output.matches('⁂').count(), 1);
+
+        let html = service.build_html_content(&content, &export_options).unwrap();
+        assert_eq!(html.matches("<p class=\"scene-break\">⁂</p>").count(), 1);
+
+        export_options.scene_break_glyph = "# # #".to_string();
+        export_options.format = ExportFormat::LaTeX;
+        tokio_test::block_on(service.export_manuscript(content.clone(), export_options.clone())).unwrap();
+        let latex = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(latex.matches("\\begin{center}\\# \\# \\#\\end{center}").count(), 1);
+
+        let docx = service.build_docx_content(&content, &export_options).unwrap();
+        assert!(zip_entry(&docx, "word/document.xml").contains("# # #"));
+    }
+
+    #[test]
+    fn test_blank_scene_break_adds_spacing() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("manuscript.txt");
+        let service = ExportService::new();
+        let content = manuscript(vec![scene("s1", 1, "It was dark."), scene("s2", 1, "It was cold.")]);
+
+        let mut export_options = options(ExportFormat::StandardManuscript, path.clone());
+        export_options.scene_break_glyph = String::new();
+        tokio_test::block_on(service.export_manuscript(content.clone(), export_options.clone())).unwrap();
+        let output = std::fs::read_to_string(&path).unwrap();
+        assert!(output.contains("It was dark.\n\n\n\n    It was cold."), "{}", output);
+        assert!(!output.contains("***"));
+
+        let html = service.build_html_content(&content, &export_options).unwrap();
+        assert!(html.contains("<div class=\"scene-break blank\"></div>"));
+
+        export_options.format = ExportFormat::LaTeX;
+        tokio_test::block_on(service.export_manuscript(content, export_options)).unwrap();
+        let latex = std::fs::read_to_string(&path).unwrap();
+        assert!(latex.contains("\\bigskip"));
+    }
+
+    #[test]
+    fn test_chapters_export_to_separate_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let output_dir = temp.path().join("chapters");
+        let service = ExportService::new();
+        let mut scenes = vec![
+            scene("s1", 1, "They met at dusk."),
+            scene("s2", 1, "The storm broke."),
+            scene("s3", 2, "Morning came."),
+            scene("s4", 3, "The end."),
+        ];
+        scenes[0].title = Some("Arrival".to_string());
+        scenes[2].title = Some("What/Now?".to_string());
+        let content = manuscript(scenes);
+
+        let export_options = options(ExportFormat::Markdown, PathBuf::from("unused.md"));
+        let results = tokio_test::block_on(
+            service.export_chapters_separately(content, export_options, &output_dir)
+        ).unwrap();
+
+        let names: Vec<String> = results
+            .iter()
+            .map(|result| result.output_path.as_ref().unwrap().file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, vec![
+            "01 - Chapter 1 - Arrival.md",
+            "02 - Chapter 2 - What_Now_.md",
+            "03 - Chapter 3.md",
+        ]);
+        assert_eq!(std::fs::read_dir(&output_dir).unwrap().count(), 3);
+
+        let first = std::fs::read_to_string(output_dir.join(&names[0])).unwrap();
+        assert!(first.contains("The storm broke."));
+        assert!(!first.contains("Morning came."));
+        assert_eq!(results[0].word_count, 7);
+    }
+
+    #[test]
+    fn test_chapter_export_writes_manifest_when_enabled() {
+        let temp = tempfile::tempdir().unwrap();
+        let output_dir = temp.path().join("package");
+        let service = ExportService::new();
+        let content = manuscript(vec![
+            scene("s1", 1, "They met at dusk."),
+            scene("s2", 2, "Morning came at last."),
+        ]);
+
+        let mut export_options = options(ExportFormat::Markdown, PathBuf::from("unused.md"));
+        export_options.write_manifest = true;
+        let before = Utc::now();
+        let results = tokio_test::block_on(
+            service.export_chapters_separately(content, export_options, &output_dir)
+        ).unwrap();
+
+        let json = std::fs::read_to_string(output_dir.join(EXPORT_MANIFEST_FILE)).unwrap();
+        let manifest: ExportManifest = serde_json::from_str(&json).unwrap();
+        assert_eq!(manifest.title, "The Long Night");
+        assert_eq!(manifest.files.len(), 2);
+        for (entry, result) in manifest.files.iter().zip(&results) {
+            assert_eq!(entry.format, ExportFormat::Markdown);
+            assert_eq!(Some(&entry.output_path), result.output_path.as_ref());
+            assert!(entry.output_path.exists());
+            assert_eq!(entry.word_count, result.word_count);
+            assert_eq!(entry.page_count, result.page_count);
+            assert_eq!(entry.file_size, Some(std::fs::metadata(&entry.output_path).unwrap().len()));
+            assert!(entry.exported_at >= before && entry.exported_at <= manifest.generated_at);
+        }
+        assert_eq!(manifest.files.iter().map(|entry| entry.word_count).collect::<Vec<_>>(), vec![4, 4]);
+
+        // Off by default: only the chapter files are written
+        let plain_dir = temp.path().join("plain");
+        let content = manuscript(vec![scene("s1", 1, "Alone.")]);
+        let export_options = options(ExportFormat::Markdown, PathBuf::from("unused.md"));
+        tokio_test::block_on(service.export_chapters_separately(content, export_options, &plain_dir)).unwrap();
+        assert!(!plain_dir.join(EXPORT_MANIFEST_FILE).exists());
+    }
+
+    #[test]
+    fn test_stored_comments_are_exported_in_position_order() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("comments.md");
+        let service = ExportService::new();
+        let mut content = manuscript(vec![scene("s1", 1, "The gun on the wall."), scene("s2", 2, "It fired.")]);
+        content.scenes[0].comments.push(CommentContent {
+            id: "c-local".to_string(),
+            text: "Sent by the editor".to_string(),
+            position: 10,
+            author: None,
+            timestamp: Utc::now(),
+        });
+
+        let stored = |id: &str, scene_id: &str, position: u32, text: &str| crate::db::SceneComment {
+            id: id.to_string(),
+            scene_id: scene_id.to_string(),
+            position,
+            text: text.to_string(),
+            author: Some("Jo".to_string()),
+            created_at: 1_700_000_000_000,
+        };
+        attach_stored_comments(&mut content, vec![
+            stored("c-local", "s1", 10, "Duplicate of the editor's copy"),
+            stored("c-late", "s1", 18, "Pay this off later"),
+            stored("c-early", "s1", 4, "Which gun?"),
+            stored("c-orphan", "gone", 0, "Scene was deleted"),
+        ]);
+
+        let ids: Vec<&str> = content.scenes[0].comments.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["c-early", "c-local", "c-late"]);
+        assert_eq!(content.scenes[0].comments[0].timestamp.timestamp_millis(), 1_700_000_000_000);
+        assert!(content.scenes[1].comments.is_empty());
+
+        let mut export_options = options(ExportFormat::Markdown, path.clone());
+        export_options.include_comments = true;
+        tokio_test::block_on(service.export_manuscript(content, export_options)).unwrap();
+        let output = std::fs::read_to_string(&path).unwrap();
+        assert!(output.contains("> **Comment:** Which gun?\n> **Comment:** Sent by the editor\n"));
+        assert!(!output.contains("Duplicate"));
+    }
+
+    #[test]
+    fn test_render_text_export_for_clipboard() {
+        let service = ExportService::new();
+        let content = manuscript(vec![scene("s1", 1, "The door opened."), scene("s2", 1, "Rain fell.")]);
+        let unused = PathBuf::from("unused");
+
+        let markdown = tokio_test::block_on(
+            service.render_text_export(content.clone(), options(ExportFormat::Markdown, unused.clone()))
+        ).unwrap();
+        assert!(markdown.contains("The door opened."));
+        assert!(markdown.contains("***"));
+        assert!(!unused.exists());
+
+        let docx = tokio_test::block_on(service.render_text_export(content, options(ExportFormat::Docx, unused)));
+        assert!(docx.unwrap_err().to_string().contains("Word Document is a binary format"));
+    }
+
+    #[test]
+    fn test_print_html_uses_page_settings() {
+        let mut stored = crate::analysis::tests::scene(0, Some(1), "<p>The door opened.</p><p>Rain &amp; wind.</p>");
+        stored.title = Some("Arrival".to_string());
+        stored.word_count = 5;
+        let next = crate::analysis::tests::scene(1, Some(2), "<p>Morning.</p>");
+        let mut manuscript = crate::db::tests::manuscript("The Long Night");
+        manuscript.comp_titles = Some("Rebecca, The Secret History".to_string());
+
+        let content = manuscript_content_from_scenes(&manuscript, &[stored, next]);
+        assert_eq!(content.scenes[0].content, "The door opened.\n\nRain & wind.");
+        assert!(content.scenes[0].is_chapter_end && content.scenes[1].is_chapter_start);
+        assert_eq!(content.metadata.comp_titles, vec!["Rebecca", "The Secret History"]);
+
+        let mut print_options = options(ExportFormat::PDF, PathBuf::from("unused.html"));
+        print_options.page_settings = PageSettings {
+            page_size: PageSize::A4,
+            margins: Margins { top: 0.5, bottom: 0.75, left: 1.5, right: 1.0 },
+            orientation: PageOrientation::Landscape,
+        };
+        print_options.page_numbers = true;
+
+        let html = ExportService::new().build_html_content(&content, &print_options).unwrap();
+        assert!(html.contains("@page { size: 11.69in 8.27in; margin: 0.5in 1in 0.75in 1.5in; @top-right { content: \"\" counter(page) \"\"; } }"));
+        assert!(html.contains("<h3>Arrival</h3>"));
+        assert!(html.contains("<p>Rain &amp; wind.</p>"));
+    }
+}
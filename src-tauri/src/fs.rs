@@ -1,7 +1,7 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
-use tauri::AppHandle;
+use tauri::{AppHandle, Manager};
 use tauri_plugin_dialog::DialogExt;
 use docx_rs::*;
 use pulldown_cmark::{Parser, html, Options, Event, Tag, TagEnd, HeadingLevel};
@@ -9,7 +9,8 @@ use html2md::parse_html;
 use regex::Regex;
 use std::fs;
 use chrono::Utc;
-use crate::error::{AppError, AppResult};
+use crate::error::{AppError, AppResult, ErrorCode};
+use crate::export::ParagraphStyle;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ContentReplacement {
@@ -52,6 +53,92 @@ pub struct FileMetadata {
     pub line_count: u32,
 }
 
+pub const DEFAULT_SCENE_BREAK_MARKERS: &[&str] = &[
+    "***", "* * *", "---", "- - -", "###", "# # #",
+    "◊", "◊ ◊ ◊", "§", "§ § §",
+];
+
+pub const DEFAULT_CHAPTER_PATTERNS: &[&str] = &[
+    r"(?i)^chapter\s+\d+",
+    r"(?i)^ch\.\s*\d+",
+    r"(?i)^part\s+\d+",
+    r"^\d+\.$",
+];
+
+pub const DEFAULT_MAX_FILE_SIZE_BYTES: u64 = 100 * 1024 * 1024;
+
+// Options controlling how imported files are split into chapters and scenes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImportOptions {
+    pub scene_break_markers: Vec<String>,
+    pub treat_h1_as_chapter: bool,
+    pub treat_h2_as_chapter: bool,
+    pub chapter_regexes: Vec<String>, // Matched against heading and paragraph text
+    pub merge_short_scenes: bool,
+    pub min_scene_words: u32, // Threshold used when merge_short_scenes is set
+    pub max_file_size_bytes: u64,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            scene_break_markers: DEFAULT_SCENE_BREAK_MARKERS.iter().map(|m| m.to_string()).collect(),
+            treat_h1_as_chapter: true,
+            treat_h2_as_chapter: true,
+            chapter_regexes: DEFAULT_CHAPTER_PATTERNS.iter().map(|p| p.to_string()).collect(),
+            merge_short_scenes: false,
+            min_scene_words: 100,
+            max_file_size_bytes: DEFAULT_MAX_FILE_SIZE_BYTES,
+        }
+    }
+}
+
+impl ImportOptions {
+    // The whole line must be a marker; runs of whitespace inside it are ignored
+    pub fn is_scene_break_marker(&self, line: &str) -> bool {
+        let normalized = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        !normalized.is_empty()
+            && self.scene_break_markers.iter().any(|marker| {
+                marker.split_whitespace().collect::<Vec<_>>().join(" ") == normalized
+            })
+    }
+
+    pub fn validate(&self) -> AppResult<()> {
+        if self.max_file_size_bytes == 0 {
+            return Err(AppError::validation_field(
+                "File size limit must be greater than zero".to_string(),
+                "max_file_size_bytes".to_string(),
+                self.max_file_size_bytes.to_string()
+            ));
+        }
+        for pattern in &self.chapter_regexes {
+            Regex::new(pattern)?;
+        }
+        Ok(())
+    }
+
+    // Compile once per import and pass to is_chapter_marker. Invalid patterns
+    // are skipped here; validate() is what rejects them.
+    pub fn compiled_chapter_regexes(&self) -> Vec<Regex> {
+        self.chapter_regexes
+            .iter()
+            .filter_map(|pattern| Regex::new(pattern).ok())
+            .collect()
+    }
+
+    fn is_chapter_heading(&self, chapter_regexes: &[Regex], level: u8, text: &str) -> bool {
+        (level == 1 && self.treat_h1_as_chapter)
+            || (level == 2 && self.treat_h2_as_chapter)
+            || is_chapter_marker(chapter_regexes, text)
+    }
+}
+
+pub fn is_chapter_marker(chapter_regexes: &[Regex], line: &str) -> bool {
+    let line = line.trim();
+    chapter_regexes.iter().any(|re| re.is_match(line))
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ImportProgress {
     pub stage: String,
@@ -87,7 +174,7 @@ fn validate_file_path(file_path: &str) -> AppResult<PathBuf> {
     Ok(path)
 }
 
-fn get_file_metadata(path: &Path) -> AppResult<(u64, String)> {
+fn get_file_metadata(path: &Path, max_file_size_bytes: u64) -> AppResult<(u64, String)> {
     let metadata = fs::metadata(path)
         .map_err(|e| AppError::file_system_with_path(
             format!("Cannot access file metadata: {}", e),
@@ -97,13 +184,12 @@ fn get_file_metadata(path: &Path) -> AppResult<(u64, String)> {
     
     let file_size = metadata.len();
     
-    // Check file size (limit to 100MB for safety)
-    if file_size > 100 * 1024 * 1024 {
+    if file_size > max_file_size_bytes {
         return Err(AppError::validation_field(
-            "File size exceeds 100MB limit",
-            "file_size",
-            &format!("{} bytes", file_size)
-        ));
+            format!("File size exceeds the {} limit", format_file_size(max_file_size_bytes)),
+            "file_size".to_string(),
+            format!("{} bytes", file_size)
+        ).with_code(ErrorCode::FileTooLarge));
     }
     
     let modified = metadata.modified()
@@ -116,12 +202,34 @@ fn get_file_metadata(path: &Path) -> AppResult<(u64, String)> {
     Ok((file_size, modified_str))
 }
 
+fn format_file_size(bytes: u64) -> String {
+    const MB: u64 = 1024 * 1024;
+    if bytes >= MB && bytes % MB == 0 {
+        format!("{}MB", bytes / MB)
+    } else if bytes >= MB {
+        format!("{:.1}MB", bytes as f64 / MB as f64)
+    } else {
+        format!("{} bytes", bytes)
+    }
+}
+
 // Replace content in single manuscript from file
 #[tauri::command]
-pub async fn replace_manuscript_content(_app: AppHandle, file_path: String) -> Result<ContentReplacement, String> {
-    let path = validate_file_path(&file_path).map_err(|e| e.to_string())?;
+pub async fn replace_manuscript_content(
+    _app: AppHandle,
+    file_path: String,
+    options: Option<ImportOptions>
+) -> Result<ContentReplacement, String> {
+    let options = options.unwrap_or_default();
+    options.validate().map_err(|e| e.to_string())?;
+
+    import_file(&file_path, &options).await.map_err(|e| e.to_string())
+}
+
+pub(crate) async fn import_file(file_path: &str, options: &ImportOptions) -> AppResult<ContentReplacement> {
+    let path = validate_file_path(file_path)?;
     
-    let (file_size, modified_time) = get_file_metadata(&path).map_err(|e| e.to_string())?;
+    let (file_size, modified_time) = get_file_metadata(&path, options.max_file_size_bytes)?;
     
     // Get file extension
     let extension = path
@@ -138,15 +246,19 @@ pub async fn replace_manuscript_content(_app: AppHandle, file_path: String) -> R
 
     // Import with appropriate handler
     let (content, mut metadata, warnings) = match extension.as_str() {
-        "txt" => import_text_file(&path).await.map_err(|e| e.to_string())?,
-        "md" | "markdown" => import_markdown_file(&path).await.map_err(|e| e.to_string())?,
-        "docx" => import_docx_file(&path).await.map_err(|e| e.to_string())?,
-        "doc" => import_doc_file(&path).await.map_err(|e| e.to_string())?,
-        "rtf" => import_rtf_file(&path).await.map_err(|e| e.to_string())?,
-        _ => return Err(format!(
-            "Unsupported file format: '.{}'. Supported formats: .txt, .md, .docx, .doc, .rtf", 
-            extension
-        )),
+        "txt" => import_text_file(&path, options).await?,
+        "md" | "markdown" => import_markdown_file(&path, options).await?,
+        "docx" => import_docx_file(&path).await?,
+        "doc" => import_doc_file(&path).await?,
+        "rtf" => import_rtf_file(&path, options).await?,
+        _ => return Err(AppError::validation_field(
+            format!(
+                "Unsupported file format: '.{}'. Supported formats: .txt, .md, .docx, .doc, .rtf",
+                extension
+            ),
+            "file_format".to_string(),
+            extension.clone()
+        ).with_code(ErrorCode::UnsupportedFormat)),
     };
 
     // Update metadata with file information
@@ -155,8 +267,8 @@ pub async fn replace_manuscript_content(_app: AppHandle, file_path: String) -> R
     metadata.line_count = content.lines().count() as u32;
 
     // Process content for scenes (no chapters needed for single manuscript)
-    let scenes = detect_scenes_from_content(&content);
-    let word_count = count_words_accurate(&content);
+    let scenes = detect_scenes_from_content(&content, options);
+    let word_count = crate::text::word_count(&content, true);
 
     Ok(ContentReplacement {
         filename,
@@ -169,8 +281,313 @@ pub async fn replace_manuscript_content(_app: AppHandle, file_path: String) -> R
     })
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileImportResult {
+    pub file_path: String,
+    pub content_hash: String,
+    pub duplicate_of: Option<String>, // Earlier file in the batch with identical bytes
+    pub replacement: Option<ContentReplacement>, // None when skipped as a duplicate
+    pub warnings: Vec<String>,
+}
+
+// Per-file result of a batch import, so failures are reported alongside successes
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum BatchImportOutcome {
+    Imported(FileImportResult),
+    Failed {
+        file_path: String,
+        filename: String,
+        error: AppError,
+    },
+}
+
+impl BatchImportOutcome {
+    fn failed(file_path: &str, error: AppError) -> Self {
+        let filename = Path::new(file_path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| file_path.to_string());
+
+        Self::Failed {
+            file_path: file_path.to_string(),
+            filename,
+            error,
+        }
+    }
+}
+
+// Hash of the raw file bytes, used to spot duplicates within a batch
+fn content_hash(bytes: &[u8]) -> String {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+async fn batch_import(file_paths: &[String], options: &ImportOptions) -> Vec<BatchImportOutcome> {
+    let mut results = Vec::new();
+    let mut seen: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+
+    for file_path in file_paths {
+        // Apply the size limit before the file is read for hashing
+        let checked = validate_file_path(file_path)
+            .and_then(|path| get_file_metadata(&path, options.max_file_size_bytes));
+        if let Err(e) = checked {
+            results.push(BatchImportOutcome::failed(file_path, e));
+            continue;
+        }
+
+        let bytes = match tokio::fs::read(file_path).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                results.push(BatchImportOutcome::failed(file_path, AppError::file_system_with_path(
+                    format!("Failed to read file: {}", e),
+                    "read",
+                    PathBuf::from(file_path)
+                )));
+                continue;
+            }
+        };
+        let hash = content_hash(&bytes);
+
+        if let Some(original) = seen.get(&hash) {
+            results.push(BatchImportOutcome::Imported(FileImportResult {
+                file_path: file_path.clone(),
+                content_hash: hash,
+                duplicate_of: Some(original.clone()),
+                replacement: None,
+                warnings: vec![format!("Skipped: identical to '{}'", original)],
+            }));
+            continue;
+        }
+
+        match import_file(file_path, options).await {
+            Ok(replacement) => {
+                seen.insert(hash.clone(), file_path.clone());
+                results.push(BatchImportOutcome::Imported(FileImportResult {
+                    file_path: file_path.clone(),
+                    content_hash: hash,
+                    duplicate_of: None,
+                    warnings: replacement.import_warnings.clone(),
+                    replacement: Some(replacement),
+                }));
+            }
+            Err(e) => results.push(BatchImportOutcome::failed(file_path, e)),
+        }
+    }
+
+    results
+}
+
+#[tauri::command]
+pub async fn batch_import_files(
+    _app: AppHandle,
+    mut file_paths: Vec<String>,
+    options: Option<ImportOptions>
+) -> Result<Vec<BatchImportOutcome>, String> {
+    let options = options.unwrap_or_default();
+    options.validate().map_err(|e| e.to_string())?;
+
+    natural_sort(&mut file_paths);
+    Ok(batch_import(&file_paths, &options).await)
+}
+
+// Compare names so digit runs order by value: "ch2" before "ch10"
+fn natural_cmp(a: &str, b: &str) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+    use std::iter::Peekable;
+    use std::str::Chars;
+
+    fn take_digits(chars: &mut Peekable<Chars<'_>>) -> String {
+        let mut digits = String::new();
+        while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+            digits.push(c);
+        }
+        digits
+    }
+
+    let (mut a_chars, mut b_chars) = (a.chars().peekable(), b.chars().peekable());
+    loop {
+        match (a_chars.peek().copied(), b_chars.peek().copied()) {
+            (None, None) => return a.cmp(b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let x_digits = take_digits(&mut a_chars);
+                let y_digits = take_digits(&mut b_chars);
+                let x_value = x_digits.trim_start_matches('0');
+                let y_value = y_digits.trim_start_matches('0');
+
+                let ordering = x_value.len().cmp(&y_value.len()).then_with(|| x_value.cmp(y_value));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(x), Some(y)) => {
+                let ordering = x.to_lowercase().cmp(y.to_lowercase());
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                a_chars.next();
+                b_chars.next();
+            }
+        }
+    }
+}
+
+// Sort paths by file name in natural order, falling back to the full path
+pub fn natural_sort<P: AsRef<Path>>(paths: &mut [P]) {
+    let file_name = |path: &P| {
+        path.as_ref()
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    };
+
+    paths.sort_by(|a, b| {
+        natural_cmp(&file_name(a), &file_name(b))
+            .then_with(|| natural_cmp(&a.as_ref().to_string_lossy(), &b.as_ref().to_string_lossy()))
+    });
+}
+
+// Extensions picked up when importing a folder of chapter files
+const DIRECTORY_IMPORT_EXTENSIONS: &[&str] = &["md", "markdown", "txt"];
+
+// Each text file in the directory becomes one chapter, titled after the file
+async fn import_directory(dir_path: &str, options: &ImportOptions) -> AppResult<FileImportResult> {
+    let dir = PathBuf::from(dir_path);
+    if !dir.is_absolute() {
+        return Err(AppError::validation_field("Directory path must be absolute", "dir_path", dir_path));
+    }
+    if !dir.is_dir() {
+        return Err(AppError::not_found(format!("Directory not found: {}", dir_path)));
+    }
+
+    let entries = fs::read_dir(&dir)
+        .map_err(|e| AppError::file_system_with_path(
+            format!("Failed to read directory: {}", e),
+            "read_dir".to_string(),
+            dir.clone()
+        ))?;
+
+    let mut warnings = Vec::new();
+    let mut files = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        if DIRECTORY_IMPORT_EXTENSIONS.contains(&extension.as_str()) {
+            files.push(path);
+        } else {
+            warnings.push(format!("Skipped non-text file '{}'", entry.file_name().to_string_lossy()));
+        }
+    }
+    natural_sort(&mut files);
+
+    if files.is_empty() {
+        return Err(AppError::validation_field(
+            "Directory contains no .md or .txt files",
+            "dir_path",
+            dir_path
+        ));
+    }
+
+    let mut content = String::new();
+    let mut scenes = Vec::new();
+    let mut file_size = 0;
+
+    for (index, path) in files.iter().enumerate() {
+        let file_path = path.to_string_lossy().to_string();
+        let replacement = import_file(&file_path, options).await?;
+
+        let chapter_title = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| replacement.filename.clone());
+        let heading = format!("<h1>{}</h1>\n", html_escape(&chapter_title));
+        let chapter_number = Some(index as u32 + 1);
+
+        content.push_str(&heading);
+        content.push_str(&replacement.content);
+        if !replacement.content.ends_with('\n') {
+            content.push('\n');
+        }
+        file_size += replacement.metadata.file_size;
+        warnings.extend(
+            replacement.import_warnings
+                .into_iter()
+                .map(|warning| format!("{}: {}", replacement.filename, warning))
+        );
+
+        // The file is the chapter, whatever headings it contains
+        for (scene_index, mut scene) in replacement.scenes.into_iter().enumerate() {
+            scene.chapter_number = chapter_number;
+            if scene_index == 0 {
+                scene.content = format!("{}{}", heading, scene.content);
+                scene.title = Some(chapter_title.clone());
+                scene.break_type = SceneBreakType::ChapterStart;
+            } else if matches!(scene.break_type, SceneBreakType::ChapterStart) {
+                scene.break_type = SceneBreakType::SceneBreak;
+            }
+            scenes.push(scene);
+        }
+    }
+
+    let title = dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| dir_path.to_string());
+    let hash = content_hash(content.as_bytes());
+
+    Ok(FileImportResult {
+        file_path: dir_path.to_string(),
+        content_hash: hash,
+        duplicate_of: None,
+        warnings: warnings.clone(),
+        replacement: Some(ContentReplacement {
+            filename: title.clone(),
+            word_count: crate::text::word_count(&content, true),
+            format: "directory".to_string(),
+            scenes,
+            metadata: FileMetadata {
+                author: None,
+                title: Some(title),
+                created: None,
+                modified: None,
+                has_formatting: true,
+                encoding: "UTF-8".to_string(),
+                file_size,
+                line_count: content.lines().count() as u32,
+            },
+            import_warnings: warnings,
+            content,
+        }),
+    })
+}
+
+#[tauri::command]
+pub async fn import_manuscript_directory(
+    _app: AppHandle,
+    dir_path: String,
+    options: Option<ImportOptions>
+) -> Result<FileImportResult, String> {
+    let options = options.unwrap_or_default();
+    options.validate().map_err(|e| e.to_string())?;
+
+    import_directory(&dir_path, &options).await.map_err(|e| e.to_string())
+}
+
 // Enhanced text file import with encoding detection
-async fn import_text_file(path: &Path) -> AppResult<(String, FileMetadata, Vec<String>)> {
+async fn import_text_file(path: &Path, options: &ImportOptions) -> AppResult<(String, FileMetadata, Vec<String>)> {
     let file_bytes = tokio::fs::read(path).await
         .map_err(|e| AppError::file_system_with_path(
             format!("Failed to read text file: {}", e),
@@ -217,7 +634,7 @@ async fn import_text_file(path: &Path) -> AppResult<(String, FileMetadata, Vec<S
     };
 
     // Convert to HTML paragraphs with scene break detection
-    let html_content = convert_text_to_html(&content);
+    let html_content = convert_text_to_html(&content, options);
 
     let metadata = FileMetadata {
         author: extract_author_from_text(&content),
@@ -234,7 +651,7 @@ async fn import_text_file(path: &Path) -> AppResult<(String, FileMetadata, Vec<S
 }
 
 // Enhanced markdown import with comprehensive parsing
-async fn import_markdown_file(path: &Path) -> AppResult<(String, FileMetadata, Vec<String>)> {
+async fn import_markdown_file(path: &Path, options: &ImportOptions) -> AppResult<(String, FileMetadata, Vec<String>)> {
     let markdown_content = tokio::fs::read_to_string(path).await
         .map_err(|e| AppError::file_system_with_path(
             format!("Failed to read markdown file: {}", e),
@@ -245,23 +662,60 @@ async fn import_markdown_file(path: &Path) -> AppResult<(String, FileMetadata, V
     let mut warnings = Vec::new();
     
     // Set up pulldown-cmark options for comprehensive parsing
-    let mut options = Options::empty();
-    options.insert(Options::ENABLE_STRIKETHROUGH);
-    options.insert(Options::ENABLE_TABLES);
-    options.insert(Options::ENABLE_FOOTNOTES);
-    options.insert(Options::ENABLE_TASKLISTS);
-    options.insert(Options::ENABLE_SMART_PUNCTUATION);
+    let mut md_options = Options::empty();
+    md_options.insert(Options::ENABLE_STRIKETHROUGH);
+    md_options.insert(Options::ENABLE_TABLES);
+    md_options.insert(Options::ENABLE_FOOTNOTES);
+    md_options.insert(Options::ENABLE_TASKLISTS);
+    md_options.insert(Options::ENABLE_SMART_PUNCTUATION);
     
-    let parser = Parser::new_ext(&markdown_content, options);
+    let parser = Parser::new_ext(&markdown_content, md_options);
     
     // Enhanced HTML conversion with scene break detection
     let mut html_output = String::new();
     let _in_scene_break = false;
     
     let events: Vec<Event> = parser.collect();
+    let mut in_code_block = false;
     
     for (_i, event) in events.iter().enumerate() {
         match event {
+            Event::Start(Tag::List(Some(start))) if *start != 1 => {
+                html_output.push_str(&format!("<ol start=\"{}\">", start));
+            }
+            Event::Start(Tag::List(Some(_))) => {
+                html_output.push_str("<ol>");
+            }
+            Event::Start(Tag::List(None)) => {
+                html_output.push_str("<ul>");
+            }
+            Event::End(TagEnd::List(ordered)) => {
+                html_output.push_str(if *ordered { "</ol>" } else { "</ul>" });
+            }
+            Event::Start(Tag::Item) => {
+                html_output.push_str("<li>");
+            }
+            Event::End(TagEnd::Item) => {
+                html_output.push_str("</li>");
+            }
+            Event::Start(Tag::BlockQuote { .. }) => {
+                html_output.push_str("<blockquote>");
+            }
+            Event::End(TagEnd::BlockQuote { .. }) => {
+                html_output.push_str("</blockquote>");
+            }
+            Event::Start(Tag::CodeBlock(_)) => {
+                in_code_block = true;
+                html_output.push_str("<pre><code>");
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                in_code_block = false;
+                html_output.push_str("</code></pre>");
+            }
+            Event::Text(text) if in_code_block => {
+                // Code is literal: no scene-break detection, whitespace kept
+                html_output.push_str(&html_escape(text));
+            }
             Event::Start(Tag::Heading { level: HeadingLevel::H1, .. }) => {
                 html_output.push_str("<h1>");
             }
@@ -307,7 +761,7 @@ async fn import_markdown_file(path: &Path) -> AppResult<(String, FileMetadata, V
             Event::Text(text) => {
                 // Check for manual scene breaks (---, ***, etc.)
                 let text_str = text.to_string();
-                if is_scene_break_marker(&text_str) {
+                if options.is_scene_break_marker(&text_str) {
                     html_output.push_str("<div class=\"scene-break\">***</div>");
                 } else {
                     html_output.push_str(&html_escape(&text_str));
@@ -332,7 +786,7 @@ async fn import_markdown_file(path: &Path) -> AppResult<(String, FileMetadata, V
     }
 
     // Clean up extra whitespace and empty paragraphs
-    let cleaned_html = clean_html_content(&html_output);
+    let cleaned_html = clean_html_content(&html_output)?;
 
     let metadata = FileMetadata {
         author: extract_author_from_markdown(&markdown_content),
@@ -349,7 +803,7 @@ async fn import_markdown_file(path: &Path) -> AppResult<(String, FileMetadata, V
 }
 
 // Enhanced RTF import with proper text extraction
-async fn import_rtf_file(path: &Path) -> AppResult<(String, FileMetadata, Vec<String>)> {
+async fn import_rtf_file(path: &Path, options: &ImportOptions) -> AppResult<(String, FileMetadata, Vec<String>)> {
     let rtf_content = tokio::fs::read_to_string(path).await
         .map_err(|e| AppError::file_system_with_path(
             format!("Failed to read RTF file: {}", e),
@@ -376,7 +830,7 @@ async fn import_rtf_file(path: &Path) -> AppResult<(String, FileMetadata, Vec<St
     }
     
     // Convert to structured HTML
-    let html_content = convert_rtf_to_html(&plain_text, &formatting_info);
+    let html_content = convert_rtf_to_html(&plain_text, &formatting_info, options);
 
     let metadata = FileMetadata {
         author: formatting_info.author,
@@ -542,7 +996,7 @@ fn process_rtf_control_word(
     }
 }
 
-fn convert_rtf_to_html(plain_text: &str, formatting_info: &RtfFormattingInfo) -> String {
+fn convert_rtf_to_html(plain_text: &str, formatting_info: &RtfFormattingInfo, options: &ImportOptions) -> String {
     let mut html = String::new();
     
     // If we have paragraph information, use it
@@ -570,7 +1024,7 @@ fn convert_rtf_to_html(plain_text: &str, formatting_info: &RtfFormattingInfo) ->
         }
     } else {
         // Fall back to simple paragraph detection
-        html = convert_text_to_html(plain_text);
+        html = convert_text_to_html(plain_text, options);
     }
     
     html
@@ -593,12 +1047,20 @@ async fn import_docx_file(path: &Path) -> AppResult<(String, FileMetadata, Vec<S
         ))?;
 
     let mut content = String::new();
-    let warnings = Vec::new();
+    let mut warnings = Vec::new();
+    let mut image_count = 0;
 
     for document_child in docx.document.children {
         if let DocumentChild::Paragraph(paragraph) = document_child {
+            let heading_level = paragraph.property.style
+                .as_ref()
+                .and_then(|style| docx_heading_level(&style.val));
             let mut para_text = String::new();
+            let mut para_images = Vec::new();
+            let mut has_text = false;
             let mut has_formatting = false;
+            let mut page_break_before = false;
+            let mut page_break_after = false;
 
             for child in paragraph.children {
                 if let ParagraphChild::Run(run) = child {
@@ -619,9 +1081,31 @@ async fn import_docx_file(path: &Path) -> AppResult<(String, FileMetadata, Vec<S
 
                     for run_child in run.children {
                         match run_child {
-                            RunChild::Text(text) => run_text.push_str(&text.text),
+                            // Escape before wrapping so document text can't inject markup
+                            RunChild::Text(text) => {
+                                has_text |= !text.text.trim().is_empty();
+                                run_text.push_str(&html_escape(&text.text));
+                            }
                             RunChild::Tab(_) => run_text.push('\t'),
+                            // Page breaks usually sit at a paragraph edge; keep them
+                            // on the side of the text they were found on
+                            RunChild::Break(br) if is_docx_page_break(&br) => {
+                                if has_text {
+                                    page_break_after = true;
+                                } else {
+                                    page_break_before = true;
+                                }
+                            }
                             RunChild::Break(_) => run_text.push('\n'),
+                            RunChild::Drawing(_) => {
+                                // Images aren't imported; keep a marker where they were
+                                image_count += 1;
+                                para_images.push(image_count);
+                                run_text.push_str(&format!(
+                                    "<span class=\"image-placeholder\" data-image=\"image-{}\">[image]</span>",
+                                    image_count
+                                ));
+                            }
                             _ => {}
                         }
                     }
@@ -638,12 +1122,36 @@ async fn import_docx_file(path: &Path) -> AppResult<(String, FileMetadata, Vec<S
                 }
             }
 
-            if !para_text.trim().is_empty() {
-                content.push_str(&format!("<p>{}</p>\n", para_text.trim()));
+            if page_break_before {
+                content.push_str(DOCX_PAGE_BREAK);
+            }
+
+            if !has_text && !para_images.is_empty() {
+                // A paragraph holding only images becomes figure placeholders
+                for image in para_images {
+                    content.push_str(&format!(
+                        "<figure data-image=\"image-{}\">[image]</figure>\n",
+                        image
+                    ));
+                }
+            } else if !para_text.trim().is_empty() {
+                let tag = heading_level.map_or("p".to_string(), |level| format!("h{}", level));
+                content.push_str(&format!("<{}>{}</{}>\n", tag, para_text.trim(), tag));
+            }
+
+            if page_break_after {
+                content.push_str(DOCX_PAGE_BREAK);
             }
         }
     }
 
+    if image_count > 0 {
+        warnings.push(format!(
+            "{} embedded image(s) were replaced with placeholders",
+            image_count
+        ));
+    }
+
     let metadata = FileMetadata {
         author: None, // Could extract from docx.core_properties if available
         title: None,  // Could extract from docx.core_properties if available
@@ -658,6 +1166,33 @@ async fn import_docx_file(path: &Path) -> AppResult<(String, FileMetadata, Vec<S
     Ok((content, metadata, warnings))
 }
 
+// Explicit page breaks in Word manuscripts separate chapters
+const DOCX_PAGE_BREAK: &str = "<div class=\"scene-break\" data-break=\"page\"></div>\n";
+
+// docx-rs keeps the break type private, so read it from the serialized form
+fn is_docx_page_break(br: &Break) -> bool {
+    serde_json::to_string(br)
+        .map(|json| json.to_lowercase().contains("\"page\""))
+        .unwrap_or(false)
+}
+
+// Word's built-in heading styles have ids like "Heading1"; some writers emit
+// "heading 1" instead
+fn docx_heading_level(style_id: &str) -> Option<u8> {
+    let normalized: String = style_id
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '_' && *c != '-')
+        .collect::<String>()
+        .to_lowercase();
+
+    match normalized.as_str() {
+        "heading1" => Some(1),
+        "heading2" => Some(2),
+        "heading3" => Some(3),
+        _ => None,
+    }
+}
+
 // DOC file import with clear error message
 async fn import_doc_file(path: &Path) -> AppResult<(String, FileMetadata, Vec<String>)> {
     let filename = path.file_name()
@@ -671,11 +1206,12 @@ async fn import_doc_file(path: &Path) -> AppResult<(String, FileMetadata, Vec<St
         ),
         "file_format".to_string(),
         ".doc files are not supported".to_string()
-    ))
+    ).with_code(ErrorCode::UnsupportedFormat))
 }
 
 // Helper functions for content processing
-fn convert_text_to_html(text: &str) -> String {
+fn convert_text_to_html(text: &str, options: &ImportOptions) -> String {
+    let chapter_regexes = options.compiled_chapter_regexes();
     let mut html = String::new();
     let _in_scene_break = false;
     
@@ -686,9 +1222,9 @@ fn convert_text_to_html(text: &str) -> String {
             continue; // Skip empty lines
         }
         
-        if is_scene_break_marker(trimmed) {
+        if options.is_scene_break_marker(trimmed) {
             html.push_str("<div class=\"scene-break\">***</div>\n");
-        } else if is_chapter_marker(trimmed) {
+        } else if is_chapter_marker(&chapter_regexes, trimmed) {
             html.push_str(&format!("<h2>{}</h2>\n", html_escape(trimmed)));
         } else {
             html.push_str(&format!("<p>{}</p>\n", html_escape(trimmed)));
@@ -698,31 +1234,6 @@ fn convert_text_to_html(text: &str) -> String {
     html
 }
 
-fn is_scene_break_marker(line: &str) -> bool {
-    let patterns = [
-        "***", "* * *", "---", "- - -", "###", "# # #",
-        "◊", "◊ ◊ ◊", "§", "§ § §"
-    ];
-    
-    let trimmed = line.trim();
-    patterns.iter().any(|&pattern| trimmed == pattern) ||
-    (trimmed.chars().all(|c| c == '*' || c == '-' || c == '#' || c.is_whitespace()) && 
-     trimmed.len() >= 3)
-}
-
-fn is_chapter_marker(line: &str) -> bool {
-    let chapter_patterns = [
-        r"(?i)^chapter\s+\d+",
-        r"(?i)^ch\.\s*\d+",
-        r"(?i)^part\s+\d+",
-        r"^\d+\.$",
-    ];
-    
-    chapter_patterns.iter().any(|&pattern| {
-        Regex::new(pattern).unwrap().is_match(line.trim())
-    })
-}
-
 fn html_escape(text: &str) -> String {
     text.replace('&', "&amp;")
         .replace('<', "&lt;")
@@ -731,47 +1242,209 @@ fn html_escape(text: &str) -> String {
         .replace('\'', "&#39;")
 }
 
-fn clean_html_content(html: &str) -> String {
-    // Remove empty paragraphs and excessive whitespace
-    let re_empty_p = Regex::new(r"<p>\s*</p>").unwrap();
-    let re_extra_whitespace = Regex::new(r"\s+").unwrap();
+fn is_block_tag(tag: &str) -> bool {
+    let name: String = tag
+        .trim_start_matches('<')
+        .trim_start_matches('/')
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+
+    matches!(
+        name.as_str(),
+        "p" | "div" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "ul" | "ol" | "li"
+            | "blockquote" | "pre" | "table" | "tr" | "td" | "th" | "br" | "hr"
+    )
+}
+
+fn clean_html_content(html: &str) -> AppResult<String> {
+    // Remove empty paragraphs, then normalize whitespace one text node at a time.
+    // Whitespace between block elements becomes a newline, whitespace inside text
+    // collapses to a single space, and <pre> blocks are left untouched.
+    let re_empty_p = Regex::new(r"<p>\s*</p>")?;
+    let re_extra_whitespace = Regex::new(r"\s+")?;
+    let re_token = Regex::new(r"(?is)<pre[\s>].*?</pre>|<[^>]*>")?;
     
     let cleaned = re_empty_p.replace_all(html, "");
-    re_extra_whitespace.replace_all(&cleaned, " ").trim().to_string()
+    let mut result = String::with_capacity(cleaned.len());
+    let mut previous_is_block = true;
+    let mut last_end = 0;
+    
+    let push_text = |result: &mut String, text: &str, previous_is_block: bool, next_is_block: bool| {
+        if text.is_empty() {
+            return;
+        }
+        if text.trim().is_empty() {
+            result.push(if previous_is_block && next_is_block { '\n' } else { ' ' });
+            return;
+        }
+        
+        let mut text = re_extra_whitespace.replace_all(text, " ").to_string();
+        if previous_is_block {
+            text = text.trim_start().to_string();
+        }
+        if next_is_block {
+            text = text.trim_end().to_string();
+        }
+        result.push_str(&text);
+    };
+    
+    for token in re_token.find_iter(&cleaned) {
+        let token_is_block = is_block_tag(token.as_str());
+        push_text(&mut result, &cleaned[last_end..token.start()], previous_is_block, token_is_block);
+        result.push_str(token.as_str());
+        previous_is_block = token_is_block;
+        last_end = token.end();
+    }
+    push_text(&mut result, &cleaned[last_end..], previous_is_block, true);
+    
+    Ok(result.trim().to_string())
 }
 
 
 // Detect scenes directly from content for single manuscript
-fn detect_scenes_from_content(content: &str) -> Vec<SceneInfo> {
-    detect_scenes_in_content(content)
+fn detect_scenes_from_content(content: &str, options: &ImportOptions) -> Vec<SceneInfo> {
+    let scenes = detect_scenes_in_content(content, options);
+
+    if options.merge_short_scenes {
+        merge_short_scenes(scenes, options.min_scene_words)
+    } else {
+        scenes
+    }
 }
 
-fn detect_scenes_in_content(content: &str) -> Vec<SceneInfo> {
+// Fold scenes below the word threshold into the scene before them. Scenes that
+// open a chapter are never merged, so chapter boundaries are preserved.
+fn merge_short_scenes(scenes: Vec<SceneInfo>, min_words: u32) -> Vec<SceneInfo> {
+    let mut merged: Vec<SceneInfo> = Vec::with_capacity(scenes.len());
+
+    for scene in scenes {
+        let mergeable = scene.word_count < min_words
+            && !matches!(scene.break_type, SceneBreakType::ChapterStart);
+
+        match merged.last_mut() {
+            Some(previous) if mergeable && previous.chapter_number == scene.chapter_number => {
+                previous.content.push_str(&scene.content);
+                previous.word_count += scene.word_count;
+            }
+            _ => merged.push(scene),
+        }
+    }
+
+    merged
+}
+
+struct SceneBuilder {
+    content: String,
+    has_body: bool,
+    chapter_number: Option<u32>,
+    break_type: SceneBreakType,
+}
+
+impl SceneBuilder {
+    fn new(chapter_number: Option<u32>, break_type: SceneBreakType) -> Self {
+        Self { content: String::new(), has_body: false, chapter_number, break_type }
+    }
+
+    fn finish(self, scenes: &mut Vec<SceneInfo>) {
+        let trimmed = self.content.trim();
+        if trimmed.is_empty() {
+            return;
+        }
+
+        scenes.push(SceneInfo {
+            title: extract_scene_title(trimmed),
+            content: trimmed.to_string(),
+            word_count: crate::text::word_count(trimmed, true),
+            chapter_number: self.chapter_number,
+            break_type: self.break_type,
+        });
+    }
+}
+
+// Scenes split at scene-break markers; chapter headings (per the import options)
+// start a new chapter. A heading stays with the content that follows it, so the
+// implicit break the Markdown importer adds after headings doesn't leave
+// heading-only scenes.
+fn detect_scenes_in_content(content: &str, options: &ImportOptions) -> Vec<SceneInfo> {
     let mut scenes = Vec::new();
-    
-    // Split by scene break markers
-    let scene_break_regex = Regex::new(r#"<div class="scene-break"[^>]*>.*?</div>"#).unwrap();
-    let parts: Vec<&str> = scene_break_regex.split(content).collect();
-    
-    for (i, part) in parts.iter().enumerate() {
-        let trimmed = part.trim();
-        if !trimmed.is_empty() {
-            let title = extract_scene_title(trimmed);
-            
-            scenes.push(SceneInfo {
-                title,
-                content: trimmed.to_string(),
-                word_count: count_words_accurate(trimmed),
-                chapter_number: None, // Will be set by caller
-                break_type: if i == 0 {
-                    SceneBreakType::ChapterStart
-                } else {
-                    SceneBreakType::SceneBreak
-                },
-            });
+    let block_regex = Regex::new(
+        r#"(?is)<div class="scene-break"[^>]*>.*?</div>|<(h([1-6])|p)\b[^>]*>(.*?)</(?:h[1-6]|p)>"#
+    ).unwrap();
+    let tag_regex = Regex::new(r"<[^>]*>").unwrap();
+    let chapter_regexes = options.compiled_chapter_regexes();
+
+    let mut chapter: Option<u32> = None;
+    let mut current = SceneBuilder::new(None, SceneBreakType::ChapterStart);
+    let mut last_end = 0;
+    // Set when a page break has already opened the current chapter, so a
+    // heading right after it doesn't count as another one
+    let mut opened_by_page_break = false;
+
+    for block in block_regex.captures_iter(content) {
+        let whole = block.get(0).unwrap();
+        let between = &content[last_end..whole.start()];
+        current.has_body |= !tag_regex.replace_all(between, "").trim().is_empty();
+        current.content.push_str(between);
+        opened_by_page_break &= !current.has_body;
+        last_end = whole.end();
+
+        if block.get(1).is_none() {
+            if whole.as_str().contains("data-break=\"page\"") {
+                // Page break: the next content starts a new chapter
+                if current.has_body {
+                    // Text before the first break is the first chapter
+                    if chapter.is_none() {
+                        chapter = Some(1);
+                        current.chapter_number = chapter;
+                    }
+                    chapter = chapter.map(|c| c + 1);
+                    std::mem::replace(&mut current, SceneBuilder::new(chapter, SceneBreakType::ChapterStart))
+                        .finish(&mut scenes);
+                    opened_by_page_break = true;
+                }
+                continue;
+            }
+
+            // Scene break: only start a new scene once this one has a body
+            if current.has_body {
+                std::mem::replace(&mut current, SceneBuilder::new(chapter, SceneBreakType::SceneBreak))
+                    .finish(&mut scenes);
+            }
+            continue;
+        }
+
+        let level: u8 = block.get(2).and_then(|l| l.as_str().parse().ok()).unwrap_or(0);
+        let text = tag_regex.replace_all(&block[3], "");
+        let is_chapter = if level > 0 {
+            options.is_chapter_heading(&chapter_regexes, level, &text)
+        } else {
+            is_chapter_marker(&chapter_regexes, &text)
+        };
+
+        if is_chapter && opened_by_page_break {
+            // Title of the chapter the page break already started
+            opened_by_page_break = false;
+        } else if is_chapter {
+            let next_chapter = chapter.map_or(1, |c| c + 1);
+            chapter = Some(next_chapter);
+            if current.has_body {
+                std::mem::replace(&mut current, SceneBuilder::new(chapter, SceneBreakType::ChapterStart))
+                    .finish(&mut scenes);
+            } else {
+                current.chapter_number = chapter;
+                current.break_type = SceneBreakType::ChapterStart;
+            }
+        } else if !text.trim().is_empty() {
+            current.has_body = true;
         }
+        current.content.push_str(whole.as_str());
     }
-    
+
+    current.content.push_str(&content[last_end..]);
+    current.finish(&mut scenes);
+
     scenes
 }
 
@@ -851,18 +1524,6 @@ fn extract_title_from_markdown(markdown: &str) -> Option<String> {
     None
 }
 
-fn count_words_accurate(text: &str) -> u32 {
-    // Remove HTML tags for accurate counting
-    let re = Regex::new(r"<[^>]*>").unwrap();
-    let clean_text = re.replace_all(text, " ");
-    
-    // Split on whitespace and filter empty strings
-    clean_text
-        .split_whitespace()
-        .filter(|word| !word.trim().is_empty())
-        .count() as u32
-}
-
 // Export functions (keeping existing ones and enhancing DOCX)
 #[tauri::command]
 pub async fn export_manuscript_file(
@@ -870,8 +1531,10 @@ pub async fn export_manuscript_file(
     content: String,
     file_path: String,
     format: String,
+    paragraph_style: Option<ParagraphStyle>,
 ) -> Result<(), String> {
     let path = validate_file_path(&file_path).map_err(|e| e.to_string())?;
+    let paragraph_style = paragraph_style.unwrap_or_default();
     
     match format.as_str() {
         "txt" => {
@@ -881,13 +1544,13 @@ pub async fn export_manuscript_file(
                 .map_err(|e| format!("Failed to export as text: {}", e))?;
         }
         "md" | "markdown" => {
-            let markdown = parse_html(&content);
+            let markdown = html_to_markdown(&content);
             tokio::fs::write(&path, markdown)
                 .await
                 .map_err(|e| format!("Failed to export as Markdown: {}", e))?;
         }
         "html" => {
-            let styled_html = create_styled_html(&content);
+            let styled_html = create_styled_html(&content, &paragraph_style);
             tokio::fs::write(&path, styled_html)
                 .await
                 .map_err(|e| format!("Failed to export as HTML: {}", e))?;
@@ -992,7 +1655,7 @@ async fn export_as_docx_enhanced(content: &str, path: &Path) -> Result<(), Strin
                     }
                 }
                 "div" => {
-                    if captures.get(3).map_or(false, |m| m.as_str().contains("scene-break")) {
+                    if captures.get(3).is_some_and(|m| m.as_str().contains("scene-break")) {
                         // Scene break - add centered paragraph with asterisks
                         let scene_break_para = Paragraph::new()
                             .add_run(Run::new().add_text("***"))
@@ -1044,7 +1707,7 @@ fn create_docx_paragraph(text: &str, formatting_stack: &[String]) -> Paragraph {
     para.add_run(run)
 }
 
-fn create_styled_html(content: &str) -> String {
+fn create_styled_html(content: &str, paragraph_style: &ParagraphStyle) -> String {
     format!(
         r#"<!DOCTYPE html>
 <html>
@@ -1062,8 +1725,7 @@ fn create_styled_html(content: &str) -> String {
             background: white;
         }}
         p {{ 
-            text-indent: 2em; 
-            margin-bottom: 1em; 
+            {}
             text-align: justify;
         }}
         h1, h2, h3 {{ 
@@ -1090,29 +1752,87 @@ fn create_styled_html(content: &str) -> String {
 {}
 </body>
 </html>"#,
+        paragraph_style.css(),
         content
     )
 }
 
-fn html_to_plain_text(html: &str) -> String {
-    // Remove HTML tags
-    let re = Regex::new(r"<[^>]*>").unwrap();
-    let text = re.replace_all(html, "");
-    
-    // Convert HTML entities
-    let text = text
-        .replace("&amp;", "&")
-        .replace("&lt;", "<")
-        .replace("&gt;", ">")
-        .replace("&quot;", "\"")
-        .replace("&#39;", "'");
-    
-    // Clean up spacing and add paragraph breaks
-    let re_space = Regex::new(r"\s+").unwrap();
-    let cleaned = re_space.replace_all(&text, " ");
-    
-    // Convert to paragraphs (very basic)
-    cleaned.trim().to_string()
+// Convert manuscript HTML to Markdown the importer reads back the same way.
+// Headings and scene breaks are emitted directly so levels and breaks survive;
+// everything between them goes through html2md.
+fn html_to_markdown(html: &str) -> String {
+    let re_block = Regex::new(r#"(?is)<h([1-6])[^>]*>(.*?)</h[1-6]\s*>|<div class="scene-break"[^>]*>(.*?)</div>"#).unwrap();
+    let mut blocks: Vec<String> = Vec::new();
+    let push_body = |blocks: &mut Vec<String>, body: &str| {
+        let markdown = parse_html(body);
+        if !markdown.trim().is_empty() {
+            blocks.push(markdown.trim().to_string());
+        }
+    };
+
+    let mut last_end = 0;
+    for captures in re_block.captures_iter(html) {
+        let whole = captures.get(0).unwrap();
+        push_body(&mut blocks, &html[last_end..whole.start()]);
+        last_end = whole.end();
+
+        if let Some(level) = captures.get(1) {
+            let level: usize = level.as_str().parse().unwrap_or(1);
+            let heading = parse_html(&captures[2]).trim().replace('\n', " ");
+            blocks.push(format!("{} {}", "#".repeat(level), heading));
+        } else if !captures[3].trim().is_empty() {
+            // Empty scene-break divs are the importer's implicit breaks after
+            // headings; only visible breaks become rules
+            blocks.push("---".to_string());
+        }
+    }
+    push_body(&mut blocks, &html[last_end..]);
+
+    let mut markdown = blocks.join("\n\n");
+    markdown.push('\n');
+    markdown
+}
+
+// Width used to center scene-break markers in plain text exports
+const PLAIN_TEXT_LINE_WIDTH: usize = 60;
+
+pub(crate) fn html_to_plain_text(html: &str) -> String {
+    // Source whitespace is insignificant in HTML; only tags produce line breaks
+    let re_source_space = Regex::new(r"\s+").unwrap();
+    let text = re_source_space.replace_all(html, " ");
+
+    // Scene breaks become a standalone *** paragraph
+    let re_scene_break = Regex::new(r#"(?i)<div class="scene-break"[^>]*>.*?</div>|<hr\s*/?>"#).unwrap();
+    let text = re_scene_break.replace_all(&text, "\n\n***\n\n");
+
+    // Line and block boundaries become newlines before tags are stripped
+    let re_line_break = Regex::new(r"(?i)<br\s*/?>").unwrap();
+    let text = re_line_break.replace_all(&text, "\n");
+    let re_block_end = Regex::new(r"(?i)</(?:p|h[1-6]|div|li|blockquote|pre)\s*>").unwrap();
+    let text = re_block_end.replace_all(&text, "\n\n");
+
+    // Remove remaining tags and decode entities
+    let re_tag = Regex::new(r"<[^>]*>").unwrap();
+    let text = re_tag.replace_all(&text, "");
+    let text = crate::text::strip_html(&text).text.replace('\u{00A0}', " ");
+
+    // Collapse whitespace within lines, keep at most one blank line between paragraphs
+    let re_space = Regex::new(r"[ \t\r\f\v]+").unwrap();
+    let lines: Vec<String> = text
+        .lines()
+        .map(|line| re_space.replace_all(line, " ").trim().to_string())
+        .collect();
+    let joined = lines.join("\n");
+    let re_blank_lines = Regex::new(r"\n{3,}").unwrap();
+    let collapsed = re_blank_lines.replace_all(&joined, "\n\n");
+
+    let padding = " ".repeat((PLAIN_TEXT_LINE_WIDTH - 3) / 2);
+    collapsed
+        .trim()
+        .lines()
+        .map(|line| if line == "***" { format!("{}***", padding) } else { line.to_string() })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 // File dialog functions (keeping existing implementations)
@@ -1156,7 +1876,7 @@ pub async fn save_file_dialog(
 
     dialog
         .set_title("Export Manuscript")
-        .set_file_name(&default_name)
+        .set_file_name(sanitize_filename(&default_name))
         .save_file(move |p| {
             let _ = tx.send(p);
         });
@@ -1166,26 +1886,598 @@ pub async fn save_file_dialog(
 }
 
 
+const MAX_FILENAME_CHARS: usize = 120;
+
+const RESERVED_DEVICE_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL",
+    "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+    "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+// Filename derived from user content such as a manuscript title, safe on all
+// platforms: characters illegal on Windows become '_', trailing dots and spaces
+// are dropped, length is capped and reserved device names are suffixed.
+pub fn sanitize_filename(title: &str) -> String {
+    let replaced: String = title
+        .chars()
+        .map(|c| match c {
+            '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+
+    let mut sanitized: String = replaced
+        .trim()
+        .chars()
+        .take(MAX_FILENAME_CHARS)
+        .collect();
+    sanitized = sanitized.trim_end_matches(|c: char| c == '.' || c.is_whitespace()).to_string();
+
+    if sanitized.is_empty() {
+        return "manuscript".to_string();
+    }
+
+    // Windows reserves device names regardless of extension ("CON.txt")
+    let stem = sanitized.split('.').next().unwrap_or("").trim_end();
+    if RESERVED_DEVICE_NAMES.iter().any(|name| name.eq_ignore_ascii_case(stem)) {
+        sanitized.insert(stem.len(), '_');
+    }
+
+    sanitized
+}
+
+// Backups default to <app data>/backups; a configured directory must be absolute
+// so the location never depends on the process working directory.
+fn resolve_backup_dir(app_data_dir: PathBuf, configured: Option<String>) -> AppResult<PathBuf> {
+    match configured {
+        Some(dir) => {
+            let path = PathBuf::from(&dir);
+            if !path.is_absolute() {
+                return Err(AppError::validation_field(
+                    "Backup directory must be absolute",
+                    "backup_dir",
+                    dir.as_str()
+                ));
+            }
+            Ok(path)
+        }
+        None => Ok(app_data_dir.join("backups")),
+    }
+}
+
+fn backup_file_path(backup_dir: &Path, title: Option<&str>, timestamp: &str) -> PathBuf {
+    let name = sanitize_filename(title.unwrap_or("manuscript"));
+    backup_dir.join(format!("{}_backup_{}.txt", name, timestamp))
+}
+
 #[tauri::command]
 pub async fn backup_manuscript(
-    _app: AppHandle,
+    app: AppHandle,
     content: String,
+    title: Option<String>,
+    backup_dir: Option<String>,
 ) -> Result<String, String> {
-    use std::path::Path;
-    
-    let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-    let backup_name = format!("manuscript_backup_{}.txt", timestamp);
+    let app_data_dir = app.path().app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {}", e))?;
+    let backup_dir = resolve_backup_dir(app_data_dir, backup_dir)
+        .map_err(|e| e.to_string())?;
 
-    let backup_dir = Path::new("backups");
     tokio::fs::create_dir_all(&backup_dir)
         .await
         .map_err(|e| format!("Failed to create backup directory: {}", e))?;
 
-    let backup_path = backup_dir.join(&backup_name);
+    let timestamp = Utc::now().format("%Y%m%d_%H%M%S").to_string();
+    let backup_path = backup_file_path(&backup_dir, title.as_deref(), &timestamp);
 
     tokio::fs::write(&backup_path, content)
         .await
         .map_err(|e| format!("Failed to create backup: {}", e))?;
 
     Ok(backup_path.to_string_lossy().to_string())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chapter_options(treat_h2_as_chapter: bool) -> ImportOptions {
+        ImportOptions {
+            treat_h1_as_chapter: false,
+            treat_h2_as_chapter,
+            chapter_regexes: Vec::new(),
+            ..ImportOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_h2_chapter_detection_is_configurable() {
+        let html = "<h2>One</h2><p>a b c</p><div class=\"scene-break\">***</div><p>d e</p><h2>Two</h2><p>f</p>";
+
+        let with_h2 = detect_scenes_in_content(html, &chapter_options(true));
+        let chapters: Vec<Option<u32>> = with_h2.iter().map(|s| s.chapter_number).collect();
+        assert_eq!(chapters, vec![Some(1), Some(1), Some(2)]);
+        assert!(matches!(with_h2[2].break_type, SceneBreakType::ChapterStart));
+        assert_eq!(with_h2[2].title.as_deref(), Some("Two"));
+
+        let without_h2 = detect_scenes_in_content(html, &chapter_options(false));
+        assert_eq!(without_h2.len(), 2);
+        assert!(without_h2.iter().all(|s| s.chapter_number.is_none()));
+    }
+
+    #[test]
+    fn test_short_scenes_merge_within_chapters_only() {
+        let options = ImportOptions {
+            merge_short_scenes: true,
+            min_scene_words: 3,
+            ..chapter_options(true)
+        };
+        let html = concat!(
+            "<h2>One</h2><p>a b c d</p>",
+            "<div class=\"scene-break\">***</div><p>e</p>",
+            "<div class=\"scene-break\">***</div><p>f g</p>",
+            "<h2>Two</h2><p>h</p>",
+            "<div class=\"scene-break\">***</div><p>i j k l</p>",
+        );
+
+        let scenes = detect_scenes_from_content(html, &options);
+        let summary: Vec<(Option<u32>, u32)> = scenes.iter().map(|s| (s.chapter_number, s.word_count)).collect();
+
+        // "e" and "f g" fold into chapter one; the short chapter two opener stays put
+        assert_eq!(summary, vec![(Some(1), 8), (Some(2), 2), (Some(2), 4)]);
+        assert!(scenes[0].content.ends_with("<p>f g</p>"));
+    }
+
+    #[test]
+    fn test_chapter_regexes_match_paragraph_text() {
+        let mut options = chapter_options(false);
+        options.chapter_regexes = vec![r"(?i)^book\s+\w+$".to_string()];
+        let html = "<p>Book One</p><p>Text.</p><p>Book Two</p><p>More.</p>";
+
+        let scenes = detect_scenes_in_content(html, &options);
+        assert_eq!(scenes.len(), 2);
+        assert_eq!(scenes[1].chapter_number, Some(2));
+
+        options.chapter_regexes.push("(".to_string());
+        assert!(options.validate().is_err());
+
+        let compiled = options.compiled_chapter_regexes();
+        assert_eq!(compiled.len(), 1);
+        assert!(is_chapter_marker(&compiled, "  Book Three "));
+        assert!(!is_chapter_marker(&compiled, "Bookish"));
+    }
+
+    #[test]
+    fn test_batch_import_flags_identical_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let first = temp.path().join("draft.txt");
+        let copy = temp.path().join("draft copy.txt");
+        let other = temp.path().join("other.txt");
+        std::fs::write(&first, "It was a dark night.").unwrap();
+        std::fs::write(&copy, "It was a dark night.").unwrap();
+        std::fs::write(&other, "It was a bright morning.").unwrap();
+
+        let paths: Vec<String> = [&first, &copy, &other]
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect();
+        let results: Vec<FileImportResult> = tokio_test::block_on(batch_import(&paths, &ImportOptions::default()))
+            .into_iter()
+            .map(|outcome| match outcome {
+                BatchImportOutcome::Imported(result) => result,
+                BatchImportOutcome::Failed { error, .. } => panic!("unexpected failure: {}", error),
+            })
+            .collect();
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].duplicate_of.is_none());
+        assert_eq!(results[1].duplicate_of.as_deref(), Some(paths[0].as_str()));
+        assert!(results[1].replacement.is_none());
+        assert_eq!(results[1].warnings.len(), 1);
+        assert!(results[2].duplicate_of.is_none());
+        assert_ne!(results[0].content_hash, results[2].content_hash);
+    }
+
+    #[test]
+    fn test_batch_import_reports_failed_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let good = temp.path().join("chapter.txt");
+        let unsupported = temp.path().join("notes.xyz");
+        std::fs::write(&good, "Some text.").unwrap();
+        std::fs::write(&unsupported, "Other text.").unwrap();
+
+        let paths = vec![
+            good.to_string_lossy().to_string(),
+            unsupported.to_string_lossy().to_string(),
+        ];
+        let outcomes = tokio_test::block_on(batch_import(&paths, &ImportOptions::default()));
+
+        assert_eq!(outcomes.len(), 2);
+        assert!(matches!(outcomes[0], BatchImportOutcome::Imported(_)));
+        match &outcomes[1] {
+            BatchImportOutcome::Failed { filename, error, .. } => {
+                assert_eq!(filename, "notes.xyz");
+                assert!(matches!(error, AppError::Validation { .. }));
+                assert_eq!(error.code(), Some(ErrorCode::UnsupportedFormat.as_str()));
+            }
+            other => panic!("expected failure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_batch_import_rejects_oversized_files_before_reading() {
+        let temp = tempfile::tempdir().unwrap();
+        let small = temp.path().join("small.txt");
+        let large = temp.path().join("large.txt");
+        std::fs::write(&small, "Short.").unwrap();
+        std::fs::write(&large, "a".repeat(4096)).unwrap();
+
+        let paths = vec![small.to_string_lossy().to_string(), large.to_string_lossy().to_string()];
+        let options = ImportOptions { max_file_size_bytes: 1024, ..ImportOptions::default() };
+        let outcomes = tokio_test::block_on(batch_import(&paths, &options));
+
+        assert!(matches!(outcomes[0], BatchImportOutcome::Imported(_)));
+        match &outcomes[1] {
+            BatchImportOutcome::Failed { error, .. } => {
+                assert_eq!(error.code(), Some(ErrorCode::FileTooLarge.as_str()));
+            }
+            other => panic!("expected failure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_custom_scene_break_marker_is_recognized() {
+        let options = ImportOptions {
+            scene_break_markers: vec!["~ ~ ~".to_string()],
+            ..ImportOptions::default()
+        };
+        let html = convert_text_to_html("One.\n~  ~  ~\nTwo.\n* * *\nThree.", &options);
+
+        assert_eq!(html.matches("class=\"scene-break\"").count(), 1);
+        assert!(html.contains("<p>* * *</p>"));
+    }
+
+    #[test]
+    fn test_dashed_lines_are_not_scene_breaks() {
+        let options = ImportOptions::default();
+        assert!(options.is_scene_break_marker("  * * *  "));
+        assert!(!options.is_scene_break_marker("------------------"));
+        assert!(!options.is_scene_break_marker("--- | ---"));
+        assert!(!options.is_scene_break_marker("*** END ***"));
+    }
+
+    #[test]
+    fn test_docx_import_escapes_run_text() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("escape.docx");
+        let file = std::fs::File::create(&path).unwrap();
+        Docx::new()
+            .add_paragraph(Paragraph::new().add_run(Run::new().add_text("a < b && c")))
+            .add_paragraph(Paragraph::new().add_run(Run::new().add_text("<b>not bold</b>").italic()))
+            .build()
+            .pack(file)
+            .unwrap();
+
+        let (html, _, _) = tokio_test::block_on(import_docx_file(&path)).unwrap();
+        assert!(html.contains("<p>a &lt; b &amp;&amp; c</p>"), "got {}", html);
+        assert!(html.contains("<p><em>&lt;b&gt;not bold&lt;/b&gt;</em></p>"), "got {}", html);
+    }
+
+    // 1x1 transparent PNG
+    const TINY_PNG: &[u8] = &[
+        0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+        0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1f, 0x15, 0xc4,
+        0x89, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x44, 0x41, 0x54, 0x78, 0xda, 0x63, 0x64, 0x60, 0xf8, 0x5f,
+        0x0f, 0x00, 0x02, 0x87, 0x01, 0x80, 0xeb, 0x47, 0xba, 0x92, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45,
+        0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+    ];
+
+    #[test]
+    fn test_docx_import_keeps_image_placeholders() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("figure.docx");
+        let file = std::fs::File::create(&path).unwrap();
+        Docx::new()
+            .add_paragraph(Paragraph::new().add_run(Run::new().add_text("Before the map.")))
+            .add_paragraph(Paragraph::new().add_run(Run::new().add_image(Pic::new(TINY_PNG))))
+            .add_paragraph(Paragraph::new().add_run(Run::new().add_text("Figure 1: The valley.")))
+            .build()
+            .pack(file)
+            .unwrap();
+
+        let (html, _, warnings) = tokio_test::block_on(import_docx_file(&path)).unwrap();
+        assert!(html.contains("<figure data-image=\"image-1\">[image]</figure>"), "got {}", html);
+        assert!(html.contains("<p>Figure 1: The valley.</p>"));
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_docx_heading_styles_become_chapters() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("styled.docx");
+        let file = std::fs::File::create(&path).unwrap();
+        Docx::new()
+            .add_paragraph(Paragraph::new().add_run(Run::new().add_text("The Arrival")).style("Heading1"))
+            .add_paragraph(Paragraph::new().add_run(Run::new().add_text("They came by night.")))
+            .add_paragraph(Paragraph::new().add_run(Run::new().add_text("The Siege")).style("Heading1"))
+            .add_paragraph(Paragraph::new().add_run(Run::new().add_text("Morning brought ladders.")))
+            .add_paragraph(Paragraph::new().add_run(Run::new().add_text("Aftermath")).style("Heading3"))
+            .build()
+            .pack(file)
+            .unwrap();
+
+        let (html, _, _) = tokio_test::block_on(import_docx_file(&path)).unwrap();
+        assert!(html.contains("<h1>The Arrival</h1>"), "got {}", html);
+        assert!(html.contains("<h3>Aftermath</h3>"), "got {}", html);
+
+        let scenes = detect_scenes_in_content(&html, &ImportOptions::default());
+        let chapters: Vec<Option<u32>> = scenes.iter().map(|s| s.chapter_number).collect();
+        assert_eq!(chapters, vec![Some(1), Some(2)]);
+        assert_eq!(scenes[1].title.as_deref(), Some("The Siege"));
+    }
+
+    #[test]
+    fn test_docx_page_breaks_become_chapter_boundaries() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("breaks.docx");
+        let file = std::fs::File::create(&path).unwrap();
+        Docx::new()
+            .add_paragraph(Paragraph::new().add_run(Run::new().add_text("The first chapter begins.")))
+            .add_paragraph(Paragraph::new().add_run(Run::new().add_break(BreakType::Page)))
+            .add_paragraph(Paragraph::new().add_run(Run::new().add_text("Chapter Two")).style("Heading1"))
+            .add_paragraph(Paragraph::new().add_run(Run::new().add_text("The second one follows.")))
+            .add_paragraph(
+                Paragraph::new()
+                    .add_run(Run::new().add_text("Last line of two."))
+                    .add_run(Run::new().add_break(BreakType::Page))
+            )
+            .add_paragraph(Paragraph::new().add_run(Run::new().add_text("The third has no heading.")))
+            .build()
+            .pack(file)
+            .unwrap();
+
+        let (html, _, _) = tokio_test::block_on(import_docx_file(&path)).unwrap();
+        assert_eq!(html.matches("data-break=\"page\"").count(), 2, "got {}", html);
+
+        let scenes = detect_scenes_in_content(&html, &ImportOptions::default());
+        let chapters: Vec<Option<u32>> = scenes.iter().map(|s| s.chapter_number).collect();
+        assert_eq!(chapters, vec![Some(1), Some(2), Some(3)]);
+        assert_eq!(scenes[1].title.as_deref(), Some("Chapter Two"));
+        assert!(scenes[2].content.contains("The third has no heading."));
+    }
+
+    #[test]
+    fn test_file_size_limit_is_configurable() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("limit.txt");
+        std::fs::write(&path, "a".repeat(2048)).unwrap();
+
+        assert!(get_file_metadata(&path, 2048).is_ok());
+
+        let error = get_file_metadata(&path, 2047).unwrap_err();
+        assert!(matches!(error, AppError::Validation { .. }));
+        assert_eq!(error.code(), Some(ErrorCode::FileTooLarge.as_str()));
+        assert!(error.to_string().contains("2047 bytes"), "got {}", error);
+
+        let options = ImportOptions { max_file_size_bytes: 1024, ..Default::default() };
+        let result = tokio_test::block_on(import_file(path.to_str().unwrap(), &options));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_file_size_limit_message_uses_megabytes() {
+        assert_eq!(format_file_size(DEFAULT_MAX_FILE_SIZE_BYTES), "100MB");
+        assert_eq!(format_file_size(5 * 1024 * 1024 + 512 * 1024), "5.5MB");
+        assert!(ImportOptions { max_file_size_bytes: 0, ..Default::default() }.validate().is_err());
+    }
+
+    #[test]
+    fn test_directory_import_makes_one_chapter_per_file() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(temp.path().join("chapter-3.md"), "The end came quietly.").unwrap();
+        std::fs::write(temp.path().join("chapter-1.md"), "# Beginnings\n\nIt started here.").unwrap();
+        std::fs::write(temp.path().join("chapter-2.txt"), "Then things went wrong.\n\n* * *\n\nVery wrong.").unwrap();
+        std::fs::write(temp.path().join("cover.png"), [0u8, 1, 2]).unwrap();
+
+        let result = tokio_test::block_on(
+            import_directory(temp.path().to_str().unwrap(), &ImportOptions::default())
+        ).unwrap();
+        let replacement = result.replacement.unwrap();
+
+        let chapters: Vec<(Option<u32>, Option<String>)> = replacement.scenes
+            .iter()
+            .filter(|scene| matches!(scene.break_type, SceneBreakType::ChapterStart))
+            .map(|scene| (scene.chapter_number, scene.title.clone()))
+            .collect();
+        assert_eq!(chapters, vec![
+            (Some(1), Some("chapter-1".to_string())),
+            (Some(2), Some("chapter-2".to_string())),
+            (Some(3), Some("chapter-3".to_string())),
+        ]);
+        assert_eq!(replacement.scenes.len(), 4);
+
+        let first = replacement.content.find("It started here.").unwrap();
+        let last = replacement.content.find("The end came quietly.").unwrap();
+        assert!(first < last);
+        assert!(result.warnings.iter().any(|w| w.contains("cover.png")));
+    }
+
+    #[test]
+    fn test_natural_sort_orders_numbers_by_value() {
+        let mut names = vec!["ch10.md".to_string(), "ch1.md".to_string(), "ch2.md".to_string()];
+        natural_sort(&mut names);
+        assert_eq!(names, vec!["ch1.md", "ch2.md", "ch10.md"]);
+
+        let mut paths = vec![
+            PathBuf::from("/b/Chapter 10.txt"),
+            PathBuf::from("/a/Chapter 2.txt"),
+            PathBuf::from("/c/chapter 1.txt"),
+        ];
+        natural_sort(&mut paths);
+        assert_eq!(paths, vec![
+            PathBuf::from("/c/chapter 1.txt"),
+            PathBuf::from("/a/Chapter 2.txt"),
+            PathBuf::from("/b/Chapter 10.txt"),
+        ]);
+    }
+
+    #[test]
+    fn test_natural_cmp_handles_leading_zeros() {
+        use std::cmp::Ordering;
+        assert_eq!(natural_cmp("ch02", "ch2"), "ch02".cmp("ch2"));
+        assert_eq!(natural_cmp("ch009", "ch10"), Ordering::Less);
+        assert_eq!(natural_cmp("part2", "part2b"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_directory_import_uses_natural_order() {
+        let temp = tempfile::tempdir().unwrap();
+        for n in [10, 2, 1] {
+            std::fs::write(temp.path().join(format!("ch{}.md", n)), format!("Text of chapter {}.", n)).unwrap();
+        }
+
+        let result = tokio_test::block_on(
+            import_directory(temp.path().to_str().unwrap(), &ImportOptions::default())
+        ).unwrap();
+        let titles: Vec<String> = result.replacement.unwrap().scenes
+            .into_iter()
+            .filter_map(|scene| scene.title)
+            .collect();
+        assert_eq!(titles, vec!["ch1", "ch2", "ch10"]);
+    }
+
+    #[test]
+    fn test_rtf_import_escapes_paragraph_text() {
+        let (plain_text, formatting_info) = parse_rtf_content(r"{\rtf1 a < b && c}").unwrap();
+        let html = convert_rtf_to_html(&plain_text, &formatting_info, &ImportOptions::default());
+        assert_eq!(html.trim(), "<p>a &lt; b &amp;&amp; c</p>");
+    }
+
+    #[test]
+    fn test_clean_html_keeps_block_separation_and_pre_spacing() {
+        let html = "<p>First   line</p>\n\n   <p>Second <em>word</em> <strong>here</strong></p><p>  </p><pre>a\n    b  c</pre>";
+        assert_eq!(
+            clean_html_content(html).unwrap(),
+            "<p>First line</p>\n<p>Second <em>word</em> <strong>here</strong></p><pre>a\n    b  c</pre>"
+        );
+    }
+
+    #[test]
+    fn test_markdown_import_handles_lists_quotes_and_code() {
+        let source = "- one\n  - nested\n- two\n\n3. three\n4. four\n\n> quoted *text*\n\n```\nlet x = 1;\n  ---\n```\n";
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("lists.md");
+        std::fs::write(&path, source).unwrap();
+
+        let (html, _, _) = tokio_test::block_on(import_markdown_file(&path, &ImportOptions::default())).unwrap();
+
+        assert!(html.contains("<ul><li>one<ul><li>nested</li></ul></li><li>two</li></ul>"), "got {}", html);
+        assert!(html.contains("<ol start=\"3\"><li>three</li><li>four</li></ol>"), "got {}", html);
+        assert!(html.contains("<blockquote><p>quoted <em>text</em></p></blockquote>"), "got {}", html);
+        assert!(html.contains("<pre><code>let x = 1;\n  ---\n</code></pre>"), "got {}", html);
+    }
+
+    #[test]
+    fn test_markdown_import_recognizes_custom_scene_break_marker() {
+        let options = ImportOptions {
+            scene_break_markers: vec!["§ § §".to_string()],
+            ..ImportOptions::default()
+        };
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("custom.md");
+        std::fs::write(&path, "Before the break.\n\n§ § §\n\nAfter the break.\n").unwrap();
+
+        let (html, _, _) = tokio_test::block_on(import_markdown_file(&path, &options)).unwrap();
+
+        assert!(html.contains("<div class=\"scene-break\">***</div>"), "got {}", html);
+        assert!(!html.contains('§'), "got {}", html);
+        assert!(html.contains("After the break."), "got {}", html);
+    }
+
+    #[test]
+    fn test_markdown_round_trip_keeps_headings_and_scene_breaks() {
+        let source = "# Book One\n\n## Chapter 1\n\nIt began *quietly*.\n\n---\n\nThen it didn't.\n\n### Interlude\n\nSilence.\n";
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("draft.md");
+        std::fs::write(&path, source).unwrap();
+
+        let (html, _, _) = tokio_test::block_on(import_markdown_file(&path, &ImportOptions::default())).unwrap();
+        let exported = html_to_markdown(&html);
+
+        assert!(exported.contains("# Book One\n"));
+        assert!(exported.contains("## Chapter 1\n"));
+        assert!(exported.contains("### Interlude\n"));
+        assert_eq!(exported.matches("\n---\n").count(), 1);
+
+        // Re-importing the export restores the same headings and breaks
+        let round_trip = temp.path().join("round_trip.md");
+        std::fs::write(&round_trip, &exported).unwrap();
+        let (html_again, _, _) = tokio_test::block_on(import_markdown_file(&round_trip, &ImportOptions::default())).unwrap();
+        for marker in ["<h1>Book One</h1>", "<h2>Chapter 1</h2>", "<h3>Interlude</h3>"] {
+            assert!(html_again.contains(marker), "missing {}", marker);
+        }
+        assert_eq!(
+            html_again.matches("class=\"scene-break\"").count(),
+            html.matches("class=\"scene-break\"").count()
+        );
+    }
+
+    #[test]
+    fn test_plain_text_export_keeps_paragraphs() {
+        let html = "<h2>Chapter 1</h2><p>First   paragraph\nwraps.</p><p>Second &amp; last.<br>New line</p>";
+        assert_eq!(
+            html_to_plain_text(html),
+            "Chapter 1\n\nFirst paragraph wraps.\n\nSecond & last.\nNew line"
+        );
+    }
+
+    #[test]
+    fn test_plain_text_export_centers_scene_breaks() {
+        let html = "<p>Before.</p><div class=\"scene-break\">***</div><p>After.</p>";
+        let text = html_to_plain_text(html);
+        let paragraphs: Vec<&str> = text.split("\n\n").collect();
+
+        assert_eq!(paragraphs.len(), 3);
+        assert_eq!(paragraphs[1].trim(), "***");
+        assert!(paragraphs[1].starts_with("   "));
+    }
+
+    #[test]
+    fn test_backup_path_is_absolute_under_configured_dir() {
+        let temp = tempfile::tempdir().unwrap();
+        let configured = temp.path().to_string_lossy().to_string();
+
+        let backup_dir = resolve_backup_dir(PathBuf::from("/unused"), Some(configured)).unwrap();
+        let path = backup_file_path(&backup_dir, Some("My Novel: Draft 2"), "20240101_120000");
+
+        assert!(path.is_absolute());
+        assert!(path.starts_with(temp.path()));
+        assert_eq!(path.file_name().unwrap(), "My Novel_ Draft 2_backup_20240101_120000.txt");
+    }
+
+    #[test]
+    fn test_sanitize_filename_replaces_reserved_characters() {
+        assert_eq!(sanitize_filename("What? A \"Novel\": Part 1/2"), "What_ A _Novel__ Part 1_2");
+        assert_eq!(sanitize_filename("  Draft...  "), "Draft");
+        assert_eq!(sanitize_filename("???"), "___");
+        assert_eq!(sanitize_filename("   "), "manuscript");
+        assert_eq!(sanitize_filename(&"a".repeat(300)).chars().count(), MAX_FILENAME_CHARS);
+    }
+
+    #[test]
+    fn test_sanitize_filename_suffixes_device_names() {
+        assert_eq!(sanitize_filename("CON"), "CON_");
+        assert_eq!(sanitize_filename("con.docx"), "con_.docx");
+        assert_eq!(sanitize_filename("lpt1"), "lpt1_");
+        assert_eq!(sanitize_filename("Console"), "Console");
+    }
+
+    #[test]
+    fn test_relative_backup_dir_is_rejected() {
+        assert!(resolve_backup_dir(PathBuf::from("/data"), Some("backups".to_string())).is_err());
+        assert_eq!(
+            resolve_backup_dir(PathBuf::from("/data"), None).unwrap(),
+            PathBuf::from("/data/backups")
+        );
+    }
+}
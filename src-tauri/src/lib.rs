@@ -5,16 +5,25 @@ pub mod menu;
 pub mod export;
 pub mod error;
 pub mod commands;
+pub mod text;
+pub mod spellcheck;
+pub mod analysis;
+pub mod settings;
+pub mod recent;
+pub mod templates;
 
 use tauri_plugin_sql::{Builder as SqlBuilder, Migration, MigrationKind};
 use tauri::Manager;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    error::install_panic_hook(error::ErrorLogger::new());
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(
             SqlBuilder::default()
                 .add_migrations(
@@ -32,6 +41,72 @@ pub fn run() {
                             sql: include_str!("../migrations/002_single_manuscript.sql"),
                             kind: MigrationKind::Up,
                         },
+                        Migration {
+                            version: 7,
+                            description: "custom_dictionary",
+                            sql: include_str!("../migrations/007_custom_dictionary.sql"),
+                            kind: MigrationKind::Up,
+                        },
+                        Migration {
+                            version: 8,
+                            description: "manuscript_cascade",
+                            sql: include_str!("../migrations/008_manuscript_cascade.sql"),
+                            kind: MigrationKind::Up,
+                        },
+                        Migration {
+                            version: 9,
+                            description: "story_beats",
+                            sql: include_str!("../migrations/009_story_beats.sql"),
+                            kind: MigrationKind::Up,
+                        },
+                        Migration {
+                            version: 10,
+                            description: "plants",
+                            sql: include_str!("../migrations/010_plants.sql"),
+                            kind: MigrationKind::Up,
+                        },
+                        Migration {
+                            version: 11,
+                            description: "events",
+                            sql: include_str!("../migrations/011_events.sql"),
+                            kind: MigrationKind::Up,
+                        },
+                        Migration {
+                            version: 12,
+                            description: "world_state",
+                            sql: include_str!("../migrations/012_world_state.sql"),
+                            kind: MigrationKind::Up,
+                        },
+                        Migration {
+                            version: 13,
+                            description: "comments",
+                            sql: include_str!("../migrations/013_comments.sql"),
+                            kind: MigrationKind::Up,
+                        },
+                        Migration {
+                            version: 14,
+                            description: "notes",
+                            sql: include_str!("../migrations/014_notes.sql"),
+                            kind: MigrationKind::Up,
+                        },
+                        Migration {
+                            version: 15,
+                            description: "scene_tags",
+                            sql: include_str!("../migrations/015_scene_tags.sql"),
+                            kind: MigrationKind::Up,
+                        },
+                        Migration {
+                            version: 16,
+                            description: "word_goals",
+                            sql: include_str!("../migrations/016_word_goals.sql"),
+                            kind: MigrationKind::Up,
+                        },
+                        Migration {
+                            version: 17,
+                            description: "module_status",
+                            sql: include_str!("../migrations/017_module_status.sql"),
+                            kind: MigrationKind::Up,
+                        },
                     ],
                 )
                 .build(),
@@ -39,12 +114,55 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             // Simplified single manuscript commands
             commands::get_manuscript_safe,
+            commands::get_manuscript_typed,
+            commands::get_scenes_typed,
             commands::update_manuscript_safe,
             commands::get_scenes_safe,
             commands::update_scene_safe,
+            commands::autosave_scene,
             commands::create_scene_safe,
             commands::delete_scene_safe,
             commands::get_recent_errors,
+            commands::get_error_statistics,
+            commands::start_error_log_stream,
+            commands::stop_error_log_stream,
+            commands::renumber_chapters,
+            commands::set_scene_hook_flags,
+            commands::set_scene_beat,
+            commands::get_manuscript_beats,
+            commands::create_plant,
+            commands::link_plant_payoff,
+            commands::get_unpaid_plants,
+            commands::record_scene_events,
+            commands::get_manuscript_events,
+            commands::set_state_at_scene,
+            commands::get_state_timeline,
+            commands::recompute_dirty_modules,
+            commands::add_comment,
+            commands::get_scene_comments,
+            commands::delete_comment,
+            commands::create_note,
+            commands::list_notes,
+            commands::update_note,
+            commands::delete_note,
+            commands::reindex_manuscript,
+            commands::move_scene_to_chapter,
+            commands::render_scene_preview,
+            commands::duplicate_manuscript,
+            commands::set_word_goal,
+            commands::get_goal_progress,
+            commands::get_database_size,
+            commands::vacuum_database,
+            commands::check_database_integrity,
+            commands::add_scene_tag,
+            commands::remove_scene_tag,
+            commands::get_scenes_by_tag,
+            commands::update_opening_scores,
+            commands::import_and_create_manuscript,
+            commands::create_scenes_batch,
+            commands::delete_manuscript_safe,
+            commands::preview_import,
+            commands::commit_import,
             // Legacy db commands for compatibility
             db::get_manuscript,
             db::get_all_scenes,
@@ -56,6 +174,8 @@ pub fn run() {
             db::rename_scene,
             db::reorder_scenes,
             db::search_content,
+            db::preview_replace,
+            db::replace_content,
             db::create_database_backup,
             db::get_dirty_scenes,
             db::get_module_status,
@@ -65,6 +185,8 @@ pub fn run() {
             db::clear_all_dirty_flags,
             // File system operations
             fs::replace_manuscript_content,
+            fs::batch_import_files,
+            fs::import_manuscript_directory,
             fs::export_manuscript_file,
             fs::open_file_dialog,
             fs::save_file_dialog,
@@ -81,15 +203,55 @@ pub fn run() {
             window::set_window_size,
             window::get_window_info,
             window::list_windows,
+            window::open_split_view,
+            window::close_split_view,
             // Export operations
             export::export_manuscript,
+            export::export_chapters,
+            export::export_to_clipboard,
+            export::generate_print_html,
             export::get_export_formats,
             export::validate_export_options,
+            // Writing tools
+            spellcheck::spellcheck_scene,
+            spellcheck::add_to_dictionary,
+            // Manuscript analysis
+            analysis::compute_readability,
+            analysis::analyze_dialogue_ratio,
+            analysis::analyze_prose_crutches,
+            analysis::detect_passive_voice,
+            analysis::extract_todos,
+            analysis::analyze_pacing,
+            analysis::analyze_pov_distribution,
+            analysis::analyze_opening,
+            analysis::build_scene_timeline,
+            analysis::build_document_outline,
+            analysis::get_full_analysis_report,
+            analysis::export_analysis_report,
+            // Preferences
+            settings::set_typewriter_mode,
+            settings::get_typewriter_mode,
+            settings::get_settings,
+            settings::update_settings,
+            settings::update_shortcut,
+            settings::get_theme,
+            settings::set_theme,
+            // Recent files
+            recent::add_recent_file,
+            recent::get_recent_files,
+            recent::clear_recent_files,
+            // Manuscript templates
+            templates::list_manuscript_templates,
+            templates::create_from_template,
         ])
         .setup(|app| {
             // Initialize database service
             let db_service = db::DatabaseService::new();
             app.manage(db_service);
+            app.manage(commands::PendingImports::default());
+            app.manage(commands::AutosaveQueue::default());
+            app.manage(commands::ErrorLogStream::default());
+            app.manage(window::WindowRegistry::default());
             
             // Create and set the app menu
             let menu = menu::create_app_menu(app.handle())?;
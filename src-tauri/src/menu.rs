@@ -1,24 +1,32 @@
 use tauri::{
-    menu::{Menu, MenuBuilder, MenuItemBuilder, SubmenuBuilder, PredefinedMenuItem},
+    menu::{Menu, MenuBuilder, MenuItem, MenuItemBuilder, SubmenuBuilder, PredefinedMenuItem},
     AppHandle, Wry
 };
-use tauri::Emitter;
+use tauri::{Emitter, Manager};
+use crate::error::{AppError, AppResult};
+use crate::settings::KeyboardShortcuts;
 
 pub fn create_app_menu(app_handle: &AppHandle) -> Result<Menu<Wry>, Box<dyn std::error::Error>> {
+    // Customized accelerators, falling back to the defaults if settings can't be read
+    let shortcuts = crate::settings::settings_path(app_handle)
+        .and_then(|path| crate::settings::load_settings(&path))
+        .map(|settings| settings.shortcuts)
+        .unwrap_or_default();
+
     // File Menu
-    let file_menu = create_file_menu(app_handle)?;
+    let file_menu = create_file_menu(app_handle, &shortcuts)?;
     
     // Edit Menu
-    let edit_menu = create_edit_menu(app_handle)?;
+    let edit_menu = create_edit_menu(app_handle, &shortcuts)?;
     
     // View Menu
-    let view_menu = create_view_menu(app_handle)?;
+    let view_menu = create_view_menu(app_handle, &shortcuts)?;
     
     // Manuscript Menu
-    let manuscript_menu = create_manuscript_menu(app_handle)?;
+    let manuscript_menu = create_manuscript_menu(app_handle, &shortcuts)?;
     
     // Tools Menu
-    let tools_menu = create_tools_menu(app_handle)?;
+    let tools_menu = create_tools_menu(app_handle, &shortcuts)?;
     
     // Help Menu
     let help_menu = create_help_menu(app_handle)?;
@@ -38,29 +46,40 @@ pub fn create_app_menu(app_handle: &AppHandle) -> Result<Menu<Wry>, Box<dyn std:
     Ok(menu)
 }
 
-fn create_file_menu(app_handle: &AppHandle) -> Result<tauri::menu::Submenu<Wry>, Box<dyn std::error::Error>> {
-    let new_manuscript = MenuItemBuilder::with_id("file_new_manuscript", "New Manuscript")
-        .accelerator("CmdOrCtrl+N")
-        .build(app_handle)?;
+// Rebuild the menu after something it shows (shortcuts, recent files) changes
+pub fn refresh_app_menu(app_handle: &AppHandle) -> AppResult<()> {
+    let menu = create_app_menu(app_handle)
+        .map_err(|e| AppError::internal(format!("Failed to rebuild menu: {}", e)))?;
+    app_handle.set_menu(menu)
+        .map_err(|e| AppError::internal(format!("Failed to set menu: {}", e)))?;
+
+    Ok(())
+}
+
+fn shortcut_item(
+    app_handle: &AppHandle,
+    shortcuts: &KeyboardShortcuts,
+    id: &str,
+    text: &str
+) -> Result<MenuItem<Wry>, Box<dyn std::error::Error>> {
+    let mut item = MenuItemBuilder::with_id(id, text);
+    if let Some(accelerator) = shortcuts.accelerator_for(id) {
+        item = item.accelerator(accelerator);
+    }
+
+    Ok(item.build(app_handle)?)
+}
+
+fn create_file_menu(app_handle: &AppHandle, shortcuts: &KeyboardShortcuts) -> Result<tauri::menu::Submenu<Wry>, Box<dyn std::error::Error>> {
+    let new_manuscript = shortcut_item(app_handle, shortcuts, "file_new_manuscript", "New Manuscript")?;
     
-    let open_manuscript = MenuItemBuilder::with_id("file_open_manuscript", "Open Manuscript...")
-        .accelerator("CmdOrCtrl+O")
-        .build(app_handle)?;
+    let open_manuscript = shortcut_item(app_handle, shortcuts, "file_open_manuscript", "Open Manuscript...")?;
     
-    let open_recent = SubmenuBuilder::with_id(app_handle, "file_recent", "Open Recent")
-        .items(&[
-            &MenuItemBuilder::with_id("file_recent_clear", "Clear Recent")
-                .build(app_handle)?,
-        ])
-        .build()?;
+    let open_recent = create_open_recent_menu(app_handle)?;
     
-    let save = MenuItemBuilder::with_id("file_save", "Save")
-        .accelerator("CmdOrCtrl+S")
-        .build(app_handle)?;
+    let save = shortcut_item(app_handle, shortcuts, "file_save", "Save")?;
     
-    let save_as = MenuItemBuilder::with_id("file_save_as", "Save As...")
-        .accelerator("CmdOrCtrl+Shift+S")
-        .build(app_handle)?;
+    let save_as = shortcut_item(app_handle, shortcuts, "file_save_as", "Save As...")?;
     
     let import_submenu = SubmenuBuilder::with_id(app_handle, "file_import", "Import")
         .items(&[
@@ -70,9 +89,7 @@ fn create_file_menu(app_handle: &AppHandle) -> Result<tauri::menu::Submenu<Wry>,
                 .build(app_handle)?,
             &MenuItemBuilder::with_id("file_import_scrivener", "Scrivener Project")
                 .build(app_handle)?,
-            &MenuItemBuilder::with_id("file_import_batch", "Batch Import...")
-                .accelerator("CmdOrCtrl+Shift+I")
-                .build(app_handle)?,
+            &shortcut_item(app_handle, shortcuts, "file_import_batch", "Batch Import...")?,
         ])
         .build()?;
     
@@ -91,16 +108,12 @@ fn create_file_menu(app_handle: &AppHandle) -> Result<tauri::menu::Submenu<Wry>,
         ])
         .build()?;
     
-    let print = MenuItemBuilder::with_id("file_print", "Print...")
-        .accelerator("CmdOrCtrl+P")
-        .build(app_handle)?;
+    let print = shortcut_item(app_handle, shortcuts, "file_print", "Print...")?;
     
     let print_preview = MenuItemBuilder::with_id("file_print_preview", "Print Preview")
         .build(app_handle)?;
     
-    let quit = MenuItemBuilder::with_id("file_quit", "Quit")
-        .accelerator("CmdOrCtrl+Q")
-        .build(app_handle)?;
+    let quit = shortcut_item(app_handle, shortcuts, "file_quit", "Quit")?;
 
     let file_menu = SubmenuBuilder::with_id(app_handle, "file", "File")
         .items(&[
@@ -124,50 +137,52 @@ fn create_file_menu(app_handle: &AppHandle) -> Result<tauri::menu::Submenu<Wry>,
     Ok(file_menu)
 }
 
-fn create_edit_menu(app_handle: &AppHandle) -> Result<tauri::menu::Submenu<Wry>, Box<dyn std::error::Error>> {
-    let undo = MenuItemBuilder::with_id("edit_undo", "Undo")
-        .accelerator("CmdOrCtrl+Z")
-        .build(app_handle)?;
+// One item per recent file (file_recent_0, file_recent_1, ...) above Clear Recent
+fn create_open_recent_menu(app_handle: &AppHandle) -> Result<tauri::menu::Submenu<Wry>, Box<dyn std::error::Error>> {
+    let recent_files = crate::recent::recent_files_path(app_handle)
+        .and_then(|path| crate::recent::load_recent_files(&path))
+        .unwrap_or_default();
+
+    let mut open_recent = SubmenuBuilder::with_id(app_handle, "file_recent", "Open Recent");
+    for (index, file) in recent_files.iter().enumerate() {
+        open_recent = open_recent.item(
+            &MenuItemBuilder::with_id(format!("file_recent_{}", index), &file.name)
+                .build(app_handle)?
+        );
+    }
+    if !recent_files.is_empty() {
+        open_recent = open_recent.separator();
+    }
+
+    let open_recent = open_recent
+        .item(&MenuItemBuilder::with_id("file_recent_clear", "Clear Recent").build(app_handle)?)
+        .build()?;
+
+    Ok(open_recent)
+}
+
+fn create_edit_menu(app_handle: &AppHandle, shortcuts: &KeyboardShortcuts) -> Result<tauri::menu::Submenu<Wry>, Box<dyn std::error::Error>> {
+    let undo = shortcut_item(app_handle, shortcuts, "edit_undo", "Undo")?;
     
-    let redo = MenuItemBuilder::with_id("edit_redo", "Redo")
-        .accelerator("CmdOrCtrl+Y")
-        .build(app_handle)?;
+    let redo = shortcut_item(app_handle, shortcuts, "edit_redo", "Redo")?;
     
-    let cut = MenuItemBuilder::with_id("edit_cut", "Cut")
-        .accelerator("CmdOrCtrl+X")
-        .build(app_handle)?;
+    let cut = shortcut_item(app_handle, shortcuts, "edit_cut", "Cut")?;
     
-    let copy = MenuItemBuilder::with_id("edit_copy", "Copy")
-        .accelerator("CmdOrCtrl+C")
-        .build(app_handle)?;
+    let copy = shortcut_item(app_handle, shortcuts, "edit_copy", "Copy")?;
     
-    let paste = MenuItemBuilder::with_id("edit_paste", "Paste")
-        .accelerator("CmdOrCtrl+V")
-        .build(app_handle)?;
+    let paste = shortcut_item(app_handle, shortcuts, "edit_paste", "Paste")?;
     
-    let select_all = MenuItemBuilder::with_id("edit_select_all", "Select All")
-        .accelerator("CmdOrCtrl+A")
-        .build(app_handle)?;
+    let select_all = shortcut_item(app_handle, shortcuts, "edit_select_all", "Select All")?;
     
-    let find = MenuItemBuilder::with_id("edit_find", "Find...")
-        .accelerator("CmdOrCtrl+F")
-        .build(app_handle)?;
+    let find = shortcut_item(app_handle, shortcuts, "edit_find", "Find...")?;
     
-    let find_next = MenuItemBuilder::with_id("edit_find_next", "Find Next")
-        .accelerator("CmdOrCtrl+G")
-        .build(app_handle)?;
+    let find_next = shortcut_item(app_handle, shortcuts, "edit_find_next", "Find Next")?;
     
-    let find_previous = MenuItemBuilder::with_id("edit_find_previous", "Find Previous")
-        .accelerator("CmdOrCtrl+Shift+G")
-        .build(app_handle)?;
+    let find_previous = shortcut_item(app_handle, shortcuts, "edit_find_previous", "Find Previous")?;
     
-    let replace = MenuItemBuilder::with_id("edit_replace", "Replace...")
-        .accelerator("CmdOrCtrl+H")
-        .build(app_handle)?;
+    let replace = shortcut_item(app_handle, shortcuts, "edit_replace", "Replace...")?;
     
-    let global_search = MenuItemBuilder::with_id("edit_global_search", "Global Search")
-        .accelerator("CmdOrCtrl+Shift+F")
-        .build(app_handle)?;
+    let global_search = shortcut_item(app_handle, shortcuts, "edit_global_search", "Global Search")?;
 
     let edit_menu = SubmenuBuilder::with_id(app_handle, "edit", "Edit")
         .items(&[
@@ -191,30 +206,18 @@ fn create_edit_menu(app_handle: &AppHandle) -> Result<tauri::menu::Submenu<Wry>,
     Ok(edit_menu)
 }
 
-fn create_view_menu(app_handle: &AppHandle) -> Result<tauri::menu::Submenu<Wry>, Box<dyn std::error::Error>> {
-    let zoom_in = MenuItemBuilder::with_id("view_zoom_in", "Zoom In")
-        .accelerator("CmdOrCtrl+Plus")
-        .build(app_handle)?;
+fn create_view_menu(app_handle: &AppHandle, shortcuts: &KeyboardShortcuts) -> Result<tauri::menu::Submenu<Wry>, Box<dyn std::error::Error>> {
+    let zoom_in = shortcut_item(app_handle, shortcuts, "view_zoom_in", "Zoom In")?;
     
-    let zoom_out = MenuItemBuilder::with_id("view_zoom_out", "Zoom Out")
-        .accelerator("CmdOrCtrl+Minus")
-        .build(app_handle)?;
+    let zoom_out = shortcut_item(app_handle, shortcuts, "view_zoom_out", "Zoom Out")?;
     
-    let zoom_reset = MenuItemBuilder::with_id("view_zoom_reset", "Actual Size")
-        .accelerator("CmdOrCtrl+0")
-        .build(app_handle)?;
+    let zoom_reset = shortcut_item(app_handle, shortcuts, "view_zoom_reset", "Actual Size")?;
     
-    let focus_mode = MenuItemBuilder::with_id("view_focus_mode", "Focus Mode")
-        .accelerator("CmdOrCtrl+Shift+F")
-        .build(app_handle)?;
+    let focus_mode = shortcut_item(app_handle, shortcuts, "view_focus_mode", "Focus Mode")?;
     
-    let distraction_free = MenuItemBuilder::with_id("view_distraction_free", "Distraction-Free Mode")
-        .accelerator("CmdOrCtrl+Shift+D")
-        .build(app_handle)?;
+    let distraction_free = shortcut_item(app_handle, shortcuts, "view_distraction_free", "Distraction-Free Mode")?;
     
-    let typewriter_mode = MenuItemBuilder::with_id("view_typewriter_mode", "Typewriter Mode")
-        .accelerator("CmdOrCtrl+T")
-        .build(app_handle)?;
+    let typewriter_mode = shortcut_item(app_handle, shortcuts, "view_typewriter_mode", "Typewriter Mode")?;
     
     let split_view_horizontal = MenuItemBuilder::with_id("view_split_horizontal", "Split View Horizontally")
         .build(app_handle)?;
@@ -225,17 +228,11 @@ fn create_view_menu(app_handle: &AppHandle) -> Result<tauri::menu::Submenu<Wry>,
     let close_split = MenuItemBuilder::with_id("view_close_split", "Close Split View")
         .build(app_handle)?;
     
-    let show_document_outline = MenuItemBuilder::with_id("view_document_outline", "Document Outline")
-        .accelerator("CmdOrCtrl+Shift+O")
-        .build(app_handle)?;
+    let show_document_outline = shortcut_item(app_handle, shortcuts, "view_document_outline", "Document Outline")?;
     
-    let show_writing_stats = MenuItemBuilder::with_id("view_writing_stats", "Writing Statistics")
-        .accelerator("CmdOrCtrl+Shift+S")
-        .build(app_handle)?;
+    let show_writing_stats = shortcut_item(app_handle, shortcuts, "view_writing_stats", "Writing Statistics")?;
     
-    let floating_notes = MenuItemBuilder::with_id("view_floating_notes", "Floating Notes")
-        .accelerator("CmdOrCtrl+Shift+N")
-        .build(app_handle)?;
+    let floating_notes = shortcut_item(app_handle, shortcuts, "view_floating_notes", "Floating Notes")?;
 
     let view_menu = SubmenuBuilder::with_id(app_handle, "view", "View")
         .items(&[
@@ -260,25 +257,17 @@ fn create_view_menu(app_handle: &AppHandle) -> Result<tauri::menu::Submenu<Wry>,
     Ok(view_menu)
 }
 
-fn create_manuscript_menu(app_handle: &AppHandle) -> Result<tauri::menu::Submenu<Wry>, Box<dyn std::error::Error>> {
-    let analyze_scene = MenuItemBuilder::with_id("manuscript_analyze_scene", "Analyze Current Scene")
-        .accelerator("CmdOrCtrl+Shift+A")
-        .build(app_handle)?;
+fn create_manuscript_menu(app_handle: &AppHandle, shortcuts: &KeyboardShortcuts) -> Result<tauri::menu::Submenu<Wry>, Box<dyn std::error::Error>> {
+    let analyze_scene = shortcut_item(app_handle, shortcuts, "manuscript_analyze_scene", "Analyze Current Scene")?;
     
     let analyze_full = MenuItemBuilder::with_id("manuscript_analyze_full", "Analyze Full Manuscript")
         .build(app_handle)?;
     
-    let quick_ai_suggestion = MenuItemBuilder::with_id("manuscript_quick_ai", "Quick AI Suggestion")
-        .accelerator("CmdOrCtrl+Enter")
-        .build(app_handle)?;
+    let quick_ai_suggestion = shortcut_item(app_handle, shortcuts, "manuscript_quick_ai", "Quick AI Suggestion")?;
     
-    let scene_comparison = MenuItemBuilder::with_id("manuscript_scene_comparison", "Open Scene Comparison")
-        .accelerator("CmdOrCtrl+Shift+C")
-        .build(app_handle)?;
+    let scene_comparison = shortcut_item(app_handle, shortcuts, "manuscript_scene_comparison", "Open Scene Comparison")?;
     
-    let statistics = MenuItemBuilder::with_id("manuscript_statistics", "Manuscript Statistics")
-        .accelerator("CmdOrCtrl+I")
-        .build(app_handle)?;
+    let statistics = shortcut_item(app_handle, shortcuts, "manuscript_statistics", "Manuscript Statistics")?;
     
     let word_frequency = MenuItemBuilder::with_id("manuscript_word_frequency", "Word Frequency Analysis")
         .build(app_handle)?;
@@ -315,10 +304,8 @@ fn create_manuscript_menu(app_handle: &AppHandle) -> Result<tauri::menu::Submenu
     Ok(manuscript_menu)
 }
 
-fn create_tools_menu(app_handle: &AppHandle) -> Result<tauri::menu::Submenu<Wry>, Box<dyn std::error::Error>> {
-    let preferences = MenuItemBuilder::with_id("tools_preferences", "Preferences...")
-        .accelerator("CmdOrCtrl+Comma")
-        .build(app_handle)?;
+fn create_tools_menu(app_handle: &AppHandle, shortcuts: &KeyboardShortcuts) -> Result<tauri::menu::Submenu<Wry>, Box<dyn std::error::Error>> {
+    let preferences = shortcut_item(app_handle, shortcuts, "tools_preferences", "Preferences...")?;
     
     let ai_settings = MenuItemBuilder::with_id("tools_ai_settings", "AI Settings...")
         .build(app_handle)?;
@@ -425,9 +412,23 @@ pub async fn handle_menu_event(
         "file_print" => {
             app_handle.emit("menu-action", "print")?;
         }
+        "file_print_preview" => {
+            app_handle.emit("menu-action", "print_preview")?;
+        }
         "file_quit" => {
             app_handle.exit(0);
         }
+        "file_recent_clear" => {
+            crate::recent::clear_recent_files_impl(app_handle)?;
+        }
+        id if id.starts_with("file_recent_") => {
+            // Ask the frontend to open the chosen file
+            let index: usize = id["file_recent_".len()..].parse()?;
+            let path = crate::recent::recent_files_path(app_handle)?;
+            if let Some(file) = crate::recent::load_recent_files(&path)?.get(index) {
+                app_handle.emit("menu-open-recent", &file.path)?;
+            }
+        }
         
         // Edit menu events
         "edit_undo" => {
@@ -465,6 +466,25 @@ pub async fn handle_menu_event(
         "view_zoom_reset" => {
             app_handle.emit("menu-action", "zoom_reset")?;
         }
+        "view_split_horizontal" => {
+            let registry = app_handle.state::<crate::window::WindowRegistry>();
+            crate::window::open_split_view(app_handle.clone(), registry, crate::window::SplitOrientation::Horizontal, None).await?;
+        }
+        "view_split_vertical" => {
+            let registry = app_handle.state::<crate::window::WindowRegistry>();
+            crate::window::open_split_view(app_handle.clone(), registry, crate::window::SplitOrientation::Vertical, None).await?;
+        }
+        "view_close_split" => {
+            let registry = app_handle.state::<crate::window::WindowRegistry>();
+            crate::window::close_split_view(app_handle.clone(), registry).await?;
+        }
+        "view_document_outline" => {
+            app_handle.emit("menu-action", "document_outline")?;
+        }
+        "view_typewriter_mode" => {
+            // Persists the flip and emits view-typewriter-toggle to every window
+            crate::settings::toggle_typewriter_mode(app_handle)?;
+        }
         
         // Manuscript menu events
         "manuscript_analyze_scene" => {
@@ -493,6 +513,9 @@ pub async fn handle_menu_event(
         "tools_export_settings" => {
             app_handle.emit("menu-action", "export_settings")?;
         }
+        "tools_manuscript_templates" => {
+            app_handle.emit("menu-action", "manuscript_templates")?;
+        }
         
         // Default case
         _ => {
@@ -0,0 +1,176 @@
+// Most-recently-used files behind File > Open Recent, stored as JSON in the app
+// data directory. Newest first, one entry per path, capped in length.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use crate::error::{AppError, AppResult};
+use crate::settings::write_json_atomic;
+
+pub const RECENT_FILES_NAME: &str = "recent_files.json";
+pub const MAX_RECENT_FILES: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecentFile {
+    pub path: String,
+    pub name: String,
+    pub last_opened: i64,
+}
+
+// Serializes read-modify-write cycles on the recent files list
+static RECENT_LOCK: Mutex<()> = Mutex::new(());
+
+pub fn recent_files_path(app: &AppHandle) -> AppResult<PathBuf> {
+    let data_dir = app.path().app_data_dir()
+        .map_err(|e| AppError::configuration(
+            format!("Cannot resolve app data directory: {}", e),
+            "app_data_dir".to_string()
+        ))?;
+
+    Ok(data_dir.join(RECENT_FILES_NAME))
+}
+
+// Empty when the list hasn't been written yet
+pub fn load_recent_files(path: &Path) -> AppResult<Vec<RecentFile>> {
+    match std::fs::read_to_string(path) {
+        Ok(json) => Ok(serde_json::from_str(&json)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(AppError::file_system_with_path(e.to_string(), "read recent files".to_string(), path.to_path_buf())),
+    }
+}
+
+// Move `file_path` to the front, dropping any older entry for it and anything
+// past the cap
+pub fn push_recent_file(files: &mut Vec<RecentFile>, file_path: &str, now: i64) {
+    files.retain(|file| file.path != file_path);
+
+    let name = Path::new(file_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| file_path.to_string());
+    files.insert(0, RecentFile { path: file_path.to_string(), name, last_opened: now });
+    files.truncate(MAX_RECENT_FILES);
+}
+
+// Drop entries whose files were moved or deleted; true if anything was removed
+pub fn prune_missing_files(files: &mut Vec<RecentFile>) -> bool {
+    let before = files.len();
+    files.retain(|file| Path::new(&file.path).exists());
+    files.len() != before
+}
+
+pub fn add_recent_file_at(list_path: &Path, file_path: &str, now: i64) -> AppResult<Vec<RecentFile>> {
+    let _guard = RECENT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    let mut files = load_recent_files(list_path)?;
+    push_recent_file(&mut files, file_path, now);
+    prune_missing_files(&mut files);
+    write_json_atomic(list_path, &files)?;
+
+    Ok(files)
+}
+
+pub fn get_recent_files_at(list_path: &Path) -> AppResult<Vec<RecentFile>> {
+    let _guard = RECENT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    let mut files = load_recent_files(list_path)?;
+    if prune_missing_files(&mut files) {
+        write_json_atomic(list_path, &files)?;
+    }
+
+    Ok(files)
+}
+
+pub fn clear_recent_files_at(list_path: &Path) -> AppResult<()> {
+    let _guard = RECENT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    write_json_atomic(list_path, &Vec::<RecentFile>::new())
+}
+
+pub fn clear_recent_files_impl(app: &AppHandle) -> AppResult<()> {
+    clear_recent_files_at(&recent_files_path(app)?)?;
+    // The Open Recent submenu is built from the list
+    crate::menu::refresh_app_menu(app)
+}
+
+#[tauri::command]
+pub async fn add_recent_file(app: AppHandle, path: String) -> Result<Vec<RecentFile>, AppError> {
+    let files = add_recent_file_at(&recent_files_path(&app)?, &path, chrono::Utc::now().timestamp_millis())?;
+    crate::menu::refresh_app_menu(&app)?;
+    Ok(files)
+}
+
+#[tauri::command]
+pub async fn get_recent_files(app: AppHandle) -> Result<Vec<RecentFile>, AppError> {
+    get_recent_files_at(&recent_files_path(&app)?)
+}
+
+#[tauri::command]
+pub async fn clear_recent_files(app: AppHandle) -> Result<(), AppError> {
+    clear_recent_files_impl(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn touch(dir: &Path, name: &str) -> String {
+        let path = dir.join(name);
+        std::fs::write(&path, "").unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_recent_files_are_deduplicated_newest_first() {
+        let temp = tempfile::tempdir().unwrap();
+        let list = temp.path().join(RECENT_FILES_NAME);
+        let draft = touch(temp.path(), "draft.docx");
+        let notes = touch(temp.path(), "notes.md");
+
+        add_recent_file_at(&list, &draft, 1).unwrap();
+        add_recent_file_at(&list, &notes, 2).unwrap();
+        let files = add_recent_file_at(&list, &draft, 3).unwrap();
+
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec![draft.as_str(), notes.as_str()]);
+        assert_eq!(files[0].name, "draft.docx");
+        assert_eq!(files[0].last_opened, 3);
+        assert_eq!(get_recent_files_at(&list).unwrap(), files);
+    }
+
+    #[test]
+    fn test_recent_files_are_capped() {
+        let temp = tempfile::tempdir().unwrap();
+        let list = temp.path().join(RECENT_FILES_NAME);
+
+        for i in 0..MAX_RECENT_FILES + 3 {
+            let path = touch(temp.path(), &format!("chapter-{}.txt", i));
+            add_recent_file_at(&list, &path, i as i64).unwrap();
+        }
+
+        let files = get_recent_files_at(&list).unwrap();
+        assert_eq!(files.len(), MAX_RECENT_FILES);
+        assert_eq!(files[0].name, format!("chapter-{}.txt", MAX_RECENT_FILES + 2));
+        assert_eq!(files.last().unwrap().name, "chapter-3.txt");
+    }
+
+    #[test]
+    fn test_missing_files_are_pruned() {
+        let temp = tempfile::tempdir().unwrap();
+        let list = temp.path().join(RECENT_FILES_NAME);
+        let kept = touch(temp.path(), "kept.docx");
+        let moved = touch(temp.path(), "moved.docx");
+
+        add_recent_file_at(&list, &kept, 1).unwrap();
+        add_recent_file_at(&list, &moved, 2).unwrap();
+        std::fs::remove_file(&moved).unwrap();
+
+        let files = get_recent_files_at(&list).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, kept);
+        assert_eq!(load_recent_files(&list).unwrap(), files);
+
+        clear_recent_files_at(&list).unwrap();
+        assert!(get_recent_files_at(&list).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,554 @@
+// User preferences persisted as JSON in the app data directory.
+//
+// Missing fields take their defaults and unknown fields are ignored, so a file
+// written by an older or newer build still loads.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+use crate::error::{AppError, AppResult};
+use crate::export::{ExportFormat, ParagraphStyle};
+
+pub const SETTINGS_FILE_NAME: &str = "settings.json";
+
+// Emitted to every window when typewriter mode changes
+pub const TYPEWRITER_TOGGLE_EVENT: &str = "view-typewriter-toggle";
+
+// Emitted to every window with the full settings after update_settings
+pub const SETTINGS_CHANGED_EVENT: &str = "settings-changed";
+
+// Emitted to every window with the new ThemeState after set_theme
+pub const THEME_CHANGED_EVENT: &str = "theme-changed";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    pub typewriter_mode: bool,
+    pub editor: EditorSettings,  // Tools > Preferences
+    pub ai: AiSettings,          // Tools > AI Settings
+    pub export: ExportSettings,  // Tools > Export Settings
+    pub shortcuts: KeyboardShortcuts,  // Tools > Keyboard Shortcuts
+    pub theme: ThemeSettings,          // Tools > Theme Editor
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeChoice {
+    Light,
+    Dark,
+    #[default]
+    System,  // Follow the operating system
+}
+
+// What the windows actually render; System resolves to one of these
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EffectiveTheme {
+    Light,
+    Dark,
+}
+
+impl ThemeChoice {
+    pub fn resolve(self, system: EffectiveTheme) -> EffectiveTheme {
+        match self {
+            ThemeChoice::Light => EffectiveTheme::Light,
+            ThemeChoice::Dark => EffectiveTheme::Dark,
+            ThemeChoice::System => system,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeSettings {
+    pub theme: ThemeChoice,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThemeState {
+    pub theme: ThemeChoice,
+    pub effective: EffectiveTheme,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EditorSettings {
+    pub font_family: String,
+    pub font_size: u32,    // Points
+    pub line_height: f32,  // Multiple of the font size
+    pub spellcheck: bool,
+    pub autosave: bool,
+}
+
+impl Default for EditorSettings {
+    fn default() -> Self {
+        Self {
+            font_family: "Georgia".to_string(),
+            font_size: 16,
+            line_height: 1.6,
+            spellcheck: true,
+            autosave: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AiSettings {
+    pub enabled: bool,
+    pub provider: Option<String>,
+    pub model: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportSettings {
+    pub default_format: ExportFormat,
+    pub default_directory: Option<String>,
+    pub paragraph_style: ParagraphStyle,
+}
+
+impl Default for ExportSettings {
+    fn default() -> Self {
+        Self {
+            default_format: ExportFormat::ShunnManuscript,
+            default_directory: None,
+            paragraph_style: ParagraphStyle::default(),
+        }
+    }
+}
+
+// Menu item id -> accelerator for every item with a shortcut out of the box.
+// These are also the only actions whose shortcut can be changed.
+pub const DEFAULT_SHORTCUTS: &[(&str, &str)] = &[
+    ("file_new_manuscript", "CmdOrCtrl+N"),
+    ("file_open_manuscript", "CmdOrCtrl+O"),
+    ("file_save", "CmdOrCtrl+S"),
+    ("file_save_as", "CmdOrCtrl+Shift+S"),
+    ("file_import_batch", "CmdOrCtrl+Shift+I"),
+    ("file_print", "CmdOrCtrl+P"),
+    ("file_quit", "CmdOrCtrl+Q"),
+    ("edit_undo", "CmdOrCtrl+Z"),
+    ("edit_redo", "CmdOrCtrl+Y"),
+    ("edit_cut", "CmdOrCtrl+X"),
+    ("edit_copy", "CmdOrCtrl+C"),
+    ("edit_paste", "CmdOrCtrl+V"),
+    ("edit_select_all", "CmdOrCtrl+A"),
+    ("edit_find", "CmdOrCtrl+F"),
+    ("edit_find_next", "CmdOrCtrl+G"),
+    ("edit_find_previous", "CmdOrCtrl+Shift+G"),
+    ("edit_replace", "CmdOrCtrl+H"),
+    ("edit_global_search", "CmdOrCtrl+Shift+F"),
+    ("view_zoom_in", "CmdOrCtrl+Plus"),
+    ("view_zoom_out", "CmdOrCtrl+Minus"),
+    ("view_zoom_reset", "CmdOrCtrl+0"),
+    ("view_focus_mode", "CmdOrCtrl+Shift+F"),
+    ("view_distraction_free", "CmdOrCtrl+Shift+D"),
+    ("view_typewriter_mode", "CmdOrCtrl+T"),
+    ("view_document_outline", "CmdOrCtrl+Shift+O"),
+    ("view_writing_stats", "CmdOrCtrl+Shift+S"),
+    ("view_floating_notes", "CmdOrCtrl+Shift+N"),
+    ("manuscript_analyze_scene", "CmdOrCtrl+Shift+A"),
+    ("manuscript_quick_ai", "CmdOrCtrl+Enter"),
+    ("manuscript_scene_comparison", "CmdOrCtrl+Shift+C"),
+    ("manuscript_statistics", "CmdOrCtrl+I"),
+    ("tools_preferences", "CmdOrCtrl+Comma"),
+];
+
+// Only the user's changes are stored, so new defaults reach existing installs.
+// An empty accelerator removes the shortcut.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyboardShortcuts {
+    pub overrides: BTreeMap<String, String>,
+}
+
+impl KeyboardShortcuts {
+    pub fn accelerator_for(&self, action_id: &str) -> Option<&str> {
+        match self.overrides.get(action_id) {
+            Some(accelerator) if accelerator.is_empty() => None,
+            Some(accelerator) => Some(accelerator),
+            None => default_shortcut(action_id),
+        }
+    }
+}
+
+pub fn default_shortcut(action_id: &str) -> Option<&'static str> {
+    DEFAULT_SHORTCUTS
+        .iter()
+        .find(|(id, _)| *id == action_id)
+        .map(|(_, accelerator)| *accelerator)
+}
+
+const ACCELERATOR_MODIFIERS: &[&str] = &[
+    "cmdorctrl", "commandorcontrol", "cmd", "command", "super", "meta",
+    "ctrl", "control", "alt", "option", "altgr", "shift",
+];
+
+const ACCELERATOR_NAMED_KEYS: &[&str] = &[
+    "plus", "minus", "comma", "period", "space", "tab", "enter", "return",
+    "escape", "esc", "backspace", "delete", "insert", "home", "end",
+    "pageup", "pagedown", "up", "down", "left", "right",
+];
+
+// Accepts the accelerator syntax the menu uses: any number of distinct
+// modifiers followed by exactly one key, joined with '+', e.g. "CmdOrCtrl+Shift+S"
+pub fn validate_accelerator(accelerator: &str) -> AppResult<()> {
+    let invalid = |reason: &str| AppError::validation_field(
+        format!("Invalid shortcut: {}", reason),
+        "accelerator".to_string(),
+        accelerator.to_string()
+    );
+
+    let parts: Vec<String> = accelerator.split('+').map(|part| part.trim().to_lowercase()).collect();
+    let (key, modifiers) = parts.split_last().ok_or_else(|| invalid("empty"))?;
+
+    for (i, modifier) in modifiers.iter().enumerate() {
+        if !ACCELERATOR_MODIFIERS.contains(&modifier.as_str()) {
+            return Err(invalid(&format!("unknown modifier '{}'", modifier)));
+        }
+        if modifiers[..i].contains(modifier) {
+            return Err(invalid(&format!("repeated modifier '{}'", modifier)));
+        }
+    }
+
+    let is_function_key = key
+        .strip_prefix('f')
+        .and_then(|n| n.parse::<u8>().ok())
+        .is_some_and(|n| (1..=24).contains(&n));
+    let is_single_key = key.chars().count() == 1
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || "=-,./;'[]\\`".contains(c));
+
+    if !(is_function_key || is_single_key || ACCELERATOR_NAMED_KEYS.contains(&key.as_str())) {
+        return Err(invalid(&format!("unknown key '{}'", key)));
+    }
+
+    Ok(())
+}
+
+impl AppSettings {
+    pub fn validate(&self) -> AppResult<()> {
+        if !(8..=72).contains(&self.editor.font_size) {
+            return Err(AppError::validation_field(
+                "Font size must be between 8 and 72".to_string(),
+                "editor.font_size".to_string(),
+                self.editor.font_size.to_string()
+            ));
+        }
+        if !(1.0..=3.0).contains(&self.editor.line_height) {
+            return Err(AppError::validation_field(
+                "Line height must be between 1.0 and 3.0".to_string(),
+                "editor.line_height".to_string(),
+                self.editor.line_height.to_string()
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+// Serializes read-modify-write cycles on the settings file
+static SETTINGS_LOCK: Mutex<()> = Mutex::new(());
+
+pub fn settings_path(app: &AppHandle) -> AppResult<PathBuf> {
+    let data_dir = app.path().app_data_dir()
+        .map_err(|e| AppError::configuration(
+            format!("Cannot resolve app data directory: {}", e),
+            "app_data_dir".to_string()
+        ))?;
+
+    Ok(data_dir.join(SETTINGS_FILE_NAME))
+}
+
+// Defaults when the file doesn't exist yet
+pub fn load_settings(path: &Path) -> AppResult<AppSettings> {
+    match std::fs::read_to_string(path) {
+        Ok(json) => Ok(serde_json::from_str(&json)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(AppSettings::default()),
+        Err(e) => Err(AppError::file_system_with_path(e.to_string(), "read settings".to_string(), path.to_path_buf())),
+    }
+}
+
+// Written to a temporary file first so a crash never leaves half a file behind
+pub(crate) fn write_json_atomic<T: Serialize>(path: &Path, value: &T) -> AppResult<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let temp_path = path.with_extension("json.tmp");
+    std::fs::write(&temp_path, serde_json::to_string_pretty(value)?)?;
+    std::fs::rename(&temp_path, path)?;
+
+    Ok(())
+}
+
+pub fn save_settings(path: &Path, settings: &AppSettings) -> AppResult<()> {
+    write_json_atomic(path, settings)
+}
+
+// Load, change and save the settings as one step
+pub fn modify_settings(path: &Path, change: impl FnOnce(&mut AppSettings)) -> AppResult<AppSettings> {
+    let _guard = SETTINGS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    let mut settings = load_settings(path)?;
+    change(&mut settings);
+    save_settings(path, &settings)?;
+
+    Ok(settings)
+}
+
+// Overlay a partial settings object: nested objects merge key by key, any other
+// value replaces what was there
+fn merge_json(target: &mut Value, patch: Value) {
+    match (target, patch) {
+        (Value::Object(target), Value::Object(patch)) => {
+            for (key, value) in patch {
+                merge_json(target.entry(key).or_insert(Value::Null), value);
+            }
+        }
+        (target, patch) => *target = patch,
+    }
+}
+
+// Apply a partial update such as {"editor": {"font_size": 18}}. Nothing is
+// saved unless the merged settings deserialize and validate.
+pub fn update_settings_at(path: &Path, partial: Value) -> AppResult<AppSettings> {
+    let _guard = SETTINGS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    let mut merged = serde_json::to_value(load_settings(path)?)?;
+    merge_json(&mut merged, partial);
+    let settings: AppSettings = serde_json::from_value(merged)
+        .map_err(|e| AppError::validation(format!("Invalid settings: {}", e)))?;
+    settings.validate()?;
+    save_settings(path, &settings)?;
+
+    Ok(settings)
+}
+
+#[tauri::command]
+pub async fn get_settings(app: AppHandle) -> Result<AppSettings, AppError> {
+    load_settings(&settings_path(&app)?)
+}
+
+#[tauri::command]
+pub async fn update_settings(app: AppHandle, partial: Value) -> Result<AppSettings, AppError> {
+    let settings = update_settings_at(&settings_path(&app)?, partial)?;
+    app.emit(SETTINGS_CHANGED_EVENT, &settings)
+        .map_err(|e| AppError::internal(format!("Failed to emit {}: {}", SETTINGS_CHANGED_EVENT, e)))?;
+
+    Ok(settings)
+}
+
+// Change one action's shortcut. An empty accelerator clears it.
+pub fn update_shortcut_at(path: &Path, action_id: &str, accelerator: &str) -> AppResult<KeyboardShortcuts> {
+    if default_shortcut(action_id).is_none() {
+        return Err(AppError::validation_field(
+            "No customizable shortcut for this action",
+            "action_id",
+            action_id
+        ));
+    }
+    if !accelerator.is_empty() {
+        validate_accelerator(accelerator)?;
+    }
+
+    let settings = modify_settings(path, |settings| {
+        settings.shortcuts.overrides.insert(action_id.to_string(), accelerator.to_string());
+    })?;
+
+    Ok(settings.shortcuts)
+}
+
+// Rebuilds the menu so the new accelerator takes effect immediately
+#[tauri::command]
+pub async fn update_shortcut(app: AppHandle, action_id: String, accelerator: String) -> Result<KeyboardShortcuts, AppError> {
+    let shortcuts = update_shortcut_at(&settings_path(&app)?, &action_id, &accelerator)?;
+    crate::menu::refresh_app_menu(&app)?;
+
+    Ok(shortcuts)
+}
+
+// The OS theme as reported by the main window, light if it can't be read
+fn system_theme(app: &AppHandle) -> EffectiveTheme {
+    match app.get_webview_window(crate::window::MAIN_WINDOW_LABEL).and_then(|window| window.theme().ok()) {
+        Some(tauri::Theme::Dark) => EffectiveTheme::Dark,
+        _ => EffectiveTheme::Light,
+    }
+}
+
+// Persist the choice, then hand the resolved state to `notify` (the event
+// emitter in the app) so every window can restyle
+pub fn set_theme_at(
+    path: &Path,
+    theme: ThemeChoice,
+    system: EffectiveTheme,
+    notify: impl FnOnce(&ThemeState) -> AppResult<()>
+) -> AppResult<ThemeState> {
+    let settings = modify_settings(path, |settings| settings.theme.theme = theme)?;
+    let state = ThemeState {
+        theme: settings.theme.theme,
+        effective: settings.theme.theme.resolve(system),
+    };
+    notify(&state)?;
+
+    Ok(state)
+}
+
+#[tauri::command]
+pub async fn get_theme(app: AppHandle) -> Result<ThemeState, AppError> {
+    let theme = load_settings(&settings_path(&app)?)?.theme.theme;
+    Ok(ThemeState { theme, effective: theme.resolve(system_theme(&app)) })
+}
+
+#[tauri::command]
+pub async fn set_theme(app: AppHandle, theme: ThemeChoice) -> Result<ThemeState, AppError> {
+    set_theme_at(&settings_path(&app)?, theme, system_theme(&app), |state| {
+        app.emit(THEME_CHANGED_EVENT, state)
+            .map_err(|e| AppError::internal(format!("Failed to emit {}: {}", THEME_CHANGED_EVENT, e)))
+    })
+}
+
+pub fn set_typewriter_mode_impl(app: &AppHandle, enabled: bool) -> AppResult<bool> {
+    let settings = modify_settings(&settings_path(app)?, |settings| settings.typewriter_mode = enabled)?;
+    app.emit(TYPEWRITER_TOGGLE_EVENT, settings.typewriter_mode)
+        .map_err(|e| AppError::internal(format!("Failed to emit {}: {}", TYPEWRITER_TOGGLE_EVENT, e)))?;
+
+    Ok(settings.typewriter_mode)
+}
+
+// Flip the stored preference, used by View > Typewriter Mode
+pub fn toggle_typewriter_mode(app: &AppHandle) -> AppResult<bool> {
+    let current = load_settings(&settings_path(app)?)?.typewriter_mode;
+    set_typewriter_mode_impl(app, !current)
+}
+
+#[tauri::command]
+pub async fn set_typewriter_mode(app: AppHandle, enabled: bool) -> Result<bool, AppError> {
+    set_typewriter_mode_impl(&app, enabled)
+}
+
+// New windows read this on startup to restore the mode
+#[tauri::command]
+pub async fn get_typewriter_mode(app: AppHandle) -> Result<bool, AppError> {
+    Ok(load_settings(&settings_path(&app)?)?.typewriter_mode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typewriter_mode_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("nested").join(SETTINGS_FILE_NAME);
+
+        assert!(!load_settings(&path).unwrap().typewriter_mode);
+
+        modify_settings(&path, |settings| settings.typewriter_mode = true).unwrap();
+        assert!(load_settings(&path).unwrap().typewriter_mode);
+
+        modify_settings(&path, |settings| settings.typewriter_mode = false).unwrap();
+        assert_eq!(load_settings(&path).unwrap(), AppSettings::default());
+    }
+
+    #[test]
+    fn test_partial_update_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join(SETTINGS_FILE_NAME);
+
+        let updated = update_settings_at(&path, serde_json::json!({
+            "editor": { "font_size": 18 },
+            "ai": { "enabled": true, "model": "local" },
+            "export": { "default_format": "Docx" }
+        })).unwrap();
+
+        assert_eq!(updated.editor.font_size, 18);
+        assert_eq!(updated.editor.font_family, "Georgia");
+        assert!(updated.ai.enabled);
+        assert_eq!(updated.export.default_format, ExportFormat::Docx);
+        assert_eq!(load_settings(&path).unwrap(), updated);
+
+        let invalid = update_settings_at(&path, serde_json::json!({ "editor": { "font_size": 200 } }));
+        assert!(matches!(invalid, Err(AppError::Validation { .. })));
+        let wrong_type = update_settings_at(&path, serde_json::json!({ "typewriter_mode": "yes" }));
+        assert!(matches!(wrong_type, Err(AppError::Validation { .. })));
+        assert_eq!(load_settings(&path).unwrap(), updated);
+    }
+
+    #[test]
+    fn test_older_settings_file_loads_with_defaults() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join(SETTINGS_FILE_NAME);
+        std::fs::write(&path, r#"{ "typewriter_mode": true, "editor": { "font_size": 14 }, "retired_option": 3 }"#).unwrap();
+
+        let settings = load_settings(&path).unwrap();
+        assert!(settings.typewriter_mode);
+        assert_eq!(settings.editor.font_size, 14);
+        assert_eq!(settings.editor.line_height, EditorSettings::default().line_height);
+        assert_eq!(settings.ai, AiSettings::default());
+        assert_eq!(settings.export, ExportSettings::default());
+    }
+
+    #[test]
+    fn test_custom_accelerator_replaces_default() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join(SETTINGS_FILE_NAME);
+
+        let shortcuts = load_settings(&path).unwrap().shortcuts;
+        assert_eq!(shortcuts.accelerator_for("file_save"), Some("CmdOrCtrl+S"));
+
+        update_shortcut_at(&path, "file_save", "CmdOrCtrl+Alt+S").unwrap();
+        update_shortcut_at(&path, "view_typewriter_mode", "").unwrap();
+
+        let shortcuts = load_settings(&path).unwrap().shortcuts;
+        assert_eq!(shortcuts.accelerator_for("file_save"), Some("CmdOrCtrl+Alt+S"));
+        assert_eq!(shortcuts.accelerator_for("view_typewriter_mode"), None);
+        assert_eq!(shortcuts.accelerator_for("edit_undo"), Some("CmdOrCtrl+Z"));
+    }
+
+    #[test]
+    fn test_invalid_accelerators_are_rejected() {
+        for accelerator in ["CmdOrCtrl+F5", "Shift+Plus", "Alt+/", "F12"] {
+            assert!(validate_accelerator(accelerator).is_ok(), "{}", accelerator);
+        }
+        for accelerator in ["", "CmdOrCtrl+", "Hyper+S", "Ctrl+Ctrl+S", "CmdOrCtrl+Shift", "CmdOrCtrl+F25", "CmdOrCtrl+Banana"] {
+            assert!(matches!(validate_accelerator(accelerator), Err(AppError::Validation { .. })), "{}", accelerator);
+        }
+        for (_, accelerator) in DEFAULT_SHORTCUTS {
+            assert!(validate_accelerator(accelerator).is_ok(), "{}", accelerator);
+        }
+
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join(SETTINGS_FILE_NAME);
+        assert!(update_shortcut_at(&path, "file_save", "CmdOrCtrl+Nope").is_err());
+        assert!(update_shortcut_at(&path, "no_such_action", "CmdOrCtrl+S").is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_theme_persists_and_notifies_resolved_value() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join(SETTINGS_FILE_NAME);
+        assert_eq!(load_settings(&path).unwrap().theme.theme, ThemeChoice::System);
+
+        let mut emitted = Vec::new();
+        let state = set_theme_at(&path, ThemeChoice::System, EffectiveTheme::Dark, |state| {
+            emitted.push(state.clone());
+            Ok(())
+        }).unwrap();
+        assert_eq!(state.effective, EffectiveTheme::Dark);
+
+        set_theme_at(&path, ThemeChoice::Light, EffectiveTheme::Dark, |state| {
+            emitted.push(state.clone());
+            Ok(())
+        }).unwrap();
+
+        let effective: Vec<EffectiveTheme> = emitted.iter().map(|state| state.effective).collect();
+        assert_eq!(effective, vec![EffectiveTheme::Dark, EffectiveTheme::Light]);
+        assert_eq!(load_settings(&path).unwrap().theme.theme, ThemeChoice::Light);
+        assert_eq!(serde_json::to_value(&emitted[1]).unwrap(), serde_json::json!({ "theme": "light", "effective": "light" }));
+    }
+}
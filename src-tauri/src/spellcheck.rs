@@ -0,0 +1,379 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use regex::Regex;
+use tauri::{AppHandle, State};
+use crate::db::DatabaseService;
+use crate::error::{AppError, AppResult};
+use crate::text::strip_html;
+
+const MAX_SUGGESTIONS: usize = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpellIssue {
+    pub word: String,
+    pub start_offset: u32, // Character offsets into the stored scene content
+    pub end_offset: u32,
+    pub suggestions: Vec<String>,
+}
+
+// Word list backed spell checker. Accepts plain word lists (one word per line)
+// and Hunspell .dic files (leading count line, affix flags after '/').
+pub struct SpellChecker {
+    words: HashSet<String>,
+}
+
+impl SpellChecker {
+    pub fn from_word_list(list: &str) -> Self {
+        let words = list
+            .lines()
+            .map(|line| line.split('/').next().unwrap_or("").trim())
+            .filter(|word| !word.is_empty() && !word.chars().all(|c| c.is_ascii_digit()))
+            .map(|word| word.to_lowercase())
+            .collect();
+
+        Self { words }
+    }
+
+    pub fn is_known(&self, word: &str, custom_words: &HashSet<String>) -> bool {
+        let lower = word.to_lowercase().replace('’', "'");
+        let known = |w: &str| self.words.contains(w) || custom_words.contains(w);
+
+        if known(&lower) {
+            return true;
+        }
+
+        // Possessives and common inflections of known stems
+        let base = lower.strip_suffix("'s").unwrap_or(&lower);
+        if known(base) {
+            return true;
+        }
+
+        for suffix in ["s", "es", "ed", "d", "ing", "ly", "er", "est"] {
+            let Some(stem) = base.strip_suffix(suffix) else {
+                continue;
+            };
+            if stem.len() < 2 {
+                continue;
+            }
+
+            if known(stem) || known(&format!("{}e", stem)) {
+                return true;
+            }
+            // stopped -> stop, running -> run
+            if let Some(single) = undouble_final_consonant(stem) {
+                if known(single) {
+                    return true;
+                }
+            }
+            // carried -> carry, happily -> happy
+            if let Some(root) = stem.strip_suffix('i') {
+                if known(&format!("{}y", root)) {
+                    return true;
+                }
+            }
+            // gently -> gentle, possibly -> possible
+            if suffix == "ly" && known(&format!("{}le", stem)) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    pub fn check_html(&self, html: &str, custom_words: &HashSet<String>) -> AppResult<Vec<SpellIssue>> {
+        let stripped = strip_html(html);
+        let word_regex = Regex::new(r"\p{L}+(?:['’]\p{L}+)*")?;
+
+        let mut issues = Vec::new();
+        for word_match in word_regex.find_iter(&stripped.text) {
+            let word = word_match.as_str();
+
+            // Single letters and all-caps acronyms are not worth flagging
+            if word.chars().count() < 2 || word.chars().all(|c| c.is_uppercase()) {
+                continue;
+            }
+
+            if self.is_known(word, custom_words) {
+                continue;
+            }
+
+            let (start, end) = stripped.source_range(word_match.start(), word_match.end());
+            issues.push(SpellIssue {
+                word: word.to_string(),
+                start_offset: start as u32,
+                end_offset: end as u32,
+                suggestions: self.suggest(word, custom_words),
+            });
+        }
+
+        Ok(issues)
+    }
+
+    // Known words within a single edit of the misspelling
+    pub fn suggest(&self, word: &str, custom_words: &HashSet<String>) -> Vec<String> {
+        let lower: Vec<char> = word.to_lowercase().chars().collect();
+        let alphabet = "abcdefghijklmnopqrstuvwxyz'";
+        let mut candidates = Vec::new();
+
+        for i in 0..=lower.len() {
+            // Deletion
+            if i < lower.len() {
+                let mut edit = lower.clone();
+                edit.remove(i);
+                candidates.push(edit);
+            }
+            // Transposition
+            if i + 1 < lower.len() {
+                let mut edit = lower.clone();
+                edit.swap(i, i + 1);
+                candidates.push(edit);
+            }
+            for letter in alphabet.chars() {
+                // Substitution
+                if i < lower.len() && lower[i] != letter {
+                    let mut edit = lower.clone();
+                    edit[i] = letter;
+                    candidates.push(edit);
+                }
+                // Insertion
+                let mut edit = lower.clone();
+                edit.insert(i, letter);
+                candidates.push(edit);
+            }
+        }
+
+        let mut suggestions: Vec<String> = Vec::new();
+        for candidate in candidates {
+            let candidate: String = candidate.into_iter().collect();
+            if (self.words.contains(&candidate) || custom_words.contains(&candidate))
+                && !suggestions.contains(&candidate)
+            {
+                suggestions.push(candidate);
+                if suggestions.len() >= MAX_SUGGESTIONS {
+                    break;
+                }
+            }
+        }
+
+        suggestions
+    }
+}
+
+// The stem with a doubled final consonant reduced to one, if it has one
+fn undouble_final_consonant(stem: &str) -> Option<&str> {
+    let mut chars = stem.chars().rev();
+    let (last, previous) = (chars.next()?, chars.next()?);
+    if last == previous && last.is_alphabetic() && !"aeiou".contains(last) {
+        Some(&stem[..stem.len() - last.len_utf8()])
+    } else {
+        None
+    }
+}
+
+static BASE_DICTIONARY: OnceLock<SpellChecker> = OnceLock::new();
+
+// Common English words, compiled in so every platform has a base dictionary
+const BUNDLED_WORD_LIST: &str = include_str!("../dictionaries/words.txt");
+
+// System dictionaries are much larger than the bundled list, so prefer them
+// where they exist (mostly Linux)
+fn dictionary_candidates() -> Vec<PathBuf> {
+    vec![
+        PathBuf::from("/usr/share/hunspell/en_US.dic"),
+        PathBuf::from("/usr/share/myspell/en_US.dic"),
+        PathBuf::from("/usr/share/dict/words"),
+    ]
+}
+
+fn base_dictionary() -> &'static SpellChecker {
+    BASE_DICTIONARY.get_or_init(|| {
+        // Hunspell dictionaries are not always UTF-8, so read lossily
+        dictionary_candidates()
+            .into_iter()
+            .find_map(|path| std::fs::read(path).ok())
+            .map(|bytes| SpellChecker::from_word_list(&String::from_utf8_lossy(&bytes)))
+            .unwrap_or_else(|| SpellChecker::from_word_list(BUNDLED_WORD_LIST))
+    })
+}
+
+async fn load_custom_words(app: &AppHandle, db_service: &DatabaseService) -> AppResult<HashSet<String>> {
+    let rows = db_service.execute_with_cache(
+        app,
+        "SELECT word FROM custom_dictionary",
+        &[]
+    ).await?;
+
+    Ok(rows
+        .as_array()
+        .map(|rows| {
+            rows.iter()
+                .filter_map(|row| row.get("word").and_then(|w| w.as_str()))
+                .map(|w| w.to_lowercase())
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+fn validate_dictionary_word(word: &str) -> AppResult<()> {
+    let trimmed = word.trim();
+
+    if trimmed.is_empty() || trimmed.chars().any(|c| c.is_whitespace()) {
+        return Err(AppError::validation_field(
+            "Dictionary entries must be a single word",
+            "word",
+            word
+        ));
+    }
+
+    if trimmed.chars().count() > 100 {
+        return Err(AppError::validation_field(
+            "Dictionary entries cannot be longer than 100 characters",
+            "word",
+            format!("{} chars", trimmed.chars().count()).as_str()
+        ));
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn spellcheck_scene(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    scene_id: String
+) -> Result<Vec<SpellIssue>, AppError> {
+    let pool = db_service.pool(&app).await?;
+    let content = crate::analysis::load_scene_content(&pool, &scene_id).await?;
+    let custom_words = load_custom_words(&app, db_service.inner()).await?;
+
+    base_dictionary().check_html(&content, &custom_words)
+}
+
+#[tauri::command]
+pub async fn add_to_dictionary(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    word: String
+) -> Result<(), AppError> {
+    validate_dictionary_word(&word)?;
+
+    let word = word.trim().to_lowercase();
+    let now = chrono::Utc::now().timestamp_millis();
+
+    db_service.with_retry(|| {
+        let app = app.clone();
+        let db_service = db_service.inner().clone();
+        let word = word.clone();
+
+        async move {
+            db_service.execute_with_cache(
+                &app,
+                "INSERT OR IGNORE INTO custom_dictionary (word, created_at) VALUES (?, ?)",
+                &[word, now.to_string()]
+            ).await
+        }
+    }).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checker() -> SpellChecker {
+        SpellChecker::from_word_list("6\nthe/S\ncat\nsat\non\nmat\nquick/Y\n")
+    }
+
+    #[test]
+    fn test_flags_misspelling_with_source_offsets() {
+        let html = "<p>The cat <em>szt</em> on the mat.</p>";
+        let issues = checker().check_html(html, &HashSet::new()).unwrap();
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].word, "szt");
+        let source: Vec<char> = html.chars().collect();
+        let flagged: String = source[issues[0].start_offset as usize..issues[0].end_offset as usize].iter().collect();
+        assert_eq!(flagged, "szt");
+        assert!(issues[0].suggestions.contains(&"sat".to_string()));
+    }
+
+    #[test]
+    fn test_custom_dictionary_suppresses_flag() {
+        let html = "<p>The cat sat on the Zorblax.</p>";
+        assert_eq!(checker().check_html(html, &HashSet::new()).unwrap().len(), 1);
+
+        let custom: HashSet<String> = ["zorblax".to_string()].into_iter().collect();
+        assert!(checker().check_html(html, &custom).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_inflections_of_known_words_are_accepted() {
+        let checker = checker();
+        let custom = HashSet::new();
+        assert!(checker.is_known("cats", &custom));
+        assert!(checker.is_known("mat's", &custom));
+        assert!(checker.is_known("quickly", &custom));
+        assert!(!checker.is_known("qwerty", &custom));
+    }
+
+    #[test]
+    fn test_doubled_consonant_and_y_stems_are_accepted() {
+        let checker = SpellChecker::from_word_list("stop\nrun\nnod\ncarry\nhappy\ngentle\n");
+        let custom = HashSet::new();
+        for word in ["stopped", "running", "nodded", "carried", "happily", "happiest", "gently"] {
+            assert!(checker.is_known(word, &custom), "{} should be known", word);
+        }
+        assert!(!checker.is_known("stopp", &custom));
+        assert!(!checker.is_known("carri", &custom));
+    }
+
+    #[test]
+    fn test_bundled_word_list_covers_everyday_prose() {
+        let checker = SpellChecker::from_word_list(BUNDLED_WORD_LIST);
+        let html = "<p>She walked into the kitchen and thought about what he'd said.</p>";
+        assert!(checker.check_html(html, &HashSet::new()).unwrap().is_empty());
+
+        let paragraph = "<p>She stopped at the top of the stairs and listened. Somewhere below a door slammed, \
+            and the old house shivered like a dog shaking off rain. She slipped off her shoes and crept down, \
+            keeping to the edge of each step where the boards were least likely to creak. Her brother was \
+            running late again. In the kitchen the kettle was still warm, and a cabbage sat on the chopping \
+            board beside a knife. She nodded to herself, then giggled and clapped a hand over her mouth, \
+            glancing toward the hall. \u{201c}Vile old place,\u{201d} her grandfather murmured from the doorway, \
+            leaning heavily on his cane. \u{201c}I used to think she was being fanciful. Now I\u{2019}m not so \
+            sure.\u{201d} He smiled faintly, but his eyes were tired and red, as though he hadn\u{2019}t slept, \
+            and the silence stretched between them until she thought it might snap.</p>";
+        let flagged: Vec<String> = checker.check_html(paragraph, &HashSet::new()).unwrap()
+            .into_iter()
+            .map(|issue| issue.word)
+            .collect();
+        assert!(flagged.is_empty(), "flagged {:?}", flagged);
+
+        let issues = checker.check_html("<p>The hosue was quiet.</p>", &HashSet::new()).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].suggestions.contains(&"house".to_string()));
+    }
+
+    #[test]
+    fn test_spellchecks_scene_text_from_the_database() {
+        let mut stored = crate::analysis::tests::scene(0, Some(1), "<p>The cat szt on the mat.</p>");
+        stored.id = uuid::Uuid::new_v4().to_string();
+
+        tokio_test::block_on(async {
+            let pool = crate::db::tests::memory_pool().await;
+            crate::db::replace_manuscript_with_scenes(&pool, &crate::db::tests::manuscript("Draft"), &[stored.clone()]).await.unwrap();
+
+            let content = crate::analysis::load_scene_content(&pool, &stored.id).await.unwrap();
+            let issues = checker().check_html(&content, &HashSet::new()).unwrap();
+            assert_eq!(issues.iter().map(|i| i.word.as_str()).collect::<Vec<_>>(), vec!["szt"]);
+        });
+    }
+
+    #[test]
+    fn test_rejects_multi_word_dictionary_entries() {
+        assert!(validate_dictionary_word("two words").is_err());
+        assert!(validate_dictionary_word("Zorblax").is_ok());
+    }
+}
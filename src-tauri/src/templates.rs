@@ -0,0 +1,196 @@
+// Manuscript templates behind Tools > Manuscript Templates. Each template is a
+// skeleton of chapters and scene titles bundled as JSON; creating from one
+// replaces the current manuscript with empty scenes laid out the same way.
+
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use tauri::{AppHandle, State};
+use crate::db::{DatabaseService, Manuscript, Scene, SINGLETON_MANUSCRIPT_ID};
+use crate::error::{AppError, AppResult, retry_with_backoff, RetryConfig};
+
+const BUNDLED_TEMPLATES: &[&str] = &[
+    include_str!("../templates/three_act.json"),
+    include_str!("../templates/heros_journey.json"),
+];
+
+#[derive(Debug, Clone, Deserialize)]
+struct ManuscriptTemplate {
+    id: String,
+    name: String,
+    description: String,
+    chapters: Vec<TemplateChapter>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TemplateChapter {
+    scenes: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateInfo {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub chapter_count: usize,
+    pub scene_count: usize,
+}
+
+fn bundled_templates() -> AppResult<Vec<ManuscriptTemplate>> {
+    BUNDLED_TEMPLATES
+        .iter()
+        .map(|json| Ok(serde_json::from_str(json)?))
+        .collect()
+}
+
+fn find_template(template_id: &str) -> AppResult<ManuscriptTemplate> {
+    bundled_templates()?
+        .into_iter()
+        .find(|template| template.id == template_id)
+        .ok_or_else(|| AppError::not_found_with_id("Manuscript template", template_id))
+}
+
+pub fn template_infos() -> AppResult<Vec<TemplateInfo>> {
+    Ok(bundled_templates()?
+        .into_iter()
+        .map(|template| TemplateInfo {
+            chapter_count: template.chapters.len(),
+            scene_count: template.chapters.iter().map(|chapter| chapter.scenes.len()).sum(),
+            id: template.id,
+            name: template.name,
+            description: template.description,
+        })
+        .collect())
+}
+
+// The manuscript and its empty, titled scenes, numbered the way imports are
+fn plan_template_manuscript(template: &ManuscriptTemplate, title: &str, now: i64) -> (Manuscript, Vec<Scene>) {
+    let manuscript = Manuscript {
+        id: SINGLETON_MANUSCRIPT_ID.to_string(),
+        title: title.to_string(),
+        author: None,
+        genre: None,
+        target_audience: None,
+        comp_titles: None,
+        created_at: now,
+        updated_at: now,
+        total_word_count: 0,
+        opening_strength_score: None,
+        hook_effectiveness: None,
+    };
+
+    let mut scenes = Vec::new();
+    for (chapter_index, chapter) in template.chapters.iter().enumerate() {
+        for (scene_index, scene_title) in chapter.scenes.iter().enumerate() {
+            scenes.push(Scene {
+                id: uuid::Uuid::new_v4().to_string(),
+                chapter_number: Some(chapter_index as i32 + 1),
+                scene_number_in_chapter: Some(scene_index as i32 + 1),
+                index_in_manuscript: scenes.len() as u32,
+                title: Some(scene_title.clone()),
+                raw_text: String::new(),
+                word_count: 0,
+                is_opening: scenes.is_empty(),
+                is_chapter_end: scene_index + 1 == chapter.scenes.len(),
+                opens_with_hook: false,
+                ends_with_hook: false,
+                pov_character: None,
+                location: None,
+                time_marker: None,
+                created_at: now,
+                updated_at: now,
+            });
+        }
+    }
+
+    (manuscript, scenes)
+}
+
+pub async fn create_from_template_impl(
+    pool: &SqlitePool,
+    template_id: &str,
+    title: &str,
+    now: i64
+) -> AppResult<String> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err(AppError::validation_field("Manuscript title cannot be empty", "title", title));
+    }
+
+    let template = find_template(template_id)?;
+    let (manuscript, scenes) = plan_template_manuscript(&template, title, now);
+
+    retry_with_backoff(|| {
+        let manuscript = &manuscript;
+        let scenes = &scenes;
+
+        async move {
+            crate::db::replace_manuscript_with_scenes(pool, manuscript, scenes).await
+        }
+    }, RetryConfig::default()).await?;
+
+    Ok(manuscript.id)
+}
+
+#[tauri::command]
+pub async fn list_manuscript_templates() -> Result<Vec<TemplateInfo>, AppError> {
+    template_infos()
+}
+
+// Replace the current manuscript with a new one laid out from a template
+#[tauri::command]
+pub async fn create_from_template(
+    app: AppHandle,
+    db_service: State<'_, DatabaseService>,
+    template_id: String,
+    title: String
+) -> Result<String, AppError> {
+    let pool = db_service.pool(&app).await?;
+    let now = chrono::Utc::now().timestamp_millis();
+    let manuscript_id = create_from_template_impl(&pool, &template_id, &title, now).await?;
+
+    db_service.invalidate_cache("manuscripts").await;
+    db_service.invalidate_cache("scenes").await;
+
+    Ok(manuscript_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_templates_parse() {
+        let infos = template_infos().unwrap();
+        let ids: Vec<&str> = infos.iter().map(|info| info.id.as_str()).collect();
+        assert_eq!(ids, vec!["three-act", "heros-journey"]);
+        assert_eq!(infos[1].scene_count, 12);
+    }
+
+    #[test]
+    fn test_create_from_template_seeds_scenes() {
+        tokio_test::block_on(async {
+            let pool = crate::db::tests::memory_pool().await;
+            let id = create_from_template_impl(&pool, "three-act", "  My Novel ", 42).await.unwrap();
+            assert_eq!(id, SINGLETON_MANUSCRIPT_ID);
+
+            let title: String = sqlx::query_scalar("SELECT title FROM manuscripts").fetch_one(&pool).await.unwrap();
+            assert_eq!(title, "My Novel");
+
+            let rows: Vec<(String, i64, i64, bool)> = sqlx::query_as(
+                "SELECT title, chapter_number, scene_number_in_chapter, is_chapter_end FROM scenes ORDER BY index_in_manuscript"
+            )
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+            assert_eq!(rows.len(), 9);
+            assert_eq!(rows[0], ("Setup".to_string(), 1, 1, false));
+            assert_eq!(rows[4], ("Midpoint".to_string(), 2, 2, false));
+            assert_eq!(rows[8], ("Resolution".to_string(), 3, 3, true));
+
+            let missing = create_from_template_impl(&pool, "sonnet", "Poems", 42).await;
+            assert!(matches!(missing, Err(AppError::NotFound { .. })));
+            let untitled = create_from_template_impl(&pool, "three-act", " ", 42).await;
+            assert!(matches!(untitled, Err(AppError::Validation { .. })));
+        });
+    }
+}
@@ -0,0 +1,527 @@
+// Shared text utilities for analysis features.
+//
+// Scene content is stored as HTML, but analysis runs over the visible text. The
+// stripped text keeps a mapping back to the source so results can be reported as
+// offsets into the original content. All offsets exposed by this module are
+// character (Unicode scalar) offsets, not byte offsets.
+
+use std::sync::OnceLock;
+use regex::Regex;
+
+#[derive(Debug, Clone, Copy)]
+struct MappedChar {
+    text_byte: usize,
+    source_start: usize,
+    source_end: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct StrippedText {
+    pub text: String,
+    chars: Vec<MappedChar>,
+    source_len: usize,
+}
+
+impl StrippedText {
+    // Map a byte range in `text` to a character range in the original source
+    pub fn source_range(&self, byte_start: usize, byte_end: usize) -> (usize, usize) {
+        if byte_start >= byte_end {
+            let offset = self.source_offset_at(byte_start);
+            return (offset, offset);
+        }
+
+        let start = self.char_at_byte(byte_start)
+            .map(|c| c.source_start)
+            .unwrap_or(self.source_len);
+        let end = self.char_before_byte(byte_end)
+            .map(|c| c.source_end)
+            .unwrap_or(start);
+
+        (start, end)
+    }
+
+    // 1-based line of a byte position in `text`. For many lookups in the same
+    // text, compute line_breaks once and use line_at instead.
+    pub fn line_number(&self, byte: usize) -> u32 {
+        line_at(&self.line_breaks(), byte)
+    }
+
+    // Byte positions in `text` of the newlines that end a line, in order. Lines
+    // end at block boundaries and <br>; newlines that only format the HTML
+    // source (e.g. "</p>\n<p>") aren't visible, so they only count when the
+    // source has no markup at all.
+    pub fn line_breaks(&self) -> Vec<usize> {
+        let is_newline = |c: &&MappedChar| self.text[c.text_byte..].starts_with('\n');
+        let is_markup = |c: &&MappedChar| c.source_end - c.source_start > 1;
+        let has_markup_breaks = self.chars.iter().filter(is_newline).any(|c| is_markup(&c));
+
+        self.chars
+            .iter()
+            .filter(is_newline)
+            .filter(|c| !has_markup_breaks || is_markup(c))
+            .map(|c| c.text_byte)
+            .collect()
+    }
+
+    // Character offset in the source corresponding to a byte position in `text`
+    pub fn source_offset_at(&self, byte: usize) -> usize {
+        self.char_at_byte(byte)
+            .map(|c| c.source_start)
+            .unwrap_or(self.source_len)
+    }
+
+    fn char_at_byte(&self, byte: usize) -> Option<&MappedChar> {
+        let index = match self.chars.binary_search_by_key(&byte, |c| c.text_byte) {
+            Ok(index) => index,
+            Err(index) => index,
+        };
+        self.chars.get(index)
+    }
+
+    fn char_before_byte(&self, byte: usize) -> Option<&MappedChar> {
+        let index = match self.chars.binary_search_by_key(&byte, |c| c.text_byte) {
+            Ok(index) => index,
+            Err(index) => index,
+        };
+        index.checked_sub(1).and_then(|i| self.chars.get(i))
+    }
+}
+
+// 1-based line of a byte position, given the text's line_breaks
+pub fn line_at(line_breaks: &[usize], byte: usize) -> u32 {
+    line_breaks.partition_point(|&line_break| line_break < byte) as u32 + 1
+}
+
+// Strip tags and decode entities, keeping a newline for block-level boundaries
+pub fn strip_html(html: &str) -> StrippedText {
+    let source: Vec<char> = html.chars().collect();
+    let mut text = String::with_capacity(html.len());
+    let mut chars = Vec::with_capacity(source.len());
+
+    let mut push = |text: &mut String, ch: char, source_start: usize, source_end: usize| {
+        chars.push(MappedChar {
+            text_byte: text.len(),
+            source_start,
+            source_end,
+        });
+        text.push(ch);
+    };
+
+    let mut i = 0;
+    while i < source.len() {
+        let ch = source[i];
+
+        if ch == '<' {
+            if let Some(close) = source[i..].iter().position(|&c| c == '>') {
+                let end = i + close + 1;
+                let tag: String = source[i + 1..end - 1].iter().collect();
+                if is_block_boundary(&tag) {
+                    push(&mut text, '\n', i, end);
+                }
+                i = end;
+                continue;
+            }
+        } else if ch == '&' {
+            let window_end = (i + 12).min(source.len());
+            if let Some(semi) = source[i..window_end].iter().position(|&c| c == ';') {
+                let end = i + semi + 1;
+                let entity: String = source[i + 1..end - 1].iter().collect();
+                if let Some(decoded) = decode_entity(&entity) {
+                    push(&mut text, decoded, i, end);
+                    i = end;
+                    continue;
+                }
+            }
+        }
+
+        push(&mut text, ch, i, i + 1);
+        i += 1;
+    }
+
+    StrippedText {
+        text,
+        chars,
+        source_len: source.len(),
+    }
+}
+
+fn is_block_boundary(tag: &str) -> bool {
+    let tag = tag.trim();
+    let is_closing = tag.starts_with('/');
+    let name: String = tag
+        .trim_start_matches('/')
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+
+    match name.as_str() {
+        "br" => true,
+        "p" | "div" | "li" | "blockquote" | "pre" | "tr"
+        | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => is_closing,
+        _ => false,
+    }
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    match entity {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{00A0}'),
+        "mdash" => Some('—'),
+        "ndash" => Some('–'),
+        "hellip" => Some('…'),
+        "lsquo" => Some('‘'),
+        "rsquo" => Some('’'),
+        "ldquo" => Some('“'),
+        "rdquo" => Some('”'),
+        _ => {
+            let numeric = entity.strip_prefix('#')?;
+            let code = if let Some(hex) = numeric.strip_prefix('x').or_else(|| numeric.strip_prefix('X')) {
+                u32::from_str_radix(hex, 16).ok()?
+            } else {
+                numeric.parse::<u32>().ok()?
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+// Elements kept by sanitize_html. Other tags are dropped but their text is kept.
+const ALLOWED_TAGS: &[&str] = &[
+    "p", "br", "hr", "div", "span", "blockquote", "pre",
+    "h1", "h2", "h3", "h4", "h5", "h6", "ul", "ol", "li",
+    "em", "strong", "b", "i", "u", "s", "sub", "sup",
+];
+
+const VOID_TAGS: &[&str] = &["br", "hr"];
+
+// Dropped together with everything inside them
+const DROPPED_CONTENT_TAGS: &[&str] = &[
+    "script", "style", "iframe", "object", "embed", "template", "noscript", "textarea", "title", "head",
+];
+
+// Opening one of these closes an open paragraph, as browsers do
+const CLOSES_PARAGRAPH: &[&str] = &[
+    "p", "div", "blockquote", "pre", "h1", "h2", "h3", "h4", "h5", "h6", "ul", "ol", "hr",
+];
+
+struct HtmlTag {
+    name: String,
+    closing: bool,
+    class: Option<String>,
+}
+
+// Rewrite stored HTML so it can be injected into a view safely: only
+// whitelisted tags survive, the only attribute kept is a plain class list,
+// every element is closed in order, and stray '<', '>' and '&' are escaped
+pub fn sanitize_html(html: &str) -> String {
+    let mut output = String::with_capacity(html.len());
+    let mut open: Vec<String> = Vec::new();
+    let mut rest = html;
+
+    while let Some(ch) = rest.chars().next() {
+        match ch {
+            '<' if rest.starts_with("<!--") => {
+                rest = rest[4..].find("-->").map_or("", |end| &rest[4 + end + 3..]);
+            }
+            '<' if rest.starts_with("<!") || rest.starts_with("<?") => {
+                rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+            }
+            '<' => {
+                let Some((tag, end)) = rest.find('>').and_then(|end| parse_tag(&rest[1..end]).map(|tag| (tag, end))) else {
+                    output.push_str("&lt;");
+                    rest = &rest[1..];
+                    continue;
+                };
+
+                rest = &rest[end + 1..];
+                if !tag.closing && DROPPED_CONTENT_TAGS.contains(&tag.name.as_str()) {
+                    rest = skip_element_content(rest, &tag.name);
+                } else {
+                    write_tag(&tag, &mut open, &mut output);
+                }
+            }
+            '>' => {
+                output.push_str("&gt;");
+                rest = &rest[1..];
+            }
+            '&' => {
+                let entity_end = rest[1..]
+                    .find(';')
+                    .filter(|&semi| semi <= 10 && decode_entity(&rest[1..1 + semi]).is_some());
+                match entity_end {
+                    Some(semi) => {
+                        output.push_str(&rest[..semi + 2]);
+                        rest = &rest[semi + 2..];
+                    }
+                    None => {
+                        output.push_str("&amp;");
+                        rest = &rest[1..];
+                    }
+                }
+            }
+            _ => {
+                output.push(ch);
+                rest = &rest[ch.len_utf8()..];
+            }
+        }
+    }
+
+    close_from(&mut open, 0, &mut output);
+    output
+}
+
+// The inside of a tag, without its angle brackets. Returns None when it isn't
+// a tag at all, e.g. the "< 4" in "3 < 4".
+fn parse_tag(inner: &str) -> Option<HtmlTag> {
+    static CLASS_REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = CLASS_REGEX.get_or_init(|| {
+        Regex::new(r#"(?i)(?:^|\s)class\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'=<>`]+))"#).expect("class pattern is valid")
+    });
+
+    if inner.contains('<') {
+        return None;
+    }
+
+    let (closing, body) = match inner.strip_prefix('/') {
+        Some(body) => (true, body),
+        None => (false, inner),
+    };
+    let name = body
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let class = regex
+        .captures(&body[name.len()..])
+        .and_then(|captures| captures.iter().skip(1).flatten().next())
+        .map(|value| {
+            value.as_str()
+                .chars()
+                .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ' '))
+                .collect::<String>()
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .filter(|class| !class.is_empty());
+
+    Some(HtmlTag { name, closing, class })
+}
+
+fn write_tag(tag: &HtmlTag, open: &mut Vec<String>, output: &mut String) {
+    let name = tag.name.as_str();
+    if !ALLOWED_TAGS.contains(&name) {
+        return;
+    }
+
+    // A closing tag closes everything opened since its element; a closing tag
+    // with nothing to close is dropped
+    if tag.closing {
+        if let Some(position) = open.iter().rposition(|open_name| open_name == name) {
+            close_from(open, position, output);
+        }
+        return;
+    }
+
+    if CLOSES_PARAGRAPH.contains(&name) {
+        if let Some(position) = open.iter().rposition(|open_name| open_name == "p") {
+            close_from(open, position, output);
+        }
+    }
+
+    output.push('<');
+    output.push_str(name);
+    if let Some(class) = &tag.class {
+        output.push_str(&format!(" class=\"{}\"", class));
+    }
+    output.push('>');
+
+    if !VOID_TAGS.contains(&name) {
+        open.push(tag.name.clone());
+    }
+}
+
+fn close_from(open: &mut Vec<String>, position: usize, output: &mut String) {
+    for name in open.drain(position..).rev() {
+        output.push_str(&format!("</{}>", name));
+    }
+}
+
+// Text after a dropped element's closing tag, or nothing if it never closes
+fn skip_element_content<'a>(rest: &'a str, name: &str) -> &'a str {
+    let closing = format!("</{}", name);
+    match rest.to_ascii_lowercase().find(&closing) {
+        Some(start) => rest[start..].find('>').map_or("", |end| &rest[start + end + 1..]),
+        None => "",
+    }
+}
+
+// Words as (byte offset, word) pairs. Internal apostrophes are kept, so "don't"
+// and "Jane’s" are single words.
+pub fn words(text: &str) -> Vec<(usize, &str)> {
+    static WORD_REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = WORD_REGEX.get_or_init(|| {
+        Regex::new(r"[\p{L}\p{N}]+(?:['’][\p{L}\p{N}]+)*").expect("word pattern is valid")
+    });
+
+    regex.find_iter(text).map(|m| (m.start(), m.as_str())).collect()
+}
+
+// Sentences as (byte offset, sentence) pairs. Terminal punctuation (and any
+// closing quotes after it) stays with the sentence; line breaks also end one.
+pub fn sentences(text: &str) -> Vec<(usize, &str)> {
+    static BOUNDARY_REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = BOUNDARY_REGEX.get_or_init(|| {
+        Regex::new(r#"[.!?…]+["'”’)\]]*(?:\s+|$)|\n+"#).expect("sentence pattern is valid")
+    });
+
+    let mut result = Vec::new();
+    let mut start = 0;
+    for boundary in regex.find_iter(text) {
+        if is_abbreviation_period(text, start, boundary.start(), boundary.as_str()) {
+            continue;
+        }
+        push_sentence(text, start, boundary.end(), &mut result);
+        start = boundary.end();
+    }
+    push_sentence(text, start, text.len(), &mut result);
+
+    result
+}
+
+const ABBREVIATIONS: &[&str] = &[
+    "mr", "mrs", "ms", "dr", "st", "jr", "sr", "prof", "rev", "capt", "col", "gen",
+    "lt", "sgt", "mt", "no", "vs", "etc", "e.g", "i.e", "approx", "dept",
+];
+
+// A single period after a known abbreviation or an initial ("J. R. Smith") is
+// not a sentence boundary unless it ends the line
+fn is_abbreviation_period(text: &str, sentence_start: usize, boundary_start: usize, boundary: &str) -> bool {
+    if !boundary.starts_with('.') || boundary.starts_with("..") || boundary.contains('\n') {
+        return false;
+    }
+    if boundary.trim_end().len() != 1 || boundary.trim_end().len() == boundary.len() {
+        return false;
+    }
+
+    let token = text[sentence_start..boundary_start]
+        .rsplit(char::is_whitespace)
+        .next()
+        .unwrap_or("")
+        .trim_start_matches(|c: char| !c.is_alphanumeric());
+
+    let is_initial = token.chars().count() == 1 && token.chars().all(|c| c.is_uppercase());
+    is_initial || ABBREVIATIONS.contains(&token.to_lowercase().as_str())
+}
+
+fn push_sentence<'a>(text: &'a str, start: usize, end: usize, result: &mut Vec<(usize, &'a str)>) {
+    let segment = &text[start..end];
+    let leading = segment.len() - segment.trim_start().len();
+    let trimmed = segment.trim();
+    if !words(trimmed).is_empty() {
+        result.push((start + leading, trimmed));
+    }
+}
+
+// Canonical word count for stored scene and manuscript totals. HTML is stripped
+// first so markup never counts, and words are split the same way analysis does.
+pub fn word_count(text: &str, is_html: bool) -> u32 {
+    if is_html {
+        words(&strip_html(text).text).len() as u32
+    } else {
+        words(text).len() as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_html_maps_offsets_to_source() {
+        let html = "<p>Tom &amp; <em>Jerry</em></p><p>Next</p>";
+        let stripped = strip_html(html);
+        assert_eq!(stripped.text, "Tom & Jerry\nNext\n");
+
+        let byte_start = stripped.text.find("Jerry").unwrap();
+        let (start, end) = stripped.source_range(byte_start, byte_start + "Jerry".len());
+        let source: Vec<char> = html.chars().collect();
+        assert_eq!(source[start..end].iter().collect::<String>(), "Jerry");
+
+        let amp = stripped.text.find('&').unwrap();
+        let (start, end) = stripped.source_range(amp, amp + 1);
+        assert_eq!(source[start..end].iter().collect::<String>(), "&amp;");
+    }
+
+    #[test]
+    fn test_strip_html_handles_multibyte_text() {
+        let stripped = strip_html("<p>Café — déjà vu</p>");
+        assert_eq!(stripped.text, "Café — déjà vu\n");
+        let byte_start = stripped.text.find("déjà").unwrap();
+        let (start, end) = stripped.source_range(byte_start, byte_start + "déjà".len());
+        assert_eq!((start, end), (10, 14));
+    }
+
+    #[test]
+    fn test_line_numbers_from_precomputed_breaks() {
+        let stripped = strip_html("<p>One</p>\n<p>Two<br>Three</p>");
+        let line_breaks = stripped.line_breaks();
+        let line_of = |word: &str| line_at(&line_breaks, stripped.text.find(word).unwrap());
+        assert_eq!((line_of("One"), line_of("Two"), line_of("Three")), (1, 2, 3));
+        assert_eq!(stripped.line_number(stripped.text.find("Three").unwrap()), 3);
+
+        let plain = strip_html("a\nb\nc");
+        assert_eq!(line_at(&plain.line_breaks(), plain.text.find('c').unwrap()), 3);
+    }
+
+    #[test]
+    fn test_sentence_and_word_splitting() {
+        let text = "\"Stop!\" she said. He didn't.\nThen silence";
+        let sentences: Vec<&str> = sentences(text).into_iter().map(|(_, s)| s).collect();
+        assert_eq!(sentences, vec!["\"Stop!\"", "she said.", "He didn't.", "Then silence"]);
+        assert_eq!(words("He didn't go").len(), 3);
+    }
+
+    #[test]
+    fn test_word_count_matches_for_html_and_plain_text() {
+        let html = "<h1>Part One</h1><p>She didn&rsquo;t <em>know</em>.</p><div class=\"scene-break\">* * *</div><p>Then&nbsp;rain.</p>";
+        let plain = "Part One\n\nShe didn’t know.\n\n* * *\n\nThen rain.";
+
+        assert_eq!(word_count(html, true), 7);
+        assert_eq!(word_count(plain, false), 7);
+        assert_eq!(word_count("", true), 0);
+    }
+
+    #[test]
+    fn test_sentences_guard_abbreviations() {
+        let text = "Mr. Smith met J. R. Jones at St. Paul's. They talked, e.g. about work.";
+        assert_eq!(sentences(text).len(), 2);
+    }
+
+    #[test]
+    fn test_sanitize_html_balances_and_whitelists_tags() {
+        let html = "<p class=\"lead\" onclick=\"steal()\">Hello <em>world<p>Next <b>bold</p></em>\
+                    <script>alert('<p>x</p>')</script><div class='scene-break'>* * *</div>\
+                    <a href=\"javascript:void(0)\">link</a> 3 < 4 &amp; 5 > 2 & more<!-- note --><br/>\
+                    </strong><ul><li>One</ul><img src=x onerror=alert(1)>";
+        assert_eq!(
+            sanitize_html(html),
+            "<p class=\"lead\">Hello <em>world</em></p><p>Next <b>bold</b></p>\
+             <div class=\"scene-break\">* * *</div>link 3 &lt; 4 &amp; 5 &gt; 2 &amp; more<br>\
+             <ul><li>One</li></ul>"
+        );
+
+        assert_eq!(sanitize_html("<blockquote><P>Cut <i>off"), "<blockquote><p>Cut <i>off</i></p></blockquote>");
+        assert_eq!(sanitize_html("<p>Dangling <"), "<p>Dangling &lt;</p>");
+        assert_eq!(sanitize_html("<p>Kept</p><style>p { color: red }"), "<p>Kept</p>");
+    }
+}
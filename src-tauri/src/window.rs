@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Manager, WebviewWindowBuilder};
+use tauri::{AppHandle, Emitter, Manager, State, WebviewWindowBuilder};
 use std::collections::HashMap;
+use std::sync::Mutex;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct WindowConfig {
@@ -261,4 +262,190 @@ pub async fn get_window_info(
 pub async fn list_windows(app_handle: AppHandle) -> Result<Vec<String>, String> {
     let windows: Vec<String> = app_handle.webview_windows().keys().cloned().collect();
     Ok(windows)
-}
\ No newline at end of file
+}
+
+pub const MAIN_WINDOW_LABEL: &str = "main";
+pub const SPLIT_VIEW_LABEL: &str = "split_view";
+
+// Secondary windows the app arranges itself, keyed by label. The main window's
+// bounds from before a split are kept so closing the split can restore them.
+#[derive(Default)]
+pub struct WindowRegistry {
+    windows: Mutex<HashMap<String, ManagedWindow>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManagedWindow {
+    pub kind: &'static str,
+    pub restore_main: Option<WindowRect>,
+}
+
+impl WindowRegistry {
+    pub fn register(&self, label: &str, window: ManagedWindow) {
+        self.lock().insert(label.to_string(), window);
+    }
+
+    pub fn unregister(&self, label: &str) -> Option<ManagedWindow> {
+        self.lock().remove(label)
+    }
+
+    pub fn get(&self, label: &str) -> Option<ManagedWindow> {
+        self.lock().get(label).cloned()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, ManagedWindow>> {
+        self.windows.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SplitOrientation {
+    Horizontal, // Panes stacked top and bottom
+    Vertical,   // Panes side by side
+}
+
+// Physical pixel bounds of a window
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+// Divide the main window's bounds between the main editor (left or top) and
+// the split editor (right or bottom)
+pub fn split_layout(main: WindowRect, orientation: SplitOrientation) -> (WindowRect, WindowRect) {
+    match orientation {
+        SplitOrientation::Vertical => {
+            let first = (main.width / 2.0).floor();
+            (
+                WindowRect { width: first, ..main },
+                WindowRect { x: main.x + first, width: main.width - first, ..main },
+            )
+        }
+        SplitOrientation::Horizontal => {
+            let first = (main.height / 2.0).floor();
+            (
+                WindowRect { height: first, ..main },
+                WindowRect { y: main.y + first, height: main.height - first, ..main },
+            )
+        }
+    }
+}
+
+fn window_rect(window: &tauri::WebviewWindow) -> Result<WindowRect, String> {
+    let position = window.outer_position().map_err(|e| e.to_string())?;
+    let size = window.outer_size().map_err(|e| e.to_string())?;
+    Ok(WindowRect {
+        x: position.x as f64,
+        y: position.y as f64,
+        width: size.width as f64,
+        height: size.height as f64,
+    })
+}
+
+fn apply_rect(window: &tauri::WebviewWindow, rect: WindowRect) -> Result<(), String> {
+    window.set_position(tauri::Position::Physical(tauri::PhysicalPosition { x: rect.x as i32, y: rect.y as i32 }))
+        .map_err(|e| e.to_string())?;
+    window.set_size(tauri::Size::Physical(tauri::PhysicalSize { width: rect.width as u32, height: rect.height as u32 }))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn open_split_view(
+    app_handle: AppHandle,
+    registry: State<'_, WindowRegistry>,
+    orientation: SplitOrientation,
+    scene_id: Option<String>,
+) -> Result<(), String> {
+    let main_window = app_handle.get_webview_window(MAIN_WINDOW_LABEL)
+        .ok_or_else(|| "Main window not found".to_string())?;
+
+    // Lay out from the bounds the main window had before any split
+    let main_bounds = match registry.get(SPLIT_VIEW_LABEL).and_then(|w| w.restore_main) {
+        Some(bounds) => bounds,
+        None => window_rect(&main_window)?,
+    };
+    let (main_rect, split_rect) = split_layout(main_bounds, orientation);
+
+    if let Some(window) = app_handle.get_webview_window(SPLIT_VIEW_LABEL) {
+        // Already open: rearrange and point it at the requested scene
+        apply_rect(&window, split_rect)?;
+        if let Some(scene_id) = &scene_id {
+            app_handle.emit_to(SPLIT_VIEW_LABEL, "split-view-scene", scene_id).map_err(|e| e.to_string())?;
+        }
+    } else {
+        let url = match &scene_id {
+            Some(scene_id) => format!("/split-view?scene={}", scene_id),
+            None => "/split-view".to_string(),
+        };
+
+        let window = WebviewWindowBuilder::new(&app_handle, SPLIT_VIEW_LABEL, tauri::WebviewUrl::App(url.into()))
+        .title("Narrative Surgeon - Split View")
+        .resizable(true)
+        .build()
+        .map_err(|e| e.to_string())?;
+        apply_rect(&window, split_rect)?;
+    }
+
+    apply_rect(&main_window, main_rect)?;
+    registry.register(SPLIT_VIEW_LABEL, ManagedWindow {
+        kind: "split_view",
+        restore_main: Some(main_bounds),
+    });
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn close_split_view(
+    app_handle: AppHandle,
+    registry: State<'_, WindowRegistry>,
+) -> Result<(), String> {
+    if let Some(window) = app_handle.get_webview_window(SPLIT_VIEW_LABEL) {
+        window.close().map_err(|e| e.to_string())?;
+    }
+
+    // Give the main window its full bounds back
+    if let Some(restore) = registry.unregister(SPLIT_VIEW_LABEL).and_then(|w| w.restore_main) {
+        if let Some(main_window) = app_handle.get_webview_window(MAIN_WINDOW_LABEL) {
+            apply_rect(&main_window, restore)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAIN: WindowRect = WindowRect { x: 100.0, y: 50.0, width: 1401.0, height: 900.0 };
+
+    #[test]
+    fn test_vertical_split_places_editor_to_the_right() {
+        let (main, split) = split_layout(MAIN, SplitOrientation::Vertical);
+        assert_eq!(main, WindowRect { x: 100.0, y: 50.0, width: 700.0, height: 900.0 });
+        assert_eq!(split, WindowRect { x: 800.0, y: 50.0, width: 701.0, height: 900.0 });
+    }
+
+    #[test]
+    fn test_horizontal_split_places_editor_below() {
+        let (main, split) = split_layout(MAIN, SplitOrientation::Horizontal);
+        assert_eq!(main, WindowRect { x: 100.0, y: 50.0, width: 1401.0, height: 450.0 });
+        assert_eq!(split, WindowRect { x: 100.0, y: 500.0, width: 1401.0, height: 450.0 });
+        assert_eq!(main.height + split.height, MAIN.height);
+    }
+
+    #[test]
+    fn test_registry_keeps_bounds_until_unregistered() {
+        let registry = WindowRegistry::default();
+        registry.register(SPLIT_VIEW_LABEL, ManagedWindow { kind: "split_view", restore_main: Some(MAIN) });
+
+        assert_eq!(registry.get(SPLIT_VIEW_LABEL).unwrap().restore_main, Some(MAIN));
+        assert_eq!(registry.unregister(SPLIT_VIEW_LABEL).unwrap().restore_main, Some(MAIN));
+        assert!(registry.get(SPLIT_VIEW_LABEL).is_none());
+    }
+}
@@ -200,6 +200,7 @@ mod database_tests {
             initial_delay_ms: 10,
             max_delay_ms: 100,
             backoff_multiplier: 2.0,
+            ..RetryConfig::default()
         };
 
         let result = retry_with_backoff(
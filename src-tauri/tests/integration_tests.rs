@@ -252,6 +252,7 @@ mod integration_tests {
             initial_delay_ms: 1,
             max_delay_ms: 10,
             backoff_multiplier: 2.0,
+            ..RetryConfig::default()
         };
         
         let result = retry_with_backoff(